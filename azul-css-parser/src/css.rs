@@ -9,10 +9,11 @@ use simplecss::Tokenizer;
 use css_parser;
 pub use css_parser::CssParsingError;
 use azul_css::{
-    Css, CssDeclaration, Stylesheet,
+    Css, CssDeclaration, Stylesheet, CssSourceLocation,
     DynamicCssProperty, DynamicCssPropertyDefault,
     CssPropertyType, CssRuleBlock, CssPath, CssPathSelector,
     CssNthChildSelector, CssPathPseudoSelector, CssNthChildSelector::*,
+    CssScrollbarPseudoElement, CssWideKeyword,
     NodeTypePath, NodeTypePathParseError,
 };
 
@@ -104,6 +105,13 @@ fn pseudo_selector_from_str<'a>(selector: &'a str, value: Option<&'a str>)
         "hover" => Ok(CssPathPseudoSelector::Hover),
         "active" => Ok(CssPathPseudoSelector::Active),
         "focus" => Ok(CssPathPseudoSelector::Focus),
+        "-webkit-scrollbar" => Ok(CssPathPseudoSelector::Scrollbar(CssScrollbarPseudoElement::Scrollbar)),
+        "-webkit-scrollbar-track" => Ok(CssPathPseudoSelector::Scrollbar(CssScrollbarPseudoElement::Track)),
+        "-webkit-scrollbar-track-piece" => Ok(CssPathPseudoSelector::Scrollbar(CssScrollbarPseudoElement::TrackPiece)),
+        "-webkit-scrollbar-thumb" => Ok(CssPathPseudoSelector::Scrollbar(CssScrollbarPseudoElement::Thumb)),
+        "-webkit-scrollbar-button" => Ok(CssPathPseudoSelector::Scrollbar(CssScrollbarPseudoElement::Button)),
+        "-webkit-scrollbar-corner" => Ok(CssPathPseudoSelector::Scrollbar(CssScrollbarPseudoElement::Corner)),
+        "-webkit-resizer" => Ok(CssPathPseudoSelector::Scrollbar(CssScrollbarPseudoElement::Resizer)),
         "nth-child" => {
             let value = value.ok_or(CssPseudoSelectorParseError::EmptyNthChild)?;
             let parsed = parse_nth_child_selector(value)?;
@@ -161,7 +169,7 @@ fn parse_nth_child_pattern<'a>(value: &'a str) -> Result<CssNthChildSelector, Cs
 
     let offset = match offset_iterator.next() {
         Some(offset_string) => {
-            offset_string.trim();
+            let offset_string = offset_string.trim();
             if offset_string.is_empty() {
                 return Err(CssPseudoSelectorParseError::InvalidNthChildPattern(value));
             } else {
@@ -191,6 +199,10 @@ fn test_css_pseudo_selector_parse() {
         (("nth-child", Some("odd")), NthChild(Odd)),
         (("nth-child", Some("5n")), NthChild(Pattern { repeat: 5, offset: 0 })),
         (("nth-child", Some("2n+3")), NthChild(Pattern { repeat: 2, offset: 3 })),
+        (("-webkit-scrollbar", None), Scrollbar(CssScrollbarPseudoElement::Scrollbar)),
+        (("-webkit-scrollbar-thumb", None), Scrollbar(CssScrollbarPseudoElement::Thumb)),
+        (("-webkit-scrollbar-track-piece", None), Scrollbar(CssScrollbarPseudoElement::TrackPiece)),
+        (("-webkit-resizer", None), Scrollbar(CssScrollbarPseudoElement::Resizer)),
     ];
 
     let err = [
@@ -222,38 +234,69 @@ impl<'a> fmt::Display for CssParseError<'a> {
     }
 }
 
+/// Error that was skipped during `new_from_str_lenient`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CssParseWarning<'a> {
+    pub warning: CssParseErrorInner<'a>,
+    pub location: ErrorLocation,
+}
+
+impl<'a> fmt::Display for CssParseWarning<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CSS warning at line {}:{}: {}", self.location.line, self.location.column, self.warning)
+    }
+}
+
 pub fn new_from_str<'a>(css_string: &'a str) -> Result<Css, CssParseError<'a>> {
+    new_from_str_with_file_name(css_string, None)
+}
+
+/// Same as `new_from_str`, but records the file name in the source locations
+/// of the parsed rules, so that they can point back to the original file
+pub fn new_from_str_with_file_name<'a>(css_string: &'a str, file_name: Option<&str>) -> Result<Css, CssParseError<'a>> {
     let mut tokenizer = Tokenizer::new(css_string);
-    match new_from_str_inner(css_string, &mut tokenizer) {
+    match new_from_str_inner(css_string, file_name, &mut tokenizer, None) {
         Ok(stylesheet) => Ok(Css {
             stylesheets: vec![
                 stylesheet
             ],
         }),
         Err(e) => {
-            let error_location = tokenizer.pos().saturating_sub(1);
-            let line_number: usize = css_string[0..error_location].lines().count();
-
-            // Rust doesn't count "\n" as a character, so we have to add the line number count on top
-            let total_characters: usize = css_string[0..error_location].lines().take(line_number.saturating_sub(1)).map(|line| line.chars().count()).sum();
-            let total_characters = total_characters + line_number;
-            /*println!("line_number: {} error location: {}, total characters: {}", line_number,
-                     error_location, total_characters);*/
-            let characters_in_line = (error_location + 2) - total_characters;
-
-            let error_location = ErrorLocation {
-                line: line_number,
-                column: characters_in_line,
-            };
-
             Err(CssParseError {
                 error: e,
-                location: error_location,
+                location: get_error_location(css_string, tokenizer.pos()),
             })
         }
     }
 }
 
+/// Parses a CSS string the way a browser would: Invalid declarations or rule blocks
+/// are skipped instead of discarding the whole stylesheet. Returns the parsed `Css`
+/// together with all errors that were encountered on the way.
+pub fn new_from_str_lenient<'a>(css_string: &'a str, file_name: Option<&str>) -> (Css, Vec<CssParseWarning<'a>>) {
+    let mut tokenizer = Tokenizer::new(css_string);
+    let mut warnings = Vec::new();
+    let stylesheet = new_from_str_inner(css_string, file_name, &mut tokenizer, Some(&mut warnings))
+        .unwrap_or_default(); // lenient parsing never returns an error
+    (Css { stylesheets: vec![stylesheet] }, warnings)
+}
+
+/// Calculates the line and column from the position of the tokenizer after an error
+fn get_error_location(css_string: &str, tokenizer_pos: usize) -> ErrorLocation {
+    let error_location = tokenizer_pos.saturating_sub(1).min(css_string.len());
+    let line_number: usize = css_string[0..error_location].lines().count();
+
+    // Rust doesn't count "\n" as a character, so we have to add the line number count on top
+    let total_characters: usize = css_string[0..error_location].lines().take(line_number.saturating_sub(1)).map(|line| line.chars().count()).sum();
+    let total_characters = total_characters + line_number;
+    let characters_in_line = (error_location + 2).saturating_sub(total_characters);
+
+    ErrorLocation {
+        line: line_number,
+        column: characters_in_line,
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum CssPathParseError<'a> {
     EmptyPath,
@@ -349,111 +392,299 @@ pub fn parse_css_path<'a>(input: &'a str) -> Result<CssPath, CssPathParseError<'
     }
 }
 
+/// Returns the byte offsets at which the lines of the string start, for `get_source_location`
+fn get_line_starts(css_string: &str) -> Vec<usize> {
+    let mut line_starts = vec![0];
+    line_starts.extend(css_string.match_indices('\n').map(|(pos, _)| pos + 1));
+    line_starts
+}
+
+/// Returns the source location of the next token, starting at the byte offset `pos`
+/// (skips any whitespace, comments and semicolons in front of the token).
+///
+/// `line_starts` has to be the result of `get_line_starts(css_string)`, so that
+/// the line doesn't have to be searched from the start of the file for every token.
+fn get_source_location(css_string: &str, line_starts: &[usize], file_name: Option<&str>, pos: usize) -> CssSourceLocation {
+
+    let mut pos = pos.min(css_string.len());
+
+    loop {
+        let rest = &css_string[pos..];
+        let trimmed = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ';');
+        pos += rest.len() - trimmed.len();
+        if trimmed.starts_with("/*") {
+            pos += trimmed.find("*/").map(|end| end + 2).unwrap_or(trimmed.len());
+        } else {
+            break;
+        }
+    }
+
+    // line_starts[0] is always 0, so `Err(0)` can't happen
+    let line_index = match line_starts.binary_search(&pos) {
+        Ok(index) => index,
+        Err(index) => index - 1,
+    };
+    let line = line_index + 1;
+    let column = css_string[line_starts[line_index]..pos].chars().count() + 1;
+
+    CssSourceLocation {
+        file_name: file_name.map(|f| f.to_string()),
+        line,
+        column,
+    }
+}
+
+/// A rule block that is currently being parsed. Nested blocks (`.a { .b { } }`)
+/// are pushed on top of the block they are nested in.
+struct OpenRuleBlock {
+    /// Paths of the block, already combined with the paths of the parent blocks
+    paths: Vec<(CssPath, Option<CssSourceLocation>)>,
+    declarations: Vec<CssDeclaration>,
+    declaration_locations: Vec<CssSourceLocation>,
+    /// Where the rules of this block are inserted into the finished blocks, so that
+    /// the rules of a block always come before the rules of its nested blocks
+    insert_position: usize,
+    /// Set if the block contains nested blocks
+    has_nested_blocks: bool,
+    /// Set if one selector of the block (or of a parent block) is invalid
+    skip: bool,
+}
+
+impl OpenRuleBlock {
+    fn into_rule_blocks(self) -> Vec<CssRuleBlock> {
+        let OpenRuleBlock { paths, declarations, declaration_locations, .. } = self;
+        paths.into_iter().map(|(path, location)| CssRuleBlock {
+            path,
+            declarations: declarations.clone(),
+            location,
+            declaration_locations: declaration_locations.clone(),
+        }).collect()
+    }
+}
+
+/// Combines the selectors of a nested block with a path of its parent block.
+///
+/// `parent_references` are the positions in `selectors` where a `&` was written, which is
+/// replaced by the parent path (`.a { &:hover { } }` is `.a:hover`). Selectors without `&`
+/// select the children of the parent (`.a { .b { } }` is `.a .b`, `.a { > .b { } }` is `.a > .b`).
+fn combine_nested_path(parent: &CssPath, selectors: &[CssPathSelector], parent_references: &[usize]) -> CssPath {
+
+    let mut combined = Vec::new();
+
+    if parent_references.is_empty() {
+        combined.extend(parent.selectors.iter().cloned());
+        match selectors.first() {
+            Some(CssPathSelector::Children) | Some(CssPathSelector::DirectChildren) => { },
+            _ => combined.push(CssPathSelector::Children),
+        }
+        combined.extend(selectors.iter().cloned());
+    } else {
+        for position in 0..=selectors.len() {
+            for _ in parent_references.iter().filter(|p| **p == position) {
+                combined.extend(parent.selectors.iter().cloned());
+            }
+            if let Some(selector) = selectors.get(position) {
+                combined.push(selector.clone());
+            }
+        }
+    }
+
+    CssPath { selectors: combined }
+}
+
 /// Parses a CSS string (single-threaded) and returns the parsed rules in blocks
-fn new_from_str_inner<'a>(css_string: &'a str, tokenizer: &mut Tokenizer<'a>) -> Result<Stylesheet, CssParseErrorInner<'a>> {
+///
+/// Nested blocks (`.toolbar { .button { } &:hover { } }`) are flattened into ordinary blocks,
+/// see `combine_nested_path`.
+///
+/// If `warnings` is set, the parser works like a browser: invalid declarations and rule blocks
+/// are skipped and the errors are pushed to `warnings` instead of aborting the parsing.
+fn new_from_str_inner<'a>(
+    css_string: &'a str,
+    file_name: Option<&str>,
+    tokenizer: &mut Tokenizer<'a>,
+    mut warnings: Option<&mut Vec<CssParseWarning<'a>>>,
+) -> Result<Stylesheet, CssParseErrorInner<'a>> {
+
     use simplecss::{Token, Combinator};
 
+    // In lenient mode, record the error and continue, otherwise abort parsing
+    macro_rules! warn_or_fail {($error:expr) => ({
+        let error = $error;
+        match warnings.as_mut() {
+            Some(warnings) => warnings.push(CssParseWarning {
+                warning: error,
+                location: get_error_location(css_string, tokenizer.pos()),
+            }),
+            None => return Err(error),
+        }
+    })}
+
     let mut css_blocks = Vec::new();
 
-    // Used for error checking / checking for closed braces
-    let mut parser_in_block = false;
-    let mut block_nesting = 0_usize;
+    // Blocks that haven't been closed yet, the innermost block is last.
+    // Also used for checking for closed braces.
+    let mut open_blocks = Vec::<OpenRuleBlock>::new();
+    // Set if one selector of the current rule is invalid - browsers drop the whole rule then
+    let mut skip_current_rule = false;
 
     // Current css paths (i.e. `div#id, .class, p` are stored here -
-    // when the block is finished, all `current_rules` gets duplicated with
-    // one path corresponding to one set of rules each).
+    // when the block is started, all paths are combined with the paths
+    // of the parent block, if the block is nested).
     let mut current_paths = Vec::new();
-    // Current CSS declarations
-    let mut current_rules = Vec::new();
     // Keep track of the current path during parsing
     let mut last_path = Vec::new();
+    // Positions in `last_path` where the parent selector `&` was written
+    let mut last_parent_references = Vec::new();
+    // Where the first selector of `last_path` started
+    let mut last_path_location = None;
 
     let css_property_map = azul_css::get_css_key_map();
+    let line_starts = get_line_starts(css_string);
     loop {
-        let token = tokenizer.parse_next()?;
+        let token_start = tokenizer.pos();
+        let token = match tokenizer.parse_next() {
+            Ok(token) => token,
+            Err(e) => {
+                // The tokenizer can't recover from syntax errors, keep what was parsed so far
+                warn_or_fail!(CssParseErrorInner::from(e));
+                break;
+            }
+        };
+
+        // Remember where the path started for the source location of the rule
+        if last_path.is_empty() && last_parent_references.is_empty() {
+            last_path_location = Some(get_source_location(css_string, &line_starts, file_name, token_start));
+        }
+
         match token {
             Token::BlockStart => {
-                if parser_in_block {
-                    // multi-nested CSS blocks are currently not supported
-                    return Err(CssParseErrorInner::MalformedCss);
-                }
-                parser_in_block = true;
-                block_nesting += 1;
-                current_paths.push(last_path.clone());
+                current_paths.push((last_path.clone(), last_parent_references.clone(), last_path_location.take()));
                 last_path.clear();
+                last_parent_references.clear();
+
+                let (paths, parent_skip) = match open_blocks.last() {
+                    Some(parent) => {
+                        let paths = parent.paths.iter().flat_map(|(parent_path, _)| {
+                            current_paths.iter().map(move |(path, parent_references, location)| {
+                                (combine_nested_path(parent_path, path, parent_references), location.clone())
+                            })
+                        }).collect();
+                        (paths, parent.skip)
+                    },
+                    None => {
+                        if current_paths.iter().any(|(_, parent_references, _)| !parent_references.is_empty()) {
+                            // `&` outside of a nested block
+                            warn_or_fail!(CssParseErrorInner::MalformedCss);
+                            skip_current_rule = true;
+                        }
+                        let paths = current_paths.iter().map(|(path, _, location)| {
+                            (CssPath { selectors: path.clone() }, location.clone())
+                        }).collect();
+                        (paths, false)
+                    },
+                };
+
+                if let Some(parent) = open_blocks.last_mut() {
+                    parent.has_nested_blocks = true;
+                }
+
+                open_blocks.push(OpenRuleBlock {
+                    paths,
+                    declarations: Vec::new(),
+                    declaration_locations: Vec::new(),
+                    insert_position: css_blocks.len(),
+                    has_nested_blocks: false,
+                    skip: parent_skip || skip_current_rule,
+                });
+
+                skip_current_rule = false;
+                current_paths.clear();
             },
             Token::Comma => {
-                current_paths.push(last_path.clone());
+                current_paths.push((last_path.clone(), last_parent_references.clone(), last_path_location.take()));
                 last_path.clear();
+                last_parent_references.clear();
             },
             Token::BlockEnd => {
-                block_nesting -= 1;
-                if !parser_in_block {
-                    return Err(CssParseErrorInner::MalformedCss);
-                }
-                parser_in_block = false;
-                for path in current_paths.drain(..) {
-                    css_blocks.push(CssRuleBlock {
-                        path: CssPath { selectors: path },
-                        declarations: current_rules.clone(),
-                    })
+                let block = match open_blocks.pop() {
+                    Some(block) => block,
+                    None => {
+                        warn_or_fail!(CssParseErrorInner::MalformedCss);
+                        continue;
+                    },
+                };
+                // Blocks that only contain nested blocks don't need a rule of their own
+                if !block.skip && !(block.has_nested_blocks && block.declarations.is_empty()) {
+                    let insert_position = block.insert_position;
+                    css_blocks.splice(insert_position..insert_position, block.into_rule_blocks());
                 }
-                current_rules.clear();
+                skip_current_rule = false;
+                current_paths.clear();
                 last_path.clear(); // technically unnecessary, but just to be sure
+                last_parent_references.clear();
             },
 
             // tokens that adjust the last_path
             Token::UniversalSelector => {
-                if parser_in_block {
-                    return Err(CssParseErrorInner::MalformedCss);
-                }
                 last_path.push(CssPathSelector::Global);
             },
+            Token::TypeSelector("&") => {
+                last_parent_references.push(last_path.len());
+            },
             Token::TypeSelector(div_type) => {
-                if parser_in_block {
-                    return Err(CssParseErrorInner::MalformedCss);
+                match NodeTypePath::from_str(div_type) {
+                    Ok(node_type) => last_path.push(CssPathSelector::Type(node_type)),
+                    Err(e) => {
+                        warn_or_fail!(CssParseErrorInner::from(e));
+                        skip_current_rule = true;
+                    },
                 }
-                last_path.push(CssPathSelector::Type(NodeTypePath::from_str(div_type)?));
             },
             Token::IdSelector(id) => {
-                if parser_in_block {
-                    return Err(CssParseErrorInner::MalformedCss);
-                }
                 last_path.push(CssPathSelector::Id(id.to_string()));
             },
             Token::ClassSelector(class) => {
-                if parser_in_block {
-                    return Err(CssParseErrorInner::MalformedCss);
-                }
                 last_path.push(CssPathSelector::Class(class.to_string()));
             },
             Token::Combinator(Combinator::GreaterThan) => {
-                if parser_in_block {
-                    return Err(CssParseErrorInner::MalformedCss);
-                }
                 last_path.push(CssPathSelector::DirectChildren);
             },
             Token::Combinator(Combinator::Space) => {
-                if parser_in_block {
-                    return Err(CssParseErrorInner::MalformedCss);
-                }
                 last_path.push(CssPathSelector::Children);
             },
             Token::PseudoClass { selector, value } => {
-                if parser_in_block {
-                    return Err(CssParseErrorInner::MalformedCss);
+                match pseudo_selector_from_str(selector, value) {
+                    Ok(pseudo_selector) => last_path.push(CssPathSelector::PseudoSelector(pseudo_selector)),
+                    Err(e) => {
+                        warn_or_fail!(CssParseErrorInner::from(e));
+                        skip_current_rule = true;
+                    },
                 }
-                last_path.push(CssPathSelector::PseudoSelector(pseudo_selector_from_str(selector, value)?));
             },
             Token::Declaration(key, val) => {
-                if !parser_in_block {
-                    return Err(CssParseErrorInner::MalformedCss);
+                if open_blocks.is_empty() {
+                    warn_or_fail!(CssParseErrorInner::MalformedCss);
+                    continue;
                 }
 
-                let parsed_key = CssPropertyType::from_str(key, &css_property_map)
-                    .ok_or(CssParseErrorInner::UnknownPropertyKey(key, val))?;
-
-                current_rules.push(determine_static_or_dynamic_css_property(parsed_key, val)?);
+                let parsed_key = match CssPropertyType::from_str(key, &css_property_map) {
+                    Some(k) => k,
+                    None => {
+                        warn_or_fail!(CssParseErrorInner::UnknownPropertyKey(key, val));
+                        continue;
+                    }
+                };
+
+                match determine_static_or_dynamic_css_property(parsed_key, val) {
+                    Ok(declaration) => {
+                        let location = get_source_location(css_string, &line_starts, file_name, token_start);
+                        let block = open_blocks.last_mut().unwrap();
+                        block.declarations.push(declaration);
+                        block.declaration_locations.push(location);
+                    },
+                    Err(e) => warn_or_fail!(CssParseErrorInner::from(e)),
+                }
             },
             Token::EndOfStream => {
                 break;
@@ -465,8 +696,15 @@ fn new_from_str_inner<'a>(css_string: &'a str, tokenizer: &mut Tokenizer<'a>) ->
     }
 
     // non-even number of blocks
-    if block_nesting != 0 {
-        return Err(CssParseErrorInner::UnclosedBlock);
+    if !open_blocks.is_empty() {
+        warn_or_fail!(CssParseErrorInner::UnclosedBlock);
+        // Browsers close all open blocks at the end of the stylesheet
+        while let Some(block) = open_blocks.pop() {
+            if !block.skip && !(block.has_nested_blocks && block.declarations.is_empty()) {
+                let insert_position = block.insert_position;
+                css_blocks.splice(insert_position..insert_position, block.into_rule_blocks());
+            }
+        }
     }
 
     Ok(css_blocks.into())
@@ -508,12 +746,36 @@ pub const START_BRACE: &str = "[[";
 pub const END_BRACE: &str = "]]";
 
 /// Determine if a Css property is static (immutable) or if it can change
-/// during the runtime of the program
+/// during the runtime of the program. Values ending with `!important`
+/// return a `CssDeclaration::Important`.
 pub fn determine_static_or_dynamic_css_property<'a>(key: CssPropertyType, value: &'a str)
 -> Result<CssDeclaration, DynamicCssParseError<'a>>
 {
+    let (value, is_important) = split_important_flag(value);
+    let declaration = determine_static_or_dynamic_css_property_inner(key, value)?;
+    Ok(if is_important { declaration.into_important() } else { declaration })
+}
+
+/// Splits `"5px !important"` into `("5px", true)`
+fn split_important_flag(value: &str) -> (&str, bool) {
+    const IMPORTANT: &str = "important";
+
     let value = value.trim();
+    let split_pos = value.len().saturating_sub(IMPORTANT.len());
 
+    if value.is_char_boundary(split_pos) && value[split_pos..].eq_ignore_ascii_case(IMPORTANT) {
+        let before = value[..split_pos].trim_end();
+        if before.ends_with('!') {
+            return (before[..before.len() - 1].trim_end(), true);
+        }
+    }
+
+    (value, false)
+}
+
+fn determine_static_or_dynamic_css_property_inner<'a>(key: CssPropertyType, value: &'a str)
+-> Result<CssDeclaration, DynamicCssParseError<'a>>
+{
     let is_starting_with_braces = value.starts_with(START_BRACE);
     let is_ending_with_braces = value.ends_with(END_BRACE);
 
@@ -525,11 +787,28 @@ pub fn determine_static_or_dynamic_css_property<'a>(key: CssPropertyType, value:
             parse_dynamic_css_property(key, value).and_then(|val| Ok(CssDeclaration::Dynamic(val)))
         },
         (false, false) => {
-            Ok(CssDeclaration::Static(css_parser::parse_key_value_pair(key, value)?))
+            if let Some(keyword) = parse_css_wide_keyword(value) {
+                Ok(CssDeclaration::Keyword(key, keyword))
+            } else if key == CssPropertyType::TextColor && value.eq_ignore_ascii_case("currentcolor") {
+                // `color: currentColor` is the text color of the parent
+                Ok(CssDeclaration::Keyword(key, CssWideKeyword::Inherit))
+            } else if let Some(property) = css_parser::parse_current_color_property(key, value) {
+                Ok(CssDeclaration::CurrentColor(property?))
+            } else {
+                Ok(CssDeclaration::Static(css_parser::parse_key_value_pair(key, value)?))
+            }
         }
     }
 }
 
+/// Parses the `inherit`, `initial` and `unset` keywords, which are valid for every property
+fn parse_css_wide_keyword(value: &str) -> Option<CssWideKeyword> {
+    use azul_css::CssWideKeyword::*;
+    [("inherit", Inherit), ("initial", Initial), ("unset", Unset)].iter()
+        .find(|(name, _)| value.eq_ignore_ascii_case(name))
+        .map(|(_, keyword)| *keyword)
+}
+
 pub fn parse_dynamic_css_property<'a>(key: CssPropertyType, value: &'a str) -> Result<DynamicCssProperty, DynamicCssParseError<'a>> {
     use std::char;
 
@@ -672,8 +951,8 @@ fn test_css_parse_1() {
     ").unwrap();
 
     let expected_css_rules = vec![
-        CssRuleBlock {
-            path: CssPath {
+        CssRuleBlock::new(
+            CssPath {
                 selectors: vec![
                     CssPathSelector::Type(NodeTypePath::Div),
                     CssPathSelector::Id(String::from("my_id")),
@@ -684,8 +963,8 @@ fn test_css_parse_1() {
                     CssPathSelector::PseudoSelector(CssPathPseudoSelector::First),
                 ],
             },
-            declarations: vec![CssDeclaration::Static(CssProperty::BackgroundColor(StyleBackgroundColor(ColorU { r: 255, g: 0, b: 0, a: 255 })))],
-        }
+            vec![CssDeclaration::Static(CssProperty::BackgroundColor(StyleBackgroundColor(ColorU { r: 255, g: 0, b: 0, a: 255 })))],
+        )
     ];
 
     assert_eq!(parsed_css, Css { stylesheets: vec![expected_css_rules.into()] });
@@ -707,14 +986,30 @@ fn test_css_simple_selector_parse() {
     ];
     assert_eq!(new_from_str(css).unwrap(), Css {
         stylesheets: vec![Stylesheet {
-            rules: vec![CssRuleBlock {
-                path: CssPath { selectors: parsed },
-                declarations: Vec::new(),
-            }],
+            rules: vec![CssRuleBlock::new(
+                CssPath { selectors: parsed },
+                Vec::new(),
+            )],
         }],
     });
 }
 
+#[test]
+fn test_css_scrollbar_selector_parse() {
+    use self::CssPathSelector::*;
+    let css = ".list::-webkit-scrollbar-thumb { }";
+    let path = CssPath { selectors: vec![
+        Class("list".into()),
+        PseudoSelector(CssPathPseudoSelector::Scrollbar(CssScrollbarPseudoElement::Thumb)),
+    ]};
+    assert_eq!(new_from_str(css).unwrap().stylesheets[0].rules[0].path, path);
+    assert_eq!(path.split_scrollbar_pseudo_element(), Some((
+        CssScrollbarPseudoElement::Thumb,
+        CssPath { selectors: vec![Class("list".into())] },
+    )));
+    assert_eq!(format!("{}", path), ".list::-webkit-scrollbar-thumb");
+}
+
 #[cfg(test)]
 mod stylesheet_parse {
 
@@ -737,12 +1032,12 @@ mod stylesheet_parse {
         {
             let css_1 = ".my_class { background-color: red; }";
             let expected_rules = vec![
-                CssRuleBlock {
-                    path: CssPath { selectors: vec![CssPathSelector::Class("my_class".into())] },
-                    declarations: vec![
+                CssRuleBlock::new(
+                    CssPath { selectors: vec![CssPathSelector::Class("my_class".into())] },
+                    vec![
                         CssDeclaration::Static(red.clone())
                     ],
-                },
+                ),
             ];
             test_css(css_1, expected_rules);
         }
@@ -751,14 +1046,14 @@ mod stylesheet_parse {
         {
             let css_2 = "#my_id { background-color: red; } .my_class { background-color: blue; }";
             let expected_rules = vec![
-                CssRuleBlock {
-                    path: CssPath { selectors: vec![CssPathSelector::Id("my_id".into())] },
-                    declarations: vec![CssDeclaration::Static(red.clone())]
-                },
-                CssRuleBlock {
-                    path: CssPath { selectors: vec![CssPathSelector::Class("my_class".into())] },
-                    declarations: vec![CssDeclaration::Static(blue.clone())]
-                },
+                CssRuleBlock::new(
+                    CssPath { selectors: vec![CssPathSelector::Id("my_id".into())] },
+                    vec![CssDeclaration::Static(red.clone())],
+                ),
+                CssRuleBlock::new(
+                    CssPath { selectors: vec![CssPathSelector::Class("my_class".into())] },
+                    vec![CssDeclaration::Static(blue.clone())],
+                ),
             ];
             test_css(css_2, expected_rules);
         }
@@ -767,18 +1062,18 @@ mod stylesheet_parse {
         {
             let css_3 = "* { background-color: black; } .my_class#my_id { background-color: red; } .my_class { background-color: blue; }";
             let expected_rules = vec![
-                CssRuleBlock {
-                    path: CssPath { selectors: vec![CssPathSelector::Global] },
-                    declarations: vec![CssDeclaration::Static(black.clone())]
-                },
-                CssRuleBlock {
-                    path: CssPath { selectors: vec![CssPathSelector::Class("my_class".into()), CssPathSelector::Id("my_id".into())] },
-                    declarations: vec![CssDeclaration::Static(red.clone())]
-                },
-                CssRuleBlock {
-                    path: CssPath { selectors: vec![CssPathSelector::Class("my_class".into())] },
-                    declarations: vec![CssDeclaration::Static(blue.clone())]
-                },
+                CssRuleBlock::new(
+                    CssPath { selectors: vec![CssPathSelector::Global] },
+                    vec![CssDeclaration::Static(black.clone())],
+                ),
+                CssRuleBlock::new(
+                    CssPath { selectors: vec![CssPathSelector::Class("my_class".into()), CssPathSelector::Id("my_id".into())] },
+                    vec![CssDeclaration::Static(red.clone())],
+                ),
+                CssRuleBlock::new(
+                    CssPath { selectors: vec![CssPathSelector::Class("my_class".into())] },
+                    vec![CssDeclaration::Static(blue.clone())],
+                ),
             ];
             test_css(css_3, expected_rules);
         }
@@ -801,11 +1096,11 @@ fn test_multiple_rules() {
 
     let expected_rules = vec![
         // Rules are sorted by order of appearance in source string
-        CssRuleBlock { path: CssPath { selectors: vec![Global] }, declarations: Vec::new() },
-        CssRuleBlock { path: CssPath { selectors: vec![Global, Type(NodeTypePath::Div), Class("my_class".into()), Id("my_id".into())] }, declarations: Vec::new() },
-        CssRuleBlock { path: CssPath { selectors: vec![Global, Type(NodeTypePath::Div), Id("my_id".into())] }, declarations: Vec::new() },
-        CssRuleBlock { path: CssPath { selectors: vec![Global, Id("my_id".into())] }, declarations: Vec::new() },
-        CssRuleBlock { path: CssPath { selectors: vec![Type(NodeTypePath::Div), Class("my_class".into()), Class("specific".into()), Id("my_id".into())] }, declarations: Vec::new() },
+        CssRuleBlock::new(CssPath { selectors: vec![Global] }, Vec::new()),
+        CssRuleBlock::new(CssPath { selectors: vec![Global, Type(NodeTypePath::Div), Class("my_class".into()), Id("my_id".into())] }, Vec::new()),
+        CssRuleBlock::new(CssPath { selectors: vec![Global, Type(NodeTypePath::Div), Id("my_id".into())] }, Vec::new()),
+        CssRuleBlock::new(CssPath { selectors: vec![Global, Id("my_id".into())] }, Vec::new()),
+        CssRuleBlock::new(CssPath { selectors: vec![Type(NodeTypePath::Div), Class("my_class".into()), Class("specific".into()), Id("my_id".into())] }, Vec::new()),
     ];
 
     assert_eq!(parsed_css, Css { stylesheets: vec![expected_rules.into()] });
@@ -832,12 +1127,12 @@ fn test_case_issue_93() {
     ").unwrap();
 
     fn declaration(classes: &[CssPathSelector], color: ColorU) -> CssRuleBlock {
-        CssRuleBlock {
-            path: CssPath { selectors: classes.to_vec() },
-            declarations: vec![
+        CssRuleBlock::new(
+            CssPath { selectors: classes.to_vec() },
+            vec![
                 CssDeclaration::Static(CssProperty::TextColor(StyleTextColor(color))),
             ],
-        }
+        )
     }
 
     let expected_rules = vec![
@@ -847,4 +1142,325 @@ fn test_case_issue_93() {
     ];
 
     assert_eq!(parsed_css, Css { stylesheets: vec![expected_rules.into()] });
-}
\ No newline at end of file
+}
+#[test]
+fn test_css_source_locations() {
+    let css = "/* comment */\n.a, #b {\n    width: 5px;\n    color: red;\n}\n\n  div { height: 10px; }";
+    let parsed = new_from_str_with_file_name(css, Some("style.css")).unwrap();
+    let rules = &parsed.stylesheets[0].rules;
+
+    let loc = |line, column| CssSourceLocation { file_name: Some("style.css".into()), line, column };
+
+    assert_eq!(rules.len(), 3);
+    assert_eq!(rules[0].location, Some(loc(2, 1)));
+    assert_eq!(rules[1].location, Some(loc(2, 5)));
+    assert_eq!(rules[0].declaration_locations, vec![loc(3, 5), loc(4, 5)]);
+    assert_eq!(rules[1].get_declaration_location(1), Some(&loc(4, 5)));
+    assert_eq!(rules[2].location, Some(loc(7, 3)));
+    assert_eq!(rules[2].declaration_locations, vec![loc(7, 9)]);
+    assert_eq!(format!("{}", loc(7, 9)), "style.css:7:9");
+}
+
+#[test]
+fn test_css_parse_lenient() {
+    use azul_css::*;
+
+    let css = "
+        .a { width: 5px; colr: red; height: 10px; }
+        .b:unknown, .c { width: 5px; }
+        .d { width: abc; color: red; }
+        .e { height: 1px;
+    ";
+
+    let (parsed, warnings) = new_from_str_lenient(css, None);
+    let expected_rules = vec![
+        CssRuleBlock::new(
+            CssPath { selectors: vec![CssPathSelector::Class("a".into())] },
+            vec![
+                CssDeclaration::Static(CssProperty::Width(LayoutWidth(PixelValue::px(5.0)))),
+                CssDeclaration::Static(CssProperty::Height(LayoutHeight(PixelValue::px(10.0)))),
+            ],
+        ),
+        CssRuleBlock::new(
+            CssPath { selectors: vec![CssPathSelector::Class("d".into())] },
+            vec![CssDeclaration::Static(CssProperty::TextColor(StyleTextColor(ColorU { r: 255, g: 0, b: 0, a: 255 })))],
+        ),
+        CssRuleBlock::new(
+            CssPath { selectors: vec![CssPathSelector::Class("e".into())] },
+            vec![CssDeclaration::Static(CssProperty::Height(LayoutHeight(PixelValue::px(1.0))))],
+        ),
+    ];
+
+    assert_eq!(parsed, Css { stylesheets: vec![expected_rules.into()] });
+    assert_eq!(warnings.len(), 4);
+    assert_eq!(warnings[0].warning, CssParseErrorInner::UnknownPropertyKey("colr", "red"));
+    assert_eq!(warnings[0].location.line, 2);
+    assert_eq!(warnings[1].warning, CssParseErrorInner::PseudoSelectorParseError(CssPseudoSelectorParseError::UnknownSelector("unknown", None)));
+    assert_eq!(warnings[3].warning, CssParseErrorInner::UnclosedBlock);
+
+    // strict parsing still fails on the first error
+    assert!(new_from_str(css).is_err());
+}
+
+#[test]
+fn test_css_parse_nested() {
+    use azul_css::*;
+    use self::CssPathSelector::*;
+
+    let css = "
+        .toolbar, #menu {
+            height: 30px;
+            .button {
+                width: 5px;
+                &:hover { width: 10px; }
+            }
+            > p { width: 1px; }
+            .dark & { height: 20px; }
+        }
+    ";
+
+    let width = |px| vec![CssDeclaration::Static(CssProperty::Width(LayoutWidth::px(px)))];
+    let height = |px| vec![CssDeclaration::Static(CssProperty::Height(LayoutHeight::px(px)))];
+    let rule = |selectors, declarations| CssRuleBlock::new(CssPath { selectors }, declarations);
+    let toolbar = || Class("toolbar".into());
+    let menu = || Id("menu".into());
+    let button = || Class("button".into());
+    let hover = || PseudoSelector(CssPathPseudoSelector::Hover);
+    let dark = || Class("dark".into());
+
+    // parent rules come before the rules of the nested blocks
+    let expected_rules = vec![
+        rule(vec![toolbar()], height(30.0)),
+        rule(vec![menu()], height(30.0)),
+        rule(vec![toolbar(), Children, button()], width(5.0)),
+        rule(vec![menu(), Children, button()], width(5.0)),
+        rule(vec![toolbar(), Children, button(), hover()], width(10.0)),
+        rule(vec![menu(), Children, button(), hover()], width(10.0)),
+        rule(vec![toolbar(), DirectChildren, Type(NodeTypePath::P)], width(1.0)),
+        rule(vec![menu(), DirectChildren, Type(NodeTypePath::P)], width(1.0)),
+        rule(vec![dark(), Children, toolbar()], height(20.0)),
+        rule(vec![dark(), Children, menu()], height(20.0)),
+    ];
+
+    assert_eq!(new_from_str(css).unwrap(), Css { stylesheets: vec![expected_rules.into()] });
+
+    // `&` is only valid inside of a nested block
+    assert!(new_from_str("&:hover { width: 5px; }").is_err());
+}
+
+#[test]
+fn test_css_parse_important() {
+    use azul_css::*;
+
+    let width = CssDeclaration::Static(CssProperty::Width(LayoutWidth(PixelValue::px(5.0))));
+
+    assert_eq!(split_important_flag("5px !important"), ("5px", true));
+    assert_eq!(split_important_flag("5px!IMPORTANT "), ("5px", true));
+    assert_eq!(split_important_flag("5px ! important"), ("5px", true));
+    assert_eq!(split_important_flag("5px"), ("5px", false));
+    assert_eq!(split_important_flag("important"), ("important", false));
+
+    assert_eq!(determine_static_or_dynamic_css_property(CssPropertyType::Width, "5px !important"), Ok(width.clone().into_important()));
+    assert_eq!(determine_static_or_dynamic_css_property(CssPropertyType::Width, "5px"), Ok(width.clone()));
+    assert!(determine_static_or_dynamic_css_property(CssPropertyType::Width, "[[ my_id | 5px ]] !important").unwrap().is_important());
+    assert_eq!(width.clone().into_important().into_important(), width.clone().into_important());
+}
+
+#[test]
+fn test_css_parse_wide_keywords() {
+    use azul_css::CssWideKeyword::*;
+
+    assert_eq!(determine_static_or_dynamic_css_property(CssPropertyType::TextColor, "inherit"), Ok(CssDeclaration::Keyword(CssPropertyType::TextColor, Inherit)));
+    assert_eq!(determine_static_or_dynamic_css_property(CssPropertyType::Width, " INITIAL "), Ok(CssDeclaration::Keyword(CssPropertyType::Width, Initial)));
+    assert_eq!(determine_static_or_dynamic_css_property(CssPropertyType::PaddingTop, "unset !important"), Ok(CssDeclaration::Keyword(CssPropertyType::PaddingTop, Unset).into_important()));
+}
+
+#[test]
+fn test_css_parse_current_color() {
+    use azul_css::{StyleBorderSide, StyleBorder, BorderStyle, PixelValue, ColorU};
+
+    let border_side = StyleBorderSide { border_width: PixelValue::px(1.0), border_style: BorderStyle::Solid, border_color: ColorU { r: 0, g: 0, b: 0, a: 255 } };
+    assert_eq!(determine_static_or_dynamic_css_property(CssPropertyType::Border, "1px solid currentColor"), Ok(CssDeclaration::CurrentColor(StyleBorder::all(border_side).into())));
+    assert_eq!(determine_static_or_dynamic_css_property(CssPropertyType::TextColor, "currentColor"), Ok(CssDeclaration::Keyword(CssPropertyType::TextColor, CssWideKeyword::Inherit)));
+    assert!(determine_static_or_dynamic_css_property(CssPropertyType::Width, "currentColor").is_err());
+}
+
+#[cfg(test)]
+mod print_css_roundtrip {
+
+    use super::*;
+
+    const ROUNDTRIP_CSS: &str = "
+        * {
+            cursor: pointer;
+            font-family: \"Helvetica\", \"Arial\";
+            width: 5px !important;
+            padding: 1px 2px 3px 4px !important;
+        }
+        div#my_id .my_class:first > p:nth-child(2n+1) {
+            border-radius: 5px 10px 15px 20px;
+            background-color: #ff000080;
+            background-size: cover;
+            background-repeat: repeat-x;
+            color: rgb(0, 128, 0);
+            font-size: 1.5em;
+            text-align: left;
+            letter-spacing: 2px;
+            word-spacing: 3pt;
+            line-height: 120%;
+            tab-width: 4;
+            width: 100px;
+            height: 50.5px;
+            min-width: 10px;
+            min-height: 10px;
+            max-width: 1000px;
+            max-height: 1000px;
+            aspect-ratio: 16 / 9;
+            position: absolute;
+            top: 1px;
+            right: 2px;
+            left: 3px;
+            bottom: 4px;
+            flex-wrap: nowrap;
+            flex-direction: row-reverse;
+            flex-grow: 2;
+            flex-shrink: 0.5;
+            justify-content: space-between;
+            align-items: flex-end;
+            align-content: space-around;
+        }
+        .focus_ring {
+            outline: 2px solid #0000ff;
+            outline-offset: 1px;
+        }
+        .outline_parts {
+            outline-style: dotted;
+            outline-color: #ff0000;
+        }
+        .overlay {
+            position: fixed;
+            right: 0px;
+            bottom: 0px;
+        }
+        .reset {
+            color: inherit;
+            width: initial;
+            padding-top: unset !important;
+        }
+        .list_header {
+            position: sticky;
+            top: 0px;
+        }
+        .current_color {
+            color: darken(color-mix(in srgb, red 30%, hwb(240 0% 0%)), 10%);
+            border: 1px solid currentColor;
+            background-color: currentColor;
+            outline-color: currentColor;
+        }
+        .list::-webkit-scrollbar {
+            width: 10px;
+        }
+        .list::-webkit-scrollbar-thumb {
+            background-color: #888888;
+            border-radius: 5px;
+        }
+        .borders {
+            border: 1px solid black;
+            border-top: 2px dashed #ff0000;
+            box-shadow: 0px 0px 5px 2px #00000080 inset;
+            box-shadow-left: none;
+            box-shadow-right: 0px 2px 4px 0px #00000033 outset, 0px 0px 0px 2px #0000ff inset;
+            padding: 1px 2px 3px 4px;
+            padding-left: 5px;
+            margin: 10px;
+            margin-bottom: 0px;
+            overflow: hidden;
+            overflow-y: scroll;
+        }
+        .backgrounds:hover {
+            background: linear-gradient(to bottom right, red 0%, blue 100%);
+        }
+        .backgrounds:active {
+            background: repeating-linear-gradient(45deg, red 0%, blue 10%);
+        }
+        .backgrounds:focus {
+            background: radial-gradient(circle, red 0%, rgba(0, 0, 255, 0.5) 50%, green 100%);
+        }
+        .backgrounds:last {
+            background: repeating-radial-gradient(ellipse, red 0%, blue 20%);
+        }
+        .progress_ring {
+            background: conic-gradient(from 90deg at 10px bottom, red 0%, blue 25%, green 100%);
+        }
+        .busy_bar {
+            background: repeating-conic-gradient(red 0%, blue 10%);
+        }
+        .image {
+            background: image(\"my_image\");
+        }
+        .card {
+            background: image(\"icon\"), linear-gradient(red 0%, blue 100%), #ff0000;
+            background-size: contain, cover;
+            background-repeat: no-repeat, repeat-y;
+            background-position: right top, 10px 20.5px, center bottom;
+            background-origin: content-box, border-box;
+            background-clip: padding-box;
+            background-attachment: fixed, scroll, local;
+        }
+        .filters {
+            filter: blur(5px) grayscale(50%) brightness(1.5) contrast(2) hue-rotate(90deg) invert(1) saturate(0.5) sepia(100%) opacity(0.25);
+            backdrop-filter: drop-shadow(2px 3px 4px rgba(0, 0, 0, 0.5));
+        }
+        .no_filter {
+            filter: none;
+        }
+        .no_background {
+            background: none;
+        }
+        .color_background {
+            background: #abcdef;
+        }
+        .dynamic {
+            width: [[ my_width | 400px ]];
+            padding-top: [[ my_padding | 5px ]];
+            text-align: [[ my_alignment | auto ]];
+            overflow-x: [[ my_overflow | visible ]];
+        }
+    ";
+
+    #[test]
+    fn test_print_css_roundtrip_pretty() {
+        let parsed = new_from_str(ROUNDTRIP_CSS).unwrap();
+        let printed = parsed.to_css_string(true);
+        assert_eq!(new_from_str(&printed).unwrap(), parsed);
+    }
+
+    // Parses the same stylesheet, so that every property type is covered by the serde derives
+    #[cfg(feature = "serde_serialization")]
+    #[test]
+    fn test_serde_json_roundtrip() {
+        use azul_css::Css;
+        let parsed = new_from_str(ROUNDTRIP_CSS).unwrap();
+        let json = ::serde_json::to_string(&parsed).unwrap();
+        let deserialized: Css = ::serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, parsed);
+    }
+
+    #[test]
+    fn test_print_css_roundtrip_minified() {
+        let parsed = new_from_str(ROUNDTRIP_CSS).unwrap();
+        let printed = parsed.to_css_string(false);
+        assert!(!printed.contains('\n'));
+        assert_eq!(new_from_str(&printed).unwrap(), parsed);
+    }
+
+    #[test]
+    fn test_print_css_pretty_format() {
+        let parsed = new_from_str("div#id > .a { width: [[ my_id | 5px ]]; color: red; }").unwrap();
+        assert_eq!(
+            parsed.to_css_string(true),
+            "div#id > .a {\n    width: [[ my_id | 5px ]];\n    color: #ff0000;\n}\n\n"
+        );
+        assert_eq!(parsed.to_css_string(false), "div#id>.a{width:[[my_id|5px]];color:#ff0000;}");
+    }
+}
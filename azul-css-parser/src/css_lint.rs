@@ -0,0 +1,280 @@
+//! Diagnostics for stylesheets: unknown properties (with "did you mean" suggestions),
+//! unsupported values, selectors that can never match and duplicate declarations.
+
+use std::{fmt, collections::BTreeSet};
+use azul_css::{
+    CssPath, CssPathSelector, CssPathPseudoSelector, CssNthChildSelector,
+    CssPropertyType, CssDeclaration, CssRuleBlock, CssSourceLocation,
+    DynamicCssPropertyDefault,
+};
+use css::{self, CssParseErrorInner, DynamicCssParseError, ErrorLocation};
+
+/// One problem found by `lint_css`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CssLintWarning<'a> {
+    pub kind: CssLintWarningKind<'a>,
+    pub location: ErrorLocation,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CssLintWarningKind<'a> {
+    /// Unknown CSS key, with the closest known key, i.e. `backround-color` => `background-color`
+    UnknownProperty { key: &'a str, value: &'a str, suggestion: Option<&'static str> },
+    /// The key is known, but the value can't be parsed or isn't supported
+    UnsupportedValue(DynamicCssParseError<'a>),
+    /// Any other error that makes the parser skip a rule, such as an invalid selector
+    ParseError(CssParseErrorInner<'a>),
+    /// The selector can't match any node in the DOM
+    NeverMatchingSelector { path: CssPath, reason: &'static str },
+    /// The same property is declared twice in one block, only the last one has an effect
+    DuplicateDeclaration(CssPropertyType),
+}
+
+impl<'a> fmt::Display for CssLintWarningKind<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::CssLintWarningKind::*;
+        match self {
+            UnknownProperty { key, value, suggestion: Some(s) } => write!(f, "Unknown CSS key: \"{}: {}\", did you mean \"{}\"?", key, value, s),
+            UnknownProperty { key, value, suggestion: None } => write!(f, "Unknown CSS key: \"{}: {}\"", key, value),
+            UnsupportedValue(e) => write!(f, "Unsupported value: {}", e),
+            ParseError(e) => write!(f, "{}", e),
+            NeverMatchingSelector { path, reason } => write!(f, "Selector \"{}\" can never match: {}", path, reason),
+            DuplicateDeclaration(property_type) => write!(f, "Duplicate declaration of \"{}\", only the last one is used", property_type),
+        }
+    }
+}
+
+impl<'a> fmt::Display for CssLintWarning<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CSS lint at line {}:{}: {}", self.location.line, self.location.column, self.kind)
+    }
+}
+
+/// Parses the stylesheet leniently and returns all problems found in it, sorted by location.
+/// Returns an empty `Vec` if the stylesheet is fine.
+pub fn lint_css<'a>(css_string: &'a str) -> Vec<CssLintWarning<'a>> {
+
+    let (parsed_css, parse_warnings) = css::new_from_str_lenient(css_string, None);
+    let css_key_map = azul_css::get_css_key_map();
+
+    let mut lints = parse_warnings.into_iter().map(|w| {
+        let kind = match w.warning {
+            CssParseErrorInner::UnknownPropertyKey(key, value) => CssLintWarningKind::UnknownProperty {
+                key,
+                value,
+                suggestion: suggest_css_key(key, css_key_map.keys().cloned()),
+            },
+            CssParseErrorInner::DynamicCssParseError(e) => CssLintWarningKind::UnsupportedValue(e),
+            other => CssLintWarningKind::ParseError(other),
+        };
+        CssLintWarning { kind, location: w.location }
+    }).collect::<Vec<_>>();
+
+    // Rules with multiple comma-separated selectors share their declarations,
+    // so the same duplicate declaration would be reported once for each selector
+    let mut reported_duplicates = BTreeSet::new();
+
+    for rule in parsed_css.stylesheets.iter().flat_map(|s| s.rules.iter()) {
+
+        if let Some(reason) = get_never_matching_reason(&rule.path) {
+            lints.push(CssLintWarning {
+                kind: CssLintWarningKind::NeverMatchingSelector { path: rule.path.clone(), reason },
+                location: to_error_location(rule.location.as_ref()),
+            });
+        }
+
+        for (index, property_type) in get_duplicate_declarations(rule) {
+            let location = rule.get_declaration_location(index);
+            if !reported_duplicates.insert(location.cloned()) {
+                continue;
+            }
+            lints.push(CssLintWarning {
+                kind: CssLintWarningKind::DuplicateDeclaration(property_type),
+                location: to_error_location(location),
+            });
+        }
+    }
+
+    lints.sort_by_key(|l| l.location);
+    lints
+}
+
+fn to_error_location(location: Option<&CssSourceLocation>) -> ErrorLocation {
+    match location {
+        Some(l) => ErrorLocation { line: l.line, column: l.column },
+        None => ErrorLocation { line: 0, column: 0 },
+    }
+}
+
+/// Returns the indices of the declarations that completely override an earlier
+/// declaration in the same block (which makes the earlier declaration useless)
+fn get_duplicate_declarations(rule: &CssRuleBlock) -> Vec<(usize, CssPropertyType)> {
+    let mut previous_parts = Vec::<Vec<(CssPropertyType, usize)>>::new();
+    rule.declarations.iter().enumerate().filter_map(|(index, declaration)| {
+        let parts = get_declared_parts(declaration);
+        let is_duplicate = previous_parts.iter().any(|previous| {
+            !previous.is_empty() && previous.iter().all(|part| parts.contains(part))
+        });
+        let property_type = parts.first().map(|(property_type, _)| *property_type);
+        previous_parts.push(parts);
+        if is_duplicate { property_type.map(|t| (index, t)) } else { None }
+    }).collect()
+}
+
+/// Returns which parts of a property a declaration sets: The sides of `border`, `padding`,
+/// `margin`, `box-shadow`, `outline` and `overflow` get merged during styling, so `border-top`
+/// and `border-bottom` don't override each other
+fn get_declared_parts(declaration: &CssDeclaration) -> Vec<(CssPropertyType, usize)> {
+    use azul_css::CssProperty::*;
+
+    let property = match declaration.without_importance() {
+        CssDeclaration::Static(s) | CssDeclaration::CurrentColor(s) => s,
+        CssDeclaration::Dynamic(d) => match &d.default {
+            DynamicCssPropertyDefault::Exact(e) => e,
+            DynamicCssPropertyDefault::Auto => return vec![(d.property_type, 0)],
+        },
+        CssDeclaration::Important(_) => return Vec::new(), // unreachable
+        // Keywords can't be split into sides, so they only override an earlier keyword of the same key
+        CssDeclaration::Keyword(property_type, _) => return vec![(*property_type, usize::MAX)],
+    };
+
+    let property_type = property.get_type();
+
+    macro_rules! sides {($($side:expr),*) => ({
+        [$($side.is_some()),*].iter().enumerate()
+            .filter(|(_, is_set)| **is_set)
+            .map(|(side, _)| (property_type, side))
+            .collect()
+    })}
+
+    match property {
+        Border(b) => sides!(b.top, b.left, b.bottom, b.right),
+        BoxShadow(b) => sides!(b.top, b.left, b.bottom, b.right),
+        Padding(p) => sides!(p.top, p.left, p.bottom, p.right),
+        Margin(m) => sides!(m.top, m.left, m.bottom, m.right),
+        Overflow(o) => sides!(o.horizontal, o.vertical),
+        Outline(o) => sides!(o.width, o.style, o.color, o.offset),
+        _ => vec![(property_type, 0)],
+    }
+}
+
+/// Returns why the path can't match any node, `None` if it might match
+fn get_never_matching_reason(path: &CssPath) -> Option<&'static str> {
+    use self::CssPathSelector::*;
+
+    let is_combinator = |s: &CssPathSelector| *s == Children || *s == DirectChildren;
+
+    if path.selectors.is_empty() {
+        return Some("empty selector");
+    }
+
+    if path.selectors.last().map(is_combinator).unwrap_or(false) {
+        return Some("selector ends with a combinator");
+    }
+
+    // Check every compound selector (i.e. `div.a:hover` in `body > div.a:hover`)
+    for group in path.selectors.split(|s| is_combinator(s)) {
+        if group.is_empty() {
+            return Some("two combinators without a selector in between");
+        }
+
+        let mut node_types = group.iter().filter_map(|s| match s { Type(t) => Some(t), _ => None });
+        if let Some(first_type) = node_types.next() {
+            if node_types.any(|t| t != first_type) {
+                return Some("a node can't have two different node types");
+            }
+        }
+
+        if group.iter().any(|s| *s == PseudoSelector(CssPathPseudoSelector::NthChild(CssNthChildSelector::Number(0)))) {
+            return Some(":nth-child() starts counting at 1");
+        }
+    }
+
+    None
+}
+
+/// Returns the known key with the smallest edit distance to `key`, if it's close enough to be a typo
+fn suggest_css_key<I: Iterator<Item=&'static str>>(key: &str, known_keys: I) -> Option<&'static str> {
+    let max_distance = (key.chars().count() / 3).max(1).min(3);
+    known_keys
+        .map(|known| (edit_distance(key, known), known))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known)| known)
+}
+
+/// Edit distance between two strings (Levenshtein distance, but swapping two
+/// adjacent characters only counts as one edit, since that's a common typo)
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<char>>();
+    let b = b.chars().collect::<Vec<char>>();
+
+    // distances[i][j] = distance between a[..i] and b[..j]
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in 0..=a.len() { distances[i][0] = i; }
+    for j in 0..=b.len() { distances[0][j] = j; }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution_cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut distance = (distances[i - 1][j - 1] + substitution_cost)
+                .min(distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
+#[test]
+fn test_edit_distance() {
+    assert_eq!(edit_distance("", ""), 0);
+    assert_eq!(edit_distance("abc", ""), 3);
+    assert_eq!(edit_distance("backround-color", "background-color"), 1);
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+    assert_eq!(edit_distance("widht", "width"), 1);
+}
+
+#[test]
+fn test_suggest_css_key() {
+    let keys = || azul_css::get_css_key_map().keys().cloned().collect::<Vec<_>>().into_iter();
+    assert_eq!(suggest_css_key("backround-color", keys()), Some("background-color"));
+    assert_eq!(suggest_css_key("widht", keys()), Some("width"));
+    assert_eq!(suggest_css_key("colr", keys()), Some("color"));
+    assert_eq!(suggest_css_key("qwertzuiop", keys()), None);
+}
+
+#[test]
+fn test_lint_css() {
+    use azul_css::CssPropertyType;
+
+    let lints = lint_css("
+        .a { backround-color: red; width: 5px; width: 6px; }
+        .b, .c { height: abc; }
+        div > { color: red; }
+        .d:nth-child(0) { color: red; color: blue; }
+        .e { border-top: 1px solid red; border-bottom: 1px solid red; padding: 5px; padding-left: 1px; }
+        .f { margin-left: 1px; margin: 5px; }
+    ");
+
+    let kinds = lints.iter().map(|l| l.kind.clone()).collect::<Vec<_>>();
+    assert_eq!(kinds.len(), 7);
+    assert_eq!(kinds[0], CssLintWarningKind::UnknownProperty { key: "backround-color", value: "red", suggestion: Some("background-color") });
+    assert_eq!(kinds[1], CssLintWarningKind::DuplicateDeclaration(CssPropertyType::Width));
+    assert!(if let CssLintWarningKind::UnsupportedValue(_) = kinds[2] { true } else { false });
+    assert!(if let CssLintWarningKind::NeverMatchingSelector { reason: "selector ends with a combinator", .. } = kinds[3] { true } else { false });
+    assert!(if let CssLintWarningKind::NeverMatchingSelector { reason: ":nth-child() starts counting at 1", .. } = kinds[4] { true } else { false });
+    assert_eq!(kinds[5], CssLintWarningKind::DuplicateDeclaration(CssPropertyType::TextColor));
+    assert_eq!(kinds[6], CssLintWarningKind::DuplicateDeclaration(CssPropertyType::Margin));
+    assert_eq!(lints[0].location.line, 2);
+}
+
+// `outline: none` doesn't set any part of the outline, so it can't be overridden by anything
+#[test]
+fn test_lint_css_empty_declaration_is_no_duplicate() {
+    assert_eq!(lint_css(".a { outline: none; width: 5px; }"), Vec::new());
+}
@@ -6,15 +6,19 @@ use azul_css::{
     StyleTextAlignmentHorz, Overflow,
     LayoutAlignItems, LayoutAlignContent, LayoutJustifyContent, Shape,
     LayoutWrap, LayoutDirection, LayoutPosition, CssProperty, LayoutOverflow,
-    StyleFontFamily, StyleFontSize, StyleLineHeight, LayoutFlexShrink, LayoutFlexGrow,
+    StyleFontFamily, StyleFontSize, StyleLineHeight, LayoutFlexShrink, LayoutFlexGrow, LayoutAspectRatio,
     LayoutLeft, LayoutRight, LayoutTop, LayoutBottom, StyleCursor, StyleWordSpacing, StyleTabWidth,
     LayoutMaxHeight, LayoutMinHeight, LayoutHeight, LayoutMaxWidth, LayoutMinWidth, LayoutWidth,
     StyleBorderRadius, PixelValue, PercentageValue, FloatValue,
-    ColorU, LayoutMargin, StyleLetterSpacing, StyleTextColor, StyleBackground, StyleBoxShadow,
+    ColorU, LayoutMargin, StyleLetterSpacing, StyleTextColor, StyleBackground, StyleBoxShadow, StyleBoxShadows, StyleOutline, DEFAULT_OUTLINE_WIDTH,
     GradientStopPre, RadialGradient, StyleBackgroundColor, StyleBackgroundSize, StyleBackgroundRepeat,
     DirectionCorner, StyleBorder, Direction, CssImageId, LinearGradient,
     BoxShadowPreDisplayItem, BorderStyle, LayoutPadding, StyleBorderSide, BorderRadius, PixelSize,
-    BackgroundType,
+    BackgroundType, StyleBackgrounds, StyleBackgroundSizes, StyleBackgroundRepeats,
+    StyleBackgroundPosition, StyleBackgroundPositions, BackgroundPositionHorizontal,
+    BackgroundPositionVertical, StyleBackgroundOrigin, StyleBackgroundOrigins,
+    StyleBackgroundClip, StyleBackgroundClips, StyleBackgroundAttachment, StyleBackgroundAttachments,
+    StyleFilter, StyleBackdropFilter, StyleFilterFunction, StyleDropShadow, ConicGradient,
 
     SizeMetric, BoxShadowClipMode, ExtendMode, FontId,
 };
@@ -109,10 +113,14 @@ pub fn parse_key_value_pair<'a>(key: CssPropertyType, value: &'a str) -> Result<
     match key {
         BorderRadius     => Ok(parse_style_border_radius(value)?.into()),
         BackgroundColor  => Ok(parse_style_background_color(value)?.into()),
-        BackgroundSize   => Ok(parse_style_background_size(value)?.into()),
-        BackgroundRepeat => Ok(parse_style_background_repeat(value)?.into()),
+        BackgroundSize   => Ok(StyleBackgroundSizes(parse_comma_separated(value, parse_style_background_size)?).into()),
+        BackgroundRepeat => Ok(StyleBackgroundRepeats(parse_comma_separated(value, parse_style_background_repeat)?).into()),
+        BackgroundPosition => Ok(StyleBackgroundPositions(parse_comma_separated(value, parse_style_background_position)?).into()),
+        BackgroundOrigin => Ok(StyleBackgroundOrigins(parse_comma_separated(value, parse_style_background_origin)?).into()),
+        BackgroundClip   => Ok(StyleBackgroundClips(parse_comma_separated(value, parse_style_background_clip)?).into()),
+        BackgroundAttachment => Ok(StyleBackgroundAttachments(parse_comma_separated(value, parse_style_background_attachment)?).into()),
         TextColor        => Ok(parse_style_text_color(value)?.into()),
-        Background       => Ok(parse_style_background(value)?.into()),
+        Background       => Ok(parse_style_backgrounds(value)?.into()),
         FontSize         => Ok(parse_style_font_size(value)?.into()),
         FontFamily       => Ok(parse_style_font_family(value)?.into()),
         LetterSpacing    => Ok(parse_style_letter_spacing(value)?.into()),
@@ -133,6 +141,7 @@ pub fn parse_key_value_pair<'a>(key: CssPropertyType, value: &'a str) -> Result<
         MinHeight        => Ok(parse_layout_min_height(value)?.into()),
         MaxWidth         => Ok(parse_layout_max_width(value)?.into()),
         MaxHeight        => Ok(parse_layout_max_height(value)?.into()),
+        AspectRatio      => Ok(parse_layout_aspect_ratio(value)?.into()),
 
         Position         => Ok(parse_layout_position(value)?.into()),
         Top              => Ok(parse_layout_top(value)?.into()),
@@ -141,12 +150,21 @@ pub fn parse_key_value_pair<'a>(key: CssPropertyType, value: &'a str) -> Result<
         Bottom           => Ok(parse_layout_bottom(value)?.into()),
         TextAlign        => Ok(parse_layout_text_align(value)?.into()),
 
-        BoxShadow        => Ok(StyleBoxShadow::all(parse_css_box_shadow(value)?).into()),
+        BoxShadow        => Ok(StyleBoxShadow::all(parse_css_box_shadows(value)?).into()),
         BoxShadowTop     => Ok(box_shadow_parser::parse_top(value)?.into()),
         BoxShadowBottom  => Ok(box_shadow_parser::parse_bottom(value)?.into()),
         BoxShadowLeft    => Ok(box_shadow_parser::parse_left(value)?.into()),
         BoxShadowRight   => Ok(box_shadow_parser::parse_right(value)?.into()),
 
+        Outline          => Ok(parse_style_outline(value)?.into()),
+        OutlineWidth     => Ok(StyleOutline { width: Some(parse_outline_width(value)?), .. Default::default() }.into()),
+        OutlineStyle     => Ok(StyleOutline { style: Some(parse_border_style(value)?), .. Default::default() }.into()),
+        OutlineColor     => Ok(StyleOutline { color: Some(parse_css_color(value)?), .. Default::default() }.into()),
+        OutlineOffset    => Ok(StyleOutline { offset: Some(parse_pixel_value(value)?), .. Default::default() }.into()),
+
+        Filter           => Ok(StyleFilter(parse_style_filter_functions(value)?).into()),
+        BackdropFilter   => Ok(StyleBackdropFilter(parse_style_filter_functions(value)?).into()),
+
         Padding          => Ok(parse_layout_padding(value)?.into()),
         PaddingTop       => Ok(layout_padding_parser::parse_top(value)?.into()),
         PaddingBottom    => Ok(layout_padding_parser::parse_bottom(value)?.into()),
@@ -192,6 +210,52 @@ pub fn parse_key_value_pair<'a>(key: CssPropertyType, value: &'a str) -> Result<
     }
 }
 
+/// Parses a value that uses `currentColor` as its color, such as `border: 1px solid currentColor`.
+/// Returns `None` if the value doesn't contain `currentColor`.
+///
+/// Since the text color of a node is only known after the cascade, the returned property
+/// only contains a placeholder color, which is replaced via `CssProperty::with_color` later on.
+pub fn parse_current_color_property<'a>(key: CssPropertyType, value: &'a str) -> Option<Result<CssProperty, CssParsingError<'a>>> {
+
+    const PLACEHOLDER: ColorU = ColorU { r: 0, g: 0, b: 0, a: 255 };
+
+    let value = value.trim();
+    let components = split_whitespace_outside_braces(value);
+    let current_color_position = components.iter().position(|c| c.eq_ignore_ascii_case("currentcolor"))?;
+
+    let parse_border_side = || -> Result<StyleBorderSide, CssParsingError<'a>> {
+        match (&components[..], current_color_position) {
+            ([width, style, _], 2) => Ok(StyleBorderSide {
+                border_width: parse_pixel_value(width).map_err(|e| CssBorderParseError::ThicknessParseError(e))?,
+                border_style: parse_border_style(style).map_err(|e| CssBorderParseError::InvalidBorderStyle(e))?,
+                border_color: PLACEHOLDER,
+            }),
+            _ => Err(CssBorderParseError::InvalidBorderDeclaration(value).into()),
+        }
+    };
+
+    let parse_outline = || -> Result<StyleOutline, CssParsingError<'a>> {
+        let mut other_components = components.clone();
+        other_components.remove(current_color_position);
+        let outline = parse_style_outline_components(value, &other_components)?;
+        Ok(StyleOutline { color: Some(PLACEHOLDER), .. outline })
+    };
+
+    use self::CssPropertyType::*;
+
+    Some(match key {
+        BackgroundColor if components.len() == 1 => Ok(StyleBackgroundColor(PLACEHOLDER).into()),
+        OutlineColor if components.len() == 1 => Ok(StyleOutline { color: Some(PLACEHOLDER), .. Default::default() }.into()),
+        Outline => parse_outline().map(|outline| outline.into()),
+        Border => parse_border_side().map(|side| StyleBorder::all(side).into()),
+        BorderTop => parse_border_side().map(|side| StyleBorder { top: Some(side), .. Default::default() }.into()),
+        BorderBottom => parse_border_side().map(|side| StyleBorder { bottom: Some(side), .. Default::default() }.into()),
+        BorderLeft => parse_border_side().map(|side| StyleBorder { left: Some(side), .. Default::default() }.into()),
+        BorderRight => parse_border_side().map(|side| StyleBorder { right: Some(side), .. Default::default() }.into()),
+        _ => Err(CssParsingError::InvalidCurrentColor(value)),
+    })
+}
+
 /// Error containing all sub-errors that could happen during CSS parsing
 ///
 /// Usually we want to crash on the first error, to notify the user of the problem.
@@ -199,50 +263,64 @@ pub fn parse_key_value_pair<'a>(key: CssPropertyType, value: &'a str) -> Result<
 pub enum CssParsingError<'a> {
     CssBorderParseError(CssBorderParseError<'a>),
     CssShadowParseError(CssShadowParseError<'a>),
+    CssOutlineParseError(CssOutlineParseError<'a>),
+    CssStyleFilterParseError(CssStyleFilterParseError<'a>),
     InvalidValueErr(InvalidValueErr<'a>),
     PixelParseError(PixelParseError<'a>),
     PercentageParseError(PercentageParseError),
     CssImageParseError(CssImageParseError<'a>),
     CssStyleFontFamilyParseError(CssStyleFontFamilyParseError<'a>),
     CssBackgroundParseError(CssBackgroundParseError<'a>),
+    CssBackgroundPositionParseError(CssBackgroundPositionParseError<'a>),
     CssColorParseError(CssColorParseError<'a>),
     CssStyleBorderRadiusParseError(CssStyleBorderRadiusParseError<'a>),
     PaddingParseError(LayoutPaddingParseError<'a>),
     MarginParseError(LayoutMarginParseError<'a>),
     FlexShrinkParseError(FlexShrinkParseError<'a>),
     FlexGrowParseError(FlexGrowParseError<'a>),
+    AspectRatioParseError(AspectRatioParseError<'a>),
+    InvalidCurrentColor(&'a str),
 }
 
 impl_display!{ CssParsingError<'a>, {
     CssStyleBorderRadiusParseError(e) => format!("Invalid border-radius: {}", e),
     CssBorderParseError(e) => format!("Invalid border property: {}", e),
     CssShadowParseError(e) => format!("Invalid shadow: \"{}\"", e),
+    CssOutlineParseError(e) => format!("Invalid outline: {}", e),
+    CssStyleFilterParseError(e) => format!("Invalid filter: {}", e),
     InvalidValueErr(e) => format!("\"{}\"", e.0),
     PixelParseError(e) => format!("{}", e),
     PercentageParseError(e) => format!("{}", e),
     CssImageParseError(e) => format!("{}", e),
     CssStyleFontFamilyParseError(e) => format!("{}", e),
     CssBackgroundParseError(e) => format!("{}", e),
+    CssBackgroundPositionParseError(e) => format!("Invalid background-position: {}", e),
     CssColorParseError(e) => format!("{}", e),
     PaddingParseError(e) => format!("{}", e),
     MarginParseError(e) => format!("{}", e),
     FlexShrinkParseError(e) => format!("{}", e),
     FlexGrowParseError(e) => format!("{}", e),
+    AspectRatioParseError(e) => format!("{}", e),
+    InvalidCurrentColor(e) => format!("currentColor can only be used as the color of color, background-color, outline and border properties: \"{}\"", e),
 }}
 
 impl_from!(CssBorderParseError<'a>, CssParsingError::CssBorderParseError);
 impl_from!(CssShadowParseError<'a>, CssParsingError::CssShadowParseError);
+impl_from!(CssOutlineParseError<'a>, CssParsingError::CssOutlineParseError);
+impl_from!(CssStyleFilterParseError<'a>, CssParsingError::CssStyleFilterParseError);
 impl_from!(CssColorParseError<'a>, CssParsingError::CssColorParseError);
 impl_from!(InvalidValueErr<'a>, CssParsingError::InvalidValueErr);
 impl_from!(PixelParseError<'a>, CssParsingError::PixelParseError);
 impl_from!(CssImageParseError<'a>, CssParsingError::CssImageParseError);
 impl_from!(CssStyleFontFamilyParseError<'a>, CssParsingError::CssStyleFontFamilyParseError);
 impl_from!(CssBackgroundParseError<'a>, CssParsingError::CssBackgroundParseError);
+impl_from!(CssBackgroundPositionParseError<'a>, CssParsingError::CssBackgroundPositionParseError);
 impl_from!(CssStyleBorderRadiusParseError<'a>, CssParsingError::CssStyleBorderRadiusParseError);
 impl_from!(LayoutPaddingParseError<'a>, CssParsingError::PaddingParseError);
 impl_from!(LayoutMarginParseError<'a>, CssParsingError::MarginParseError);
 impl_from!(FlexShrinkParseError<'a>, CssParsingError::FlexShrinkParseError);
 impl_from!(FlexGrowParseError<'a>, CssParsingError::FlexGrowParseError);
+impl_from!(AspectRatioParseError<'a>, CssParsingError::AspectRatioParseError);
 
 impl<'a> From<PercentageParseError> for CssParsingError<'a> {
     fn from(e: PercentageParseError) -> Self {
@@ -275,6 +353,8 @@ pub enum CssColorComponent {
     Hue,
     Saturation,
     Lightness,
+    Whiteness,
+    Blackness,
     Alpha,
 }
 
@@ -293,6 +373,9 @@ pub enum CssColorParseError<'a> {
     DirectionParseError(CssDirectionParseError<'a>),
     UnsupportedDirection(&'a str),
     InvalidPercentage(PercentageParseError),
+    MissingColorArgument(&'a str),
+    InvalidColorMix(&'a str),
+    UnsupportedColorSpace(&'a str),
 }
 
 impl<'a> fmt::Display for CssColorParseError<'a> {
@@ -300,7 +383,7 @@ impl<'a> fmt::Display for CssColorParseError<'a> {
         use self::CssColorParseError::*;
         match self {
             InvalidColor(i) => write!(f, "Invalid CSS color: \"{}\"", i),
-            InvalidFunctionName(i) => write!(f, "Invalid function name, expected one of: \"rgb\", \"rgba\", \"hsl\", \"hsla\" got: \"{}\"", i),
+            InvalidFunctionName(i) => write!(f, "Invalid function name, expected one of: \"rgb\", \"rgba\", \"hsl\", \"hsla\", \"hwb\", \"color-mix\", \"lighten\", \"darken\" got: \"{}\"", i),
             InvalidColorComponent(i) => write!(f, "Invalid color component when parsing CSS color: \"{}\"", i),
             IntValueParseErr(e) => write!(f, "CSS color component: Value not in range between 00 - FF: \"{}\"", e),
             FloatValueParseErr(e) => write!(f, "CSS color component: Value cannot be parsed as floating point number: \"{}\"", e),
@@ -312,6 +395,9 @@ impl<'a> fmt::Display for CssColorParseError<'a> {
             DirectionParseError(e) => write!(f, "Could not parse direction argument for CSS color: \"{}\"", e),
             UnsupportedDirection(d) => write!(f, "Unsupported direction type for CSS color: \"{}\"", d),
             InvalidPercentage(p) => write!(f, "Invalid percentage when parsing CSS color: \"{}\"", p),
+            MissingColorArgument(i) => write!(f, "Missing color argument in CSS color function: \"{}\"", i),
+            InvalidColorMix(i) => write!(f, "Invalid color-mix(), expected \"in srgb, color [percentage], color [percentage]\": \"{}\"", i),
+            UnsupportedColorSpace(i) => write!(f, "Unsupported color space, only \"in srgb\" is supported: \"{}\"", i),
         }
     }
 }
@@ -537,13 +623,17 @@ pub fn parse_css_color<'a>(input: &'a str)
     } else {
         use self::ParenthesisParseError::*;
 
-        match parse_parentheses(input, &["rgba", "rgb", "hsla", "hsl"]) {
+        match parse_parentheses(input, &["rgba", "rgb", "hsla", "hsl", "hwb", "color-mix", "lighten", "darken"]) {
             Ok((stopword, inner_value)) => {
                 match stopword {
                     "rgba" => parse_color_rgb(inner_value, true),
                     "rgb" => parse_color_rgb(inner_value, false),
                     "hsla" => parse_color_hsl(inner_value, true),
                     "hsl" => parse_color_hsl(inner_value, false),
+                    "hwb" => parse_color_hwb(inner_value),
+                    "color-mix" => parse_color_mix(inner_value),
+                    "lighten" => parse_color_lightness_adjustment(inner_value, 1.0),
+                    "darken" => parse_color_lightness_adjustment(inner_value, -1.0),
                     _ => unreachable!(),
                 }
             },
@@ -800,71 +890,237 @@ pub fn parse_color_hsl<'a>(input: &'a str, parse_alpha: bool)
 pub fn parse_color_hsl_components<'a>(components: &mut Iterator<Item = &'a str>)
 -> Result<ColorU, CssColorParseError<'a>>
 {
-    #[inline]
-    fn angle_from_str<'a>(components: &mut Iterator<Item = &'a str>, which: CssColorComponent)
-    -> Result<f32, CssColorParseError<'a>>
-    {
-        let c = components.next().ok_or(CssColorParseError::MissingColorComponent(which))?;
-        if c.is_empty() {
-            return Err(CssColorParseError::MissingColorComponent(which));
-        }
-        let dir = parse_direction(c)?;
-        match dir {
-            Direction::Angle(deg) => Ok(deg.get()),
-            Direction::FromTo(_, _) => return Err(CssColorParseError::UnsupportedDirection(c)),
-        }
+    let (h, s, l) = (
+        angle_from_str(components, CssColorComponent::Hue)?,
+        percent_from_str(components, CssColorComponent::Saturation)?,
+        percent_from_str(components, CssColorComponent::Lightness)?,
+    );
+
+    let (r, g, b) = hsl_to_rgb(h, s, l);
+
+    Ok(ColorU { r, g, b, a: 255 })
+}
+
+#[inline]
+fn angle_from_str<'a>(components: &mut Iterator<Item = &'a str>, which: CssColorComponent)
+-> Result<f32, CssColorParseError<'a>>
+{
+    let c = components.next().ok_or(CssColorParseError::MissingColorComponent(which))?;
+    if c.is_empty() {
+        return Err(CssColorParseError::MissingColorComponent(which));
     }
+    let dir = parse_direction(c)?;
+    match dir {
+        Direction::Angle(deg) => Ok(deg.get()),
+        Direction::FromTo(_, _) => return Err(CssColorParseError::UnsupportedDirection(c)),
+    }
+}
 
-    #[inline]
-    fn percent_from_str<'a>(components: &mut Iterator<Item = &'a str>, which: CssColorComponent)
-    -> Result<f32, CssColorParseError<'a>>
-    {
-        let c = components.next().ok_or(CssColorParseError::MissingColorComponent(which))?;
-        if c.is_empty() {
-            return Err(CssColorParseError::MissingColorComponent(which));
+#[inline]
+fn percent_from_str<'a>(components: &mut Iterator<Item = &'a str>, which: CssColorComponent)
+-> Result<f32, CssColorParseError<'a>>
+{
+    let c = components.next().ok_or(CssColorParseError::MissingColorComponent(which))?;
+    if c.is_empty() {
+        return Err(CssColorParseError::MissingColorComponent(which));
+    }
+
+    let parsed_percent = parse_percentage(c).map_err(|e| CssColorParseError::InvalidPercentage(e))?;
+
+    Ok(parsed_percent.get())
+}
+
+/// Adapted from [https://en.wikipedia.org/wiki/HSL_and_HSV#Converting_to_RGB]
+#[inline]
+fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (u8, u8, u8) {
+    let h = ((h % 360.0) + 360.0) % 360.0;
+    let s = s / 100.0;
+    let l = l / 100.0;
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let h = h / 60.0;
+    let x = c * (1.0 - ((h % 2.0) - 1.0).abs());
+    let (r1, g1, b1) = match h as u8 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        5 => (c, 0.0, x),
+        _ => {
+            unreachable!();
         }
+    };
+    let m = l - c / 2.0;
+    (
+        ((r1 + m) * 256.0).min(255.0) as u8,
+        ((g1 + m) * 256.0).min(255.0) as u8,
+        ((b1 + m) * 256.0).min(255.0) as u8,
+    )
+}
+
+/// Converts a color to `(hue in degrees, saturation in percent, lightness in percent)`
+fn rgb_to_hsl(color: ColorU) -> (f32, f32, f32) {
+    let r = color.r as f32 / 255.0;
+    let g = color.g as f32 / 255.0;
+    let b = color.b as f32 / 255.0;
+
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+
+    if d == 0.0 {
+        return (0.0, 0.0, l * 100.0);
+    }
+
+    let s = d / (1.0 - (2.0 * l - 1.0).abs());
+    let h = if max == r {
+        60.0 * (((g - b) / d) % 6.0)
+    } else if max == g {
+        60.0 * ((b - r) / d + 2.0)
+    } else {
+        60.0 * ((r - g) / d + 4.0)
+    };
+
+    ((h + 360.0) % 360.0, s * 100.0, l * 100.0)
+}
+
+/// Parse a color of the form `hwb(hue whiteness% blackness% [/ alpha])` without the leading
+/// `hwb(` or trailing `)`. The components may also be separated by commas. Alpha defaults to 255.
+pub fn parse_color_hwb<'a>(input: &'a str)
+-> Result<ColorU, CssColorParseError<'a>>
+{
+    let (input, alpha) = match input.find('/') {
+        Some(idx) => (&input[..idx], Some(input[(idx + 1)..].trim())),
+        None => (input, None),
+    };
 
-        let parsed_percent = parse_percentage(c).map_err(|e| CssColorParseError::InvalidPercentage(e))?;
+    let mut components = input.split(|c: char| c == ',' || c.is_whitespace()).filter(|c| !c.is_empty());
+
+    let (h, w, b) = (
+        angle_from_str(&mut components, CssColorComponent::Hue)?,
+        percent_from_str(&mut components, CssColorComponent::Whiteness)?,
+        percent_from_str(&mut components, CssColorComponent::Blackness)?,
+    );
 
-        Ok(parsed_percent.get())
+    if let Some(arg) = components.next() {
+        return Err(CssColorParseError::ExtraArguments(arg));
     }
 
-    /// Adapted from [https://en.wikipedia.org/wiki/HSL_and_HSV#Converting_to_RGB]
-    #[inline]
-    fn hsl_to_rgb<'a>(h: f32, s: f32, l: f32) -> (u8, u8, u8) {
-        let s = s / 100.0;
-        let l = l / 100.0;
-        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
-        let h = h / 60.0;
-        let x = c * (1.0 - ((h % 2.0) - 1.0).abs());
-        let (r1, g1, b1) = match h as u8 {
-            0 => (c, x, 0.0),
-            1 => (x, c, 0.0),
-            2 => (0.0, c, x),
-            3 => (0.0, x, c),
-            4 => (x, 0.0, c),
-            5 => (c, 0.0, x),
-            _ => {
-                unreachable!();
-            }
-        };
-        let m = l - c / 2.0;
-        (
-            ((r1 + m) * 256.0).min(255.0) as u8,
-            ((g1 + m) * 256.0).min(255.0) as u8,
-            ((b1 + m) * 256.0).min(255.0) as u8,
-        )
+    let a = match alpha {
+        Some(alpha) => parse_alpha_component(&mut ::std::iter::once(alpha))?,
+        None => 255,
+    };
+
+    let (w, b) = (w / 100.0, b / 100.0);
+
+    // If whiteness + blackness >= 100%, the color is a shade of gray
+    if w + b >= 1.0 {
+        let gray = (w / (w + b) * 255.0).round() as u8;
+        return Ok(ColorU { r: gray, g: gray, b: gray, a });
     }
 
-    let (h, s, l) = (
-        angle_from_str(components, CssColorComponent::Hue)?,
-        percent_from_str(components, CssColorComponent::Saturation)?,
-        percent_from_str(components, CssColorComponent::Lightness)?,
-    );
+    let (r, g, bl) = hsl_to_rgb(h, 100.0, 50.0);
+    let apply_hwb = |c: u8| ((c as f32 / 255.0 * (1.0 - w - b) + w) * 255.0).round() as u8;
+
+    Ok(ColorU { r: apply_hwb(r), g: apply_hwb(g), b: apply_hwb(bl), a })
+}
+
+/// Parse a color of the form `color-mix(in srgb, color [percentage], color [percentage])` without
+/// the leading `color-mix(` or trailing `)`. Colors without a percentage take the remaining
+/// percentage, if the percentages add up to less than 100%, the result is partially transparent.
+pub fn parse_color_mix<'a>(input: &'a str)
+-> Result<ColorU, CssColorParseError<'a>>
+{
+    let arguments = split_comma_separated(input);
+
+    let (color_space, first, second) = match arguments[..] {
+        [color_space, first, second] => (color_space.trim(), first.trim(), second.trim()),
+        _ => return Err(CssColorParseError::InvalidColorMix(input)),
+    };
+
+    match split_whitespace_outside_braces(color_space)[..] {
+        ["in", "srgb"] => { },
+        _ => return Err(CssColorParseError::UnsupportedColorSpace(color_space)),
+    }
+
+    let (first_color, first_percentage) = parse_color_mix_component(first)?;
+    let (second_color, second_percentage) = parse_color_mix_component(second)?;
+
+    let (first_percentage, second_percentage) = match (first_percentage, second_percentage) {
+        (None, None) => (50.0, 50.0),
+        (Some(p1), None) => (p1, 100.0 - p1),
+        (None, Some(p2)) => (100.0 - p2, p2),
+        (Some(p1), Some(p2)) => (p1, p2),
+    };
+
+    let sum = first_percentage + second_percentage;
+    if first_percentage < 0.0 || second_percentage < 0.0 || sum <= 0.0 {
+        return Err(CssColorParseError::InvalidColorMix(input));
+    }
+
+    let mut mixed = mix_colors(first_color, second_color, second_percentage / sum);
+    mixed.a = (mixed.a as f32 * (sum / 100.0).min(1.0)).round() as u8;
+    Ok(mixed)
+}
+
+/// Parses one color of a `color-mix()`, i.e. `red 30%` or `30% red`
+fn parse_color_mix_component<'a>(input: &'a str)
+-> Result<(ColorU, Option<f32>), CssColorParseError<'a>>
+{
+    let percentage = |p: &str| parse_percentage(p).map(|p| p.get()).map_err(|e| CssColorParseError::InvalidPercentage(e));
+
+    match split_whitespace_outside_braces(input)[..] {
+        [color] => Ok((parse_css_color(color)?, None)),
+        [percent, color] if percent.ends_with('%') => Ok((parse_css_color(color)?, Some(percentage(percent)?))),
+        [color, percent] => Ok((parse_css_color(color)?, Some(percentage(percent)?))),
+        _ => Err(CssColorParseError::InvalidColorMix(input)),
+    }
+}
+
+/// Interpolates between two colors in the sRGB color space with premultiplied alpha,
+/// `t = 0.0` returns the first color, `t = 1.0` returns the second color
+fn mix_colors(a: ColorU, b: ColorU, t: f32) -> ColorU {
+    let alpha_a = a.a as f32 / 255.0 * (1.0 - t);
+    let alpha_b = b.a as f32 / 255.0 * t;
+    let alpha = alpha_a + alpha_b;
+
+    let channel = |x: u8, y: u8| if alpha <= 0.0 {
+        0
+    } else {
+        ((x as f32 * alpha_a + y as f32 * alpha_b) / alpha).round() as u8
+    };
+
+    ColorU {
+        r: channel(a.r, b.r),
+        g: channel(a.g, b.g),
+        b: channel(a.b, b.b),
+        a: (alpha * 255.0).round() as u8,
+    }
+}
+
+/// Parse a color of the form `lighten(color, amount%)` or `darken(color, amount%)` without the
+/// function name or braces: Increases (`direction = 1.0`) or decreases (`direction = -1.0`)
+/// the HSL lightness of the color by the given amount.
+pub fn parse_color_lightness_adjustment<'a>(input: &'a str, direction: f32)
+-> Result<ColorU, CssColorParseError<'a>>
+{
+    let arguments = split_comma_separated(input);
+
+    let (color, amount) = match arguments[..] {
+        [color, amount] => (color.trim(), amount.trim()),
+        [_] => return Err(CssColorParseError::MissingColorComponent(CssColorComponent::Lightness)),
+        _ => return Err(CssColorParseError::MissingColorArgument(input)),
+    };
+
+    let color = parse_css_color(color)?;
+    let amount = parse_percentage(amount).map_err(|e| CssColorParseError::InvalidPercentage(e))?.get();
 
+    let (h, s, l) = rgb_to_hsl(color);
+    let l = (l + amount * direction).max(0.0).min(100.0);
     let (r, g, b) = hsl_to_rgb(h, s, l);
 
-    Ok(ColorU { r, g, b, a: 255 })
+    Ok(ColorU { r, g, b, a: color.a })
 }
 
 fn parse_alpha_component<'a>(components: &mut Iterator<Item=&'a str>) -> Result<u8, CssColorParseError<'a>> {
@@ -1134,14 +1390,35 @@ multi_type_parser!(parse_border_style, BorderStyle,
     ["inset", Inset],
     ["outset", Outset]);
 
-parse_tblr!(box_shadow_parser, StyleBoxShadow, CssShadowParseError, parse_css_box_shadow);
+parse_tblr!(box_shadow_parser, StyleBoxShadow, CssShadowParseError, parse_css_box_shadows);
+
+/// Parses a comma-separated list of box-shadows, i.e. `0px 1px 3px black, 0px 0px 2px red inset`
+/// or `none`
+pub fn parse_css_box_shadows<'a>(input: &'a str)
+-> Result<StyleBoxShadows, CssShadowParseError<'a>>
+{
+    if input.trim() == "none" {
+        return Ok(StyleBoxShadows(Vec::new()));
+    }
+
+    let shadows = parse_comma_separated(input, |shadow| {
+        parse_css_box_shadow(shadow)?.ok_or(CssShadowParseError::InvalidSingleStatement(shadow))
+    })?;
+
+    Ok(StyleBoxShadows(shadows))
+}
 
-/// Parses a CSS box-shadow
+/// Parses a single CSS box-shadow, such as `5px 10px 5px 10px #888888 inset`
+///
+/// The color and the `inset` / `outset` keyword may come either before or after the lengths.
 pub fn parse_css_box_shadow<'a>(input: &'a str)
 -> Result<Option<BoxShadowPreDisplayItem>, CssShadowParseError<'a>>
 {
-    let mut input_iter = input.split_whitespace();
-    let count = input_iter.clone().count();
+    let mut components = split_whitespace_outside_braces(input);
+
+    if components.as_slice() == ["none"] {
+        return Ok(None);
+    }
 
     let mut box_shadow = BoxShadowPreDisplayItem {
         offset: [PixelValue::px(0.0), PixelValue::px(0.0)],
@@ -1151,101 +1428,308 @@ pub fn parse_css_box_shadow<'a>(input: &'a str)
         clip_mode: BoxShadowClipMode::Outset,
     };
 
-    let last_val = input_iter.clone().rev().next();
-    let is_inset = last_val == Some("inset") || last_val == Some("outset");
-
-    if count > 2 && is_inset {
-        let l_val = last_val.unwrap();
-        if l_val == "outset" {
-            box_shadow.clip_mode = BoxShadowClipMode::Outset;
-        } else if l_val == "inset" {
-            box_shadow.clip_mode = BoxShadowClipMode::Inset;
+    fn parse_clip_mode(input: &str) -> Option<BoxShadowClipMode> {
+        match input {
+            "inset" => Some(BoxShadowClipMode::Inset),
+            "outset" => Some(BoxShadowClipMode::Outset),
+            _ => None,
         }
     }
 
-    match count {
-        1 => {
-            // box-shadow: none;
-            match input_iter.next().unwrap() {
-                "none" => return Ok(None),
-                _ => return Err(CssShadowParseError::InvalidSingleStatement(input)),
-            }
-        },
-        2 => {
-            // box-shadow: 5px 10px; (h_offset, v_offset)
-            let h_offset = parse_pixel_value(input_iter.next().unwrap())?;
-            let v_offset = parse_pixel_value(input_iter.next().unwrap())?;
-            box_shadow.offset[0] = h_offset;
-            box_shadow.offset[1] = v_offset;
+    if let Some(clip_mode) = components.first().and_then(|c| parse_clip_mode(c)) {
+        box_shadow.clip_mode = clip_mode;
+        components.remove(0);
+    } else if let Some(clip_mode) = components.last().and_then(|c| parse_clip_mode(c)) {
+        box_shadow.clip_mode = clip_mode;
+        components.pop();
+    }
+
+    match (components.first().cloned(), components.last().cloned()) {
+        (Some(first), _) if parse_pixel_value(first).is_err() => {
+            components.remove(0);
+            box_shadow.color = parse_css_color(first)?;
         },
-        3 => {
-            // box-shadow: 5px 10px inset; (h_offset, v_offset, inset)
-            let h_offset = parse_pixel_value(input_iter.next().unwrap())?;
-            let v_offset = parse_pixel_value(input_iter.next().unwrap())?;
-            box_shadow.offset[0] = h_offset;
-            box_shadow.offset[1] = v_offset;
-
-            if !is_inset {
-                // box-shadow: 5px 10px #888888; (h_offset, v_offset, color)
-                let color = parse_css_color(input_iter.next().unwrap())?;
-                box_shadow.color = color;
-            }
+        (_, Some(last)) if parse_pixel_value(last).is_err() => {
+            components.pop();
+            box_shadow.color = parse_css_color(last)?;
         },
-        4 => {
-            let h_offset = parse_pixel_value(input_iter.next().unwrap())?;
-            let v_offset = parse_pixel_value(input_iter.next().unwrap())?;
-            box_shadow.offset[0] = h_offset;
-            box_shadow.offset[1] = v_offset;
-
-            if !is_inset {
-                let blur = parse_pixel_value(input_iter.next().unwrap())?;
-                box_shadow.blur_radius = blur.into();
-            }
+        _ => { },
+    }
+
+    let lengths = components.iter()
+        .map(|c| parse_pixel_value(c))
+        .collect::<Result<Vec<PixelValue>, _>>()?;
 
-            let color = parse_css_color(input_iter.next().unwrap())?;
-            box_shadow.color = color;
+    match lengths.as_slice() {
+        // box-shadow: 5px 10px; (h_offset, v_offset)
+        [h_offset, v_offset] => {
+            box_shadow.offset = [*h_offset, *v_offset];
         },
-        5 => {
-            // box-shadow: 5px 10px 5px 10px #888888; (h_offset, v_offset, blur, spread, color)
-            // box-shadow: 5px 10px 5px #888888 inset; (h_offset, v_offset, blur, color, inset)
-            let h_offset = parse_pixel_value(input_iter.next().unwrap())?;
-            let v_offset = parse_pixel_value(input_iter.next().unwrap())?;
-            box_shadow.offset[0] = h_offset;
-            box_shadow.offset[1] = v_offset;
-
-            let blur = parse_pixel_value(input_iter.next().unwrap())?;
-            box_shadow.blur_radius = blur.into();
-
-            if !is_inset {
-                let spread = parse_pixel_value(input_iter.next().unwrap())?;
-                box_shadow.spread_radius = spread.into();
+        // box-shadow: 5px 10px 5px; (h_offset, v_offset, blur)
+        [h_offset, v_offset, blur] => {
+            box_shadow.offset = [*h_offset, *v_offset];
+            box_shadow.blur_radius = *blur;
+        },
+        // box-shadow: 5px 10px 5px 10px; (h_offset, v_offset, blur, spread)
+        [h_offset, v_offset, blur, spread] => {
+            box_shadow.offset = [*h_offset, *v_offset];
+            box_shadow.blur_radius = *blur;
+            box_shadow.spread_radius = *spread;
+        },
+        [] | [_] => return Err(CssShadowParseError::InvalidSingleStatement(input)),
+        _ => return Err(CssShadowParseError::TooManyComponents(input)),
+    }
+
+    Ok(Some(box_shadow))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CssOutlineParseError<'a> {
+    InvalidComponent(&'a str),
+    EmptyOutline,
+}
+
+impl_display!{ CssOutlineParseError<'a>, {
+    InvalidComponent(e) => format!("Invalid or duplicate component: \"{}\"", e),
+    EmptyOutline => format!("Empty outline"),
+}}
+
+/// Parses an `outline` shorthand, such as `2px dashed blue`. The width, style and color
+/// may appear in any order, a missing width defaults to `medium` (3px), a missing style
+/// to `none` and a missing color to the text color.
+pub fn parse_style_outline<'a>(input: &'a str)
+-> Result<StyleOutline, CssOutlineParseError<'a>>
+{
+    parse_style_outline_components(input, &split_whitespace_outside_braces(input))
+}
+
+/// Parses the whitespace-separated components of an `outline` (in any order)
+fn parse_style_outline_components<'a>(input: &'a str, components: &[&'a str])
+-> Result<StyleOutline, CssOutlineParseError<'a>>
+{
+    let mut width = None;
+    let mut style = None;
+    let mut color = None;
+
+    if components.is_empty() {
+        return Err(CssOutlineParseError::EmptyOutline);
+    }
+
+    for component in components.iter().cloned() {
+        if width.is_none() && parse_outline_width(component).is_ok() {
+            width = parse_outline_width(component).ok();
+        } else if style.is_none() && parse_border_style(component).is_ok() {
+            style = parse_border_style(component).ok();
+        } else if color.is_none() && parse_css_color(component).is_ok() {
+            color = parse_css_color(component).ok();
+        } else {
+            return Err(CssOutlineParseError::InvalidComponent(component));
+        }
+    }
+
+    Ok(StyleOutline {
+        width: Some(width.unwrap_or(DEFAULT_OUTLINE_WIDTH)),
+        style: Some(style.unwrap_or(BorderStyle::None)),
+        color,
+        offset: None,
+    })
+}
+
+/// Parses an `outline-width`, i.e. `2px` or one of the keywords `thin`, `medium` or `thick`
+pub fn parse_outline_width<'a>(input: &'a str)
+-> Result<PixelValue, PixelParseError<'a>>
+{
+    match input.trim() {
+        "thin" => Ok(PixelValue::const_px(1)),
+        "medium" => Ok(DEFAULT_OUTLINE_WIDTH),
+        "thick" => Ok(PixelValue::const_px(5)),
+        other => parse_pixel_value(other),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CssStyleFilterParseError<'a> {
+    InvalidFilter(&'a str),
+    UnclosedFilter(&'a str),
+    InvalidAmount(&'a str),
+    InvalidAngle(&'a str),
+    InvalidDropShadow(&'a str),
+    PixelParseError(PixelParseError<'a>),
+    ColorParseError(CssColorParseError<'a>),
+}
+
+impl_display!{ CssStyleFilterParseError<'a>, {
+    InvalidFilter(val) => format!("Unknown filter function: \"{}\"", val),
+    UnclosedFilter(val) => format!("Unclosed filter function: \"{}\"", val),
+    InvalidAmount(val) => format!("Invalid amount (expected a number or percentage): \"{}\"", val),
+    InvalidAngle(val) => format!("Invalid angle: \"{}\"", val),
+    InvalidDropShadow(val) => format!("Invalid drop-shadow (expected 2 or 3 lengths and a color): \"{}\"", val),
+    PixelParseError(e) => format!("{}", e),
+    ColorParseError(e) => format!("{}", e),
+}}
+
+impl_from!(PixelParseError<'a>, CssStyleFilterParseError::PixelParseError);
+impl_from!(CssColorParseError<'a>, CssStyleFilterParseError::ColorParseError);
+
+/// Parses the filter functions of a `filter` or `backdrop-filter`, i.e. `blur(5px) grayscale(50%)`
+pub fn parse_style_filter_functions<'a>(input: &'a str)
+-> Result<Vec<StyleFilterFunction>, CssStyleFilterParseError<'a>>
+{
+    use self::CssStyleFilterParseError::*;
+
+    let mut filters = Vec::new();
+    let mut current_input = input.trim();
+
+    if current_input == "none" {
+        return Ok(filters);
+    }
+
+    while !current_input.is_empty() {
+
+        let opening_brace = current_input.find('(').ok_or(InvalidFilter(current_input))?;
+
+        // Find the matching closing brace, the arguments of drop-shadow() may contain rgba()
+        let mut depth = 0;
+        let mut closing_brace = None;
+        for (idx, ch) in current_input[opening_brace..].char_indices() {
+            match ch {
+                '(' => { depth += 1; },
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        closing_brace = Some(opening_brace + idx);
+                        break;
+                    }
+                },
+                _ => { },
             }
+        }
+
+        let closing_brace = closing_brace.ok_or(UnclosedFilter(current_input))?;
+        let argument = current_input[(opening_brace + 1)..closing_brace].trim();
+
+        let filter = match &current_input[..opening_brace] {
+            "blur" => StyleFilterFunction::Blur(
+                if argument.is_empty() { PixelValue::px(0.0) } else { parse_pixel_value(argument)? }
+            ),
+            "brightness" => StyleFilterFunction::Brightness(parse_filter_amount(argument)?),
+            "contrast" => StyleFilterFunction::Contrast(parse_filter_amount(argument)?),
+            "drop-shadow" => StyleFilterFunction::DropShadow(parse_drop_shadow(argument)?),
+            "grayscale" => StyleFilterFunction::Grayscale(parse_filter_amount(argument)?),
+            "hue-rotate" => StyleFilterFunction::HueRotate(FloatValue::new(
+                if argument.is_empty() { 0.0 } else { parse_angle_degrees(argument).ok_or(InvalidAngle(argument))? }
+            )),
+            "invert" => StyleFilterFunction::Invert(parse_filter_amount(argument)?),
+            "opacity" => StyleFilterFunction::Opacity(parse_filter_amount(argument)?),
+            "saturate" => StyleFilterFunction::Saturate(parse_filter_amount(argument)?),
+            "sepia" => StyleFilterFunction::Sepia(parse_filter_amount(argument)?),
+            other => return Err(InvalidFilter(other)),
+        };
+
+        filters.push(filter);
+        current_input = current_input[(closing_brace + 1)..].trim();
+    }
+
+    Ok(filters)
+}
+
+/// Parses the amount of a filter, i.e. `50%` or `0.5`. If the amount is omitted, it defaults to 1.
+fn parse_filter_amount<'a>(input: &'a str)
+-> Result<PercentageValue, CssStyleFilterParseError<'a>>
+{
+    if input.is_empty() {
+        return Ok(PercentageValue::new(1.0));
+    }
+
+    let number = if input.ends_with('%') {
+        input[..(input.len() - 1)].parse::<f32>().map(|percent| percent / 100.0)
+    } else {
+        input.parse::<f32>()
+    };
+
+    number.map(PercentageValue::new).map_err(|_| CssStyleFilterParseError::InvalidAmount(input))
+}
+
+/// Parses an angle (`90deg`, `0.25turn`, `1.57rad`, `100grad` or `0`) and returns it in degrees
+fn parse_angle_degrees(input: &str) -> Option<f32> {
+    use std::f32::consts::PI;
+
+    if input.ends_with("deg") {
+        input[..(input.len() - 3)].parse::<f32>().ok()
+    } else if input.ends_with("grad") {
+        input[..(input.len() - 4)].parse::<f32>().ok().map(|grad| grad / 400.0 * 360.0)
+    } else if input.ends_with("rad") {
+        input[..(input.len() - 3)].parse::<f32>().ok().map(|rad| rad * 180.0 / PI)
+    } else if input.ends_with("turn") {
+        input[..(input.len() - 4)].parse::<f32>().ok().map(|turn| turn * 360.0)
+    } else if input == "0" {
+        Some(0.0)
+    } else {
+        None
+    }
+}
+
+/// Parses the arguments of a `drop-shadow()` filter, i.e. `2px 2px 5px rgba(0, 0, 0, 0.5)`.
+/// The color can be in front of or after the lengths and defaults to black.
+fn parse_drop_shadow<'a>(input: &'a str)
+-> Result<StyleDropShadow, CssStyleFilterParseError<'a>>
+{
+    let mut components = split_whitespace_outside_braces(input);
 
-            let color = parse_css_color(input_iter.next().unwrap())?;
-            box_shadow.color = color;
+    let color = match (components.first().cloned(), components.last().cloned()) {
+        (Some(first), _) if parse_pixel_value(first).is_err() => {
+            components.remove(0);
+            parse_css_color(first)?
         },
-        6 => {
-            // box-shadow: 5px 10px 5px 10px #888888 inset; (h_offset, v_offset, blur, spread, color, inset)
-            let h_offset = parse_pixel_value(input_iter.next().unwrap())?;
-            let v_offset = parse_pixel_value(input_iter.next().unwrap())?;
-            box_shadow.offset[0] = h_offset;
-            box_shadow.offset[1] = v_offset;
+        (_, Some(last)) if parse_pixel_value(last).is_err() => {
+            components.pop();
+            parse_css_color(last)?
+        },
+        _ => ColorU { r: 0, g: 0, b: 0, a: 255 },
+    };
 
-            let blur = parse_pixel_value(input_iter.next().unwrap())?;
-            box_shadow.blur_radius = blur.into();
+    let (offset_x, offset_y, blur_radius) = match components.as_slice() {
+        [x, y] => (parse_pixel_value(x)?, parse_pixel_value(y)?, PixelValue::px(0.0)),
+        [x, y, blur] => (parse_pixel_value(x)?, parse_pixel_value(y)?, parse_pixel_value(blur)?),
+        _ => return Err(CssStyleFilterParseError::InvalidDropShadow(input)),
+    };
 
-            let spread = parse_pixel_value(input_iter.next().unwrap())?;
-            box_shadow.spread_radius = spread.into();
+    Ok(StyleDropShadow {
+        offset: [offset_x, offset_y],
+        blur_radius,
+        color,
+    })
+}
+
+/// Splits the input at all whitespace that is not inside of braces,
+/// i.e. `"5px rgba(0, 0, 0, 0)"` => `["5px", "rgba(0, 0, 0, 0)"]`
+fn split_whitespace_outside_braces<'a>(input: &'a str) -> Vec<&'a str> {
+    let mut items = Vec::new();
+    let mut depth = 0;
+    let mut item_start = None;
 
-            let color = parse_css_color(input_iter.next().unwrap())?;
-            box_shadow.color = color;
+    for (idx, ch) in input.char_indices() {
+        match ch {
+            '(' => { depth += 1; },
+            ')' => { depth -= 1; },
+            c if c.is_whitespace() && depth == 0 => {
+                if let Some(start) = item_start.take() {
+                    items.push(&input[start..idx]);
+                }
+                continue;
+            },
+            _ => { },
         }
-        _ => {
-            return Err(CssShadowParseError::TooManyComponents(input));
+        if item_start.is_none() {
+            item_start = Some(idx);
         }
     }
 
-    Ok(Some(box_shadow))
+    if let Some(start) = item_start {
+        items.push(&input[start..]);
+    }
+
+    items
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -1279,21 +1763,99 @@ impl_from!(CssGradientStopParseError<'a>, CssBackgroundParseError::GradientParse
 impl_from!(CssShapeParseError<'a>, CssBackgroundParseError::ShapeParseError);
 impl_from!(CssImageParseError<'a>, CssBackgroundParseError::ImageParseError);
 
-// parses a background, such as "linear-gradient(red, green)"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CssBackgroundPositionParseError<'a> {
+    NoPosition(&'a str),
+    TooManyComponents(&'a str),
+    InvalidComponent(&'a str),
+    PixelParseError(PixelParseError<'a>),
+}
+
+impl_display!{ CssBackgroundPositionParseError<'a>, {
+    NoPosition(val) => format!("No position: \"{}\"", val),
+    TooManyComponents(val) => format!("Too many components (expected 1 or 2): \"{}\"", val),
+    InvalidComponent(val) => format!("Invalid component: \"{}\"", val),
+    PixelParseError(e) => format!("{}", e),
+}}
+
+impl_from!(PixelParseError<'a>, CssBackgroundPositionParseError::PixelParseError);
+
+/// Parses a single `background-position` value, such as `right bottom`, `center` or `10px 5px`
+///
+/// Like in CSS, keywords may be given in any order (`top left` is the same as `left top`)
+/// and a missing second value defaults to `center`.
+pub fn parse_style_background_position<'a>(input: &'a str)
+-> Result<StyleBackgroundPosition, CssBackgroundPositionParseError<'a>>
+{
+    use self::CssBackgroundPositionParseError::*;
+
+    let input = input.trim();
+    let mut components = input.split_whitespace();
+    let first = components.next().ok_or(NoPosition(input))?;
+    let second = components.next();
+
+    if components.next().is_some() {
+        return Err(TooManyComponents(input));
+    }
+
+    let (horizontal, vertical) = match second {
+        None => match first {
+            "top" | "bottom" => ("center", first),
+            _ => (first, "center"),
+        },
+        // "top left" => "left top"
+        Some(second) => match (first, second) {
+            ("top", _) | ("bottom", _) | (_, "left") | (_, "right") => (second, first),
+            _ => (first, second),
+        },
+    };
+
+    let horizontal = match horizontal {
+        "left" => BackgroundPositionHorizontal::Left,
+        "center" => BackgroundPositionHorizontal::Center,
+        "right" => BackgroundPositionHorizontal::Right,
+        "top" | "bottom" => return Err(InvalidComponent(horizontal)),
+        other => BackgroundPositionHorizontal::Exact(parse_pixel_value(other)?),
+    };
+
+    let vertical = match vertical {
+        "top" => BackgroundPositionVertical::Top,
+        "center" => BackgroundPositionVertical::Center,
+        "bottom" => BackgroundPositionVertical::Bottom,
+        "left" | "right" => return Err(InvalidComponent(vertical)),
+        other => BackgroundPositionVertical::Exact(parse_pixel_value(other)?),
+    };
+
+    Ok(StyleBackgroundPosition { horizontal, vertical })
+}
+
+// parses a background, such as "linear-gradient(red, green)", "none" or "red"
 pub fn parse_style_background<'a>(input: &'a str)
 -> Result<StyleBackground, CssBackgroundParseError<'a>>
 {
-    let (background_type, brace_contents) = parse_parentheses(input, &[
-        "none", "linear-gradient", "repeating-linear-gradient",
-        "radial-gradient", "repeating-radial-gradient", "image"
-    ])?;
+    if input.trim() == "none" {
+        return Ok(StyleBackground::NoBackground);
+    }
+
+    let (background_type, brace_contents) = match parse_parentheses(input, &[
+        "linear-gradient", "repeating-linear-gradient",
+        "radial-gradient", "repeating-radial-gradient",
+        "conic-gradient", "repeating-conic-gradient", "image"
+    ]) {
+        Ok(o) => o,
+        // "background: red" or "background: rgba(0, 0, 0, 0)"
+        Err(e) => return parse_css_color(input)
+            .map(|color| StyleBackground::Color(StyleBackgroundColor(color)))
+            .map_err(|_| e.into()),
+    };
 
     let background_type = match background_type {
-        "none" => { return Ok(StyleBackground::NoBackground); },
         "linear-gradient" => BackgroundType::LinearGradient,
         "repeating-linear-gradient" => BackgroundType::RepeatingLinearGradient,
         "radial-gradient" => BackgroundType::RadialGradient,
         "repeating-radial-gradient" => BackgroundType::RepeatingRadialGradient,
+        "conic-gradient" => BackgroundType::ConicGradient,
+        "repeating-conic-gradient" => BackgroundType::RepeatingConicGradient,
         "image" => BackgroundType::Image,
         _ => unreachable!(),
     };
@@ -1301,6 +1863,54 @@ pub fn parse_style_background<'a>(input: &'a str)
     parse_gradient(brace_contents, background_type)
 }
 
+// parses the layers of a background, such as "image(\"icon\"), linear-gradient(red, green)"
+pub fn parse_style_backgrounds<'a>(input: &'a str)
+-> Result<StyleBackgrounds, CssBackgroundParseError<'a>>
+{
+    Ok(StyleBackgrounds(parse_comma_separated(input, parse_style_background)?))
+}
+
+/// Parses a comma-separated list of values (i.e. the layers of a `background-*`
+/// property), ignoring the commas inside of braces such as `rgba(0, 0, 0, 0)`.
+pub fn parse_comma_separated<'a, T, E, F>(input: &'a str, parse_item: F)
+-> Result<Vec<T>, E> where F: Fn(&'a str) -> Result<T, E>
+{
+    let items = split_comma_separated(input);
+    if items.is_empty() {
+        return Ok(vec![parse_item(input.trim())?]);
+    }
+    items.into_iter().map(|item| parse_item(item.trim())).collect()
+}
+
+/// Splits the input at all commas that are not inside of braces
+fn split_comma_separated<'a>(input: &'a str) -> Vec<&'a str> {
+
+    // Splittin the input by "," doesn't work since rgba() might contain commas
+    let mut comma_separated_items = Vec::<&str>::new();
+    let mut current_input = &input[..];
+
+    'outer: loop {
+        let (skip_next_braces_result, character_was_found) =
+        match skip_next_braces(&current_input, ',') {
+            Some(s) => s,
+            None => break 'outer,
+        };
+        let new_push_item = if character_was_found {
+            &current_input[..skip_next_braces_result]
+        } else {
+            &current_input[..]
+        };
+        let new_current_input = &current_input[(skip_next_braces_result + 1)..];
+        comma_separated_items.push(new_push_item);
+        current_input = new_current_input;
+        if !character_was_found {
+            break 'outer;
+        }
+    }
+
+    comma_separated_items
+}
+
 /// Given a string, returns how many characters need to be skipped
 fn skip_next_braces(input: &str, target_char: char) -> Option<(usize, bool)> {
 
@@ -1345,28 +1955,7 @@ pub fn parse_gradient<'a>(input: &'a str, background_type: BackgroundType)
         return Ok(image.into());
     }
 
-    // Splittin the input by "," doesn't work since rgba() might contain commas
-    let mut comma_separated_items = Vec::<&str>::new();
-    let mut current_input = &input[..];
-
-    'outer: loop {
-        let (skip_next_braces_result, character_was_found) =
-        match skip_next_braces(&current_input, ',') {
-            Some(s) => s,
-            None => break 'outer,
-        };
-        let new_push_item = if character_was_found {
-            &current_input[..skip_next_braces_result]
-        } else {
-            &current_input[..]
-        };
-        let new_current_input = &current_input[(skip_next_braces_result + 1)..];
-        comma_separated_items.push(new_push_item);
-        current_input = new_current_input;
-        if !character_was_found {
-            break 'outer;
-        }
-    }
+    let comma_separated_items = split_comma_separated(input);
 
     let mut brace_iterator = comma_separated_items.iter();
     let mut gradient_stop_count = brace_iterator.clone().count();
@@ -1382,8 +1971,15 @@ pub fn parse_gradient<'a>(input: &'a str, background_type: BackgroundType)
     // default gradient: from top to bottom
     let mut direction = Direction::FromTo(DirectionCorner::Top, DirectionCorner::Bottom);
 
+    // default conic gradient: starting at the top, centered
+    let mut conic_start = (0.0, StyleBackgroundPosition {
+        horizontal: BackgroundPositionHorizontal::Center,
+        vertical: BackgroundPositionVertical::Center,
+    });
+
     let mut first_is_direction = false;
     let mut first_is_shape = false;
+    let mut first_is_conic_start = false;
 
     let is_linear_gradient = background_type == BackgroundType::LinearGradient ||
                              background_type == BackgroundType::RepeatingLinearGradient;
@@ -1405,8 +2001,20 @@ pub fn parse_gradient<'a>(input: &'a str, background_type: BackgroundType)
         }
     }
 
+    let is_conic_gradient = background_type == BackgroundType::ConicGradient ||
+                            background_type == BackgroundType::RepeatingConicGradient;
+
+    if is_conic_gradient {
+        if let Some(start) = parse_conic_gradient_start(first_brace_item) {
+            conic_start = start;
+            first_is_conic_start = true;
+        }
+    }
+
     let mut first_item_doesnt_count = false;
-    if (is_linear_gradient && first_is_direction) || (is_radial_gradient && first_is_shape) {
+    if (is_linear_gradient && first_is_direction) ||
+       (is_radial_gradient && first_is_shape) ||
+       (is_conic_gradient && first_is_conic_start) {
         gradient_stop_count -= 1; // first item is not a gradient stop
         first_item_doesnt_count = true;
     }
@@ -1415,13 +2023,16 @@ pub fn parse_gradient<'a>(input: &'a str, background_type: BackgroundType)
         return Err(CssBackgroundParseError::TooFewGradientStops(input));
     }
 
+    // Conic gradients can also use angles as stop offsets ("red 90deg")
+    let parse_stop = if is_conic_gradient { parse_conic_gradient_stop } else { parse_gradient_stop };
+
     let mut color_stops = Vec::<GradientStopPre>::with_capacity(gradient_stop_count);
     if !first_item_doesnt_count {
-        color_stops.push(parse_gradient_stop(first_brace_item)?);
+        color_stops.push(parse_stop(first_brace_item)?);
     }
 
     for stop in brace_iterator {
-        color_stops.push(parse_gradient_stop(stop)?);
+        color_stops.push(parse_stop(stop)?);
     }
 
     normalize_color_stops(&mut color_stops);
@@ -1455,65 +2066,100 @@ pub fn parse_gradient<'a>(input: &'a str, background_type: BackgroundType)
                 stops: color_stops,
             }))
         },
+        BackgroundType::ConicGradient | BackgroundType::RepeatingConicGradient => {
+            Ok(StyleBackground::ConicGradient(ConicGradient {
+                angle: FloatValue::new(conic_start.0),
+                center: conic_start.1,
+                extend_mode: if background_type == BackgroundType::ConicGradient { ExtendMode::Clamp } else { ExtendMode::Repeat },
+                stops: color_stops,
+            }))
+        },
         BackgroundType::Image => unreachable!(),
     }
 }
 
-// Normalize the percentages of the parsed color stops
-pub fn normalize_color_stops(color_stops: &mut Vec<GradientStopPre>) {
+/// Parses the `from 90deg at right top` part of a conic gradient, returns the angle in degrees and the center
+fn parse_conic_gradient_start(input: &str) -> Option<(f32, StyleBackgroundPosition)> {
+
+    let input = input.trim();
+
+    let (from, at) = if input.starts_with("at ") {
+        (None, Some(&input[3..]))
+    } else if input.starts_with("from ") {
+        let rest = input[5..].trim();
+        match rest.find(" at ") {
+            Some(idx) => (Some(&rest[..idx]), Some(&rest[(idx + 4)..])),
+            None => (Some(rest), None),
+        }
+    } else {
+        return None;
+    };
 
-    let mut last_stop = PercentageValue::new(0.0);
-    let mut increase_stop_cnt: Option<f32> = None;
+    let angle = match from {
+        Some(from) => parse_angle_degrees(from.trim())?,
+        None => 0.0,
+    };
+
+    let center = match at {
+        Some(at) => parse_style_background_position(at).ok()?,
+        None => StyleBackgroundPosition {
+            horizontal: BackgroundPositionHorizontal::Center,
+            vertical: BackgroundPositionVertical::Center,
+        },
+    };
+
+    Some((angle, center))
+}
+
+// parses "red", "red 5%" or "red 90deg"
+fn parse_conic_gradient_stop<'a>(input: &'a str)
+-> Result<GradientStopPre, CssGradientStopParseError<'a>>
+{
+    let input = input.trim();
+
+    if let Some(last_whitespace) = input.rfind(char::is_whitespace) {
+        if let Some(degrees) = parse_angle_degrees(&input[(last_whitespace + 1)..]) {
+            let color = parse_css_color(input[..last_whitespace].trim())?;
+            return Ok(GradientStopPre { offset: Some(PercentageValue::new(degrees / 360.0 * 100.0)), color });
+        }
+    }
+
+    parse_gradient_stop(input)
+}
+
+// Normalize the percentages of the parsed color stops: The first and last stop default
+// to 0% and 100%, stops without an offset are spaced evenly between their neighbours
+pub fn normalize_color_stops(color_stops: &mut Vec<GradientStopPre>) {
 
     let color_stop_len = color_stops.len();
-    'outer: for i in 0..color_stop_len {
-        let offset = color_stops[i].offset;
-        match offset {
-            Some(s) => {
-                last_stop = s;
-                increase_stop_cnt = None;
-            },
-            None => {
-                let (_, next) = color_stops.split_at_mut(i);
+    if color_stop_len == 0 {
+        return;
+    }
 
-                if let Some(increase_stop_cnt) = increase_stop_cnt {
-                    last_stop = PercentageValue::new(last_stop.get() + increase_stop_cnt);
-                    next[0].offset = Some(last_stop);
-                    continue 'outer;
-                }
+    if color_stops[0].offset.is_none() {
+        color_stops[0].offset = Some(PercentageValue::new(0.0));
+    }
 
-                let mut next_count: u32 = 0;
-                let mut next_value = None;
-
-                // iterate until we find a value where the offset isn't none
-                {
-                    let mut next_iter = next.iter();
-                    next_iter.next();
-                    'inner: for next_stop in next_iter {
-                        if let Some(off) = next_stop.offset {
-                            next_value = Some(off);
-                            break 'inner;
-                        } else {
-                            next_count += 1;
-                        }
-                    }
-                }
+    if color_stops[color_stop_len - 1].offset.is_none() {
+        color_stops[color_stop_len - 1].offset = Some(PercentageValue::new(100.0));
+    }
 
-                let next_value = next_value.unwrap_or(PercentageValue::new(100.0));
-                let increase = (next_value.get() / (next_count as f32)) - (last_stop.get() / (next_count as f32)) ;
-                increase_stop_cnt = Some(increase);
-                if next_count == 1 && (color_stop_len - i) == 1 {
-                    next[0].offset = Some(last_stop);
-                } else {
-                    if i == 0 {
-                        next[0].offset = Some(PercentageValue::new(0.0));
-                    } else {
-                        next[0].offset = Some(last_stop);
-                        // last_stop += increase;
-                    }
-                }
-            }
+    let mut last_positioned = 0;
+    for i in 1..color_stop_len {
+        let next_stop = match color_stops[i].offset {
+            Some(s) => s.get(),
+            None => continue,
+        };
+
+        // Distribute the stops between the last positioned stop and this one
+        let last_stop = color_stops[last_positioned].offset.unwrap().get();
+        let steps = (i - last_positioned) as f32;
+        for j in (last_positioned + 1)..i {
+            let step = (j - last_positioned) as f32;
+            color_stops[j].offset = Some(PercentageValue::new(last_stop + (next_stop - last_stop) * step / steps));
         }
+
+        last_positioned = i;
     }
 }
 
@@ -1609,7 +2255,7 @@ pub fn parse_gradient_stop<'a>(input: &'a str)
                 }
             } else {
                 // #abc + percentage_value
-                (&input[..last_whitespace], None)
+                (&input[..last_whitespace], Some(&input[(last_whitespace + 1)..]))
             }
         },
         None => (input, None) // #abc, no percentage value
@@ -1880,6 +2526,42 @@ pub fn parse_layout_flex_shrink<'a>(input: &'a str) -> Result<LayoutFlexShrink,
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum AspectRatioParseError<'a> {
+    ParseFloat(ParseFloatError, &'a str),
+    /// More than one `/` in the ratio
+    TooManyComponents(&'a str),
+    /// Width or height of the ratio is zero or negative
+    InvalidRatio(&'a str),
+}
+
+impl_display!{AspectRatioParseError<'a>, {
+    ParseFloat(e, orig_str) => format!("aspect-ratio: Could not parse floating-point value: \"{}\" - Error: \"{}\"", orig_str, e),
+    TooManyComponents(orig_str) => format!("aspect-ratio: Expected \"width / height\", got: \"{}\"", orig_str),
+    InvalidRatio(orig_str) => format!("aspect-ratio: Width and height have to be positive: \"{}\"", orig_str),
+}}
+
+/// Parses an `aspect-ratio`, such as `16 / 9` or `1.5` (which is the same as `1.5 / 1`)
+pub fn parse_layout_aspect_ratio<'a>(input: &'a str) -> Result<LayoutAspectRatio, AspectRatioParseError<'a>> {
+    let mut components = input.split('/');
+    let width = components.next().unwrap_or("");
+    let height = components.next().unwrap_or("1");
+
+    if components.next().is_some() {
+        return Err(AspectRatioParseError::TooManyComponents(input));
+    }
+
+    let aspect_ratio = LayoutAspectRatio {
+        width: parse_float_value(width).map_err(|e| AspectRatioParseError::ParseFloat(e, input))?,
+        height: parse_float_value(height).map_err(|e| AspectRatioParseError::ParseFloat(e, input))?,
+    };
+
+    match aspect_ratio.get() {
+        Some(_) => Ok(aspect_ratio),
+        None => Err(AspectRatioParseError::InvalidRatio(input)),
+    }
+}
+
 pub fn parse_style_tab_width(input: &str)
 -> Result<StyleTabWidth, PercentageParseError>
 {
@@ -2050,6 +2732,21 @@ multi_type_parser!(parse_style_background_repeat, StyleBackgroundRepeat,
                     ["repeat-x", RepeatX],
                     ["repeat-y", RepeatY]);
 
+multi_type_parser!(parse_style_background_origin, StyleBackgroundOrigin,
+                    ["border-box", BorderBox],
+                    ["padding-box", PaddingBox],
+                    ["content-box", ContentBox]);
+
+multi_type_parser!(parse_style_background_clip, StyleBackgroundClip,
+                    ["border-box", BorderBox],
+                    ["padding-box", PaddingBox],
+                    ["content-box", ContentBox]);
+
+multi_type_parser!(parse_style_background_attachment, StyleBackgroundAttachment,
+                    ["scroll", Scroll],
+                    ["fixed", Fixed],
+                    ["local", Local]);
+
 multi_type_parser!(parse_layout_direction, LayoutDirection,
                     ["row", Row],
                     ["row-reverse", RowReverse],
@@ -2088,7 +2785,9 @@ multi_type_parser!(parse_shape, Shape,
 multi_type_parser!(parse_layout_position, LayoutPosition,
                     ["static", Static],
                     ["absolute", Absolute],
-                    ["relative", Relative]);
+                    ["relative", Relative],
+                    ["fixed", Fixed],
+                    ["sticky", Sticky]);
 
 multi_type_parser!(parse_layout_text_overflow, Overflow,
                     ["auto", Auto],
@@ -2209,6 +2908,76 @@ mod css_tests {
         })));
     }
 
+    #[test]
+    fn test_parse_box_shadow_11() {
+        assert_eq!(parse_css_box_shadow("inset rgba(0, 0, 0, 0.5) 0px 0px 0px 2px"), Ok(Some(BoxShadowPreDisplayItem {
+            offset: [PixelValue::px(0.0), PixelValue::px(0.0)],
+            color: ColorU { r: 0, g: 0, b: 0, a: 128 },
+            blur_radius: PixelValue::px(0.0),
+            spread_radius: PixelValue::px(2.0),
+            clip_mode: BoxShadowClipMode::Inset,
+        })));
+    }
+
+    #[test]
+    fn test_parse_box_shadow_multiple() {
+        assert_eq!(parse_css_box_shadows("0px 1px 3px rgba(0, 0, 0, 0.2), 0px 1px 2px 1px black inset"), Ok(StyleBoxShadows(vec![
+            BoxShadowPreDisplayItem {
+                offset: [PixelValue::px(0.0), PixelValue::px(1.0)],
+                color: ColorU { r: 0, g: 0, b: 0, a: 51 },
+                blur_radius: PixelValue::px(3.0),
+                spread_radius: PixelValue::px(0.0),
+                clip_mode: BoxShadowClipMode::Outset,
+            },
+            BoxShadowPreDisplayItem {
+                offset: [PixelValue::px(0.0), PixelValue::px(1.0)],
+                color: ColorU { r: 0, g: 0, b: 0, a: 255 },
+                blur_radius: PixelValue::px(2.0),
+                spread_radius: PixelValue::px(1.0),
+                clip_mode: BoxShadowClipMode::Inset,
+            },
+        ])));
+        assert_eq!(parse_css_box_shadows("none"), Ok(StyleBoxShadows(Vec::new())));
+        assert_eq!(parse_css_box_shadows("5px 5px, none"), Err(CssShadowParseError::InvalidSingleStatement("none")));
+        assert_eq!(parse_css_box_shadows("5px 5px 1px 1px 1px"), Err(CssShadowParseError::TooManyComponents("5px 5px 1px 1px 1px")));
+    }
+
+    #[test]
+    fn test_parse_style_outline() {
+        assert_eq!(parse_style_outline("2px dashed blue"), Ok(StyleOutline {
+            width: Some(PixelValue::px(2.0)),
+            style: Some(BorderStyle::Dashed),
+            color: Some(ColorU { r: 0, g: 0, b: 255, a: 255 }),
+            offset: None,
+        }));
+        assert_eq!(parse_style_outline("rgba(0, 0, 0, 0.5) solid thick"), Ok(StyleOutline {
+            width: Some(PixelValue::px(5.0)),
+            style: Some(BorderStyle::Solid),
+            color: Some(ColorU { r: 0, g: 0, b: 0, a: 128 }),
+            offset: None,
+        }));
+        assert_eq!(parse_style_outline("solid"), Ok(StyleOutline {
+            width: Some(PixelValue::px(3.0)),
+            style: Some(BorderStyle::Solid),
+            color: None,
+            offset: None,
+        }));
+        assert_eq!(parse_style_outline("solid dashed"), Err(CssOutlineParseError::InvalidComponent("dashed")));
+        assert_eq!(parse_style_outline(""), Err(CssOutlineParseError::EmptyOutline));
+    }
+
+    #[test]
+    fn test_parse_outline_longhands() {
+        assert_eq!(parse_key_value_pair(CssPropertyType::OutlineOffset, "-2px"), Ok(CssProperty::Outline(StyleOutline {
+            offset: Some(PixelValue::px(-2.0)),
+            .. Default::default()
+        })));
+        assert_eq!(parse_key_value_pair(CssPropertyType::OutlineWidth, "thin"), Ok(CssProperty::Outline(StyleOutline {
+            width: Some(PixelValue::px(1.0)),
+            .. Default::default()
+        })));
+    }
+
     #[test]
     fn test_parse_css_border_1() {
         assert_eq!(
@@ -2269,7 +3038,7 @@ mod css_tests {
                     color: ColorU { r: 0, g: 0, b: 255, a: 255 },
                 },
                 GradientStopPre {
-                    offset: Some(PercentageValue::new(99.9999)), // note: not 100%, but close enough
+                    offset: Some(PercentageValue::new(100.0)),
                     color: ColorU { r: 255, g: 255, b: 0, a: 255 },
                 }],
         })));
@@ -2594,6 +3363,23 @@ mod css_tests {
         assert_eq!(parse_direction("60.9grad"), Ok(Direction::Angle(FloatValue::new(60.9 / 400.0 * 360.0))));
     }
 
+    #[test]
+    fn test_parse_layout_position() {
+        assert_eq!(parse_layout_position("relative"), Ok(LayoutPosition::Relative));
+        assert_eq!(parse_layout_position("fixed"), Ok(LayoutPosition::Fixed));
+        assert_eq!(parse_layout_position("sticky"), Ok(LayoutPosition::Sticky));
+        assert!(parse_layout_position("sticky-ish").is_err());
+    }
+
+    #[test]
+    fn test_parse_layout_aspect_ratio() {
+        assert_eq!(parse_layout_aspect_ratio("16 / 9"), Ok(LayoutAspectRatio::new(16.0, 9.0)));
+        assert_eq!(parse_layout_aspect_ratio("4/3"), Ok(LayoutAspectRatio::new(4.0, 3.0)));
+        assert_eq!(parse_layout_aspect_ratio("1.5"), Ok(LayoutAspectRatio::new(1.5, 1.0)));
+        assert_eq!(parse_layout_aspect_ratio("1 / 0"), Err(AspectRatioParseError::InvalidRatio("1 / 0")));
+        assert_eq!(parse_layout_aspect_ratio("1 / 2 / 3"), Err(AspectRatioParseError::TooManyComponents("1 / 2 / 3")));
+    }
+
     #[test]
     fn test_parse_float_value() {
         assert_eq!(parse_float_value("60.9"), Ok(FloatValue::new(60.9)));
@@ -2644,6 +3430,51 @@ mod css_tests {
         assert_eq!(parse_css_color("hsla(240deg, 0%, 0%  )"), Err(CssColorParseError::MissingColorComponent(CssColorComponent::Alpha)))
     }
 
+    #[test]
+    fn test_parse_css_color_hwb() {
+        assert_eq!(parse_css_color("hwb(0 0% 0%)"), Ok(ColorU { r: 255, g: 0, b: 0, a: 255 }));
+        assert_eq!(parse_css_color("hwb(120deg, 20%, 40%)"), Ok(ColorU { r: 51, g: 153, b: 51, a: 255 }));
+        assert_eq!(parse_css_color("hwb(0 60% 60% / 0.5)"), Ok(ColorU { r: 128, g: 128, b: 128, a: 128 }));
+        assert_eq!(parse_css_color("hwb(0 0%)"), Err(CssColorParseError::MissingColorComponent(CssColorComponent::Blackness)));
+    }
+
+    #[test]
+    fn test_parse_css_color_mix() {
+        assert_eq!(parse_css_color("color-mix(in srgb, red, blue)"), Ok(ColorU { r: 128, g: 0, b: 128, a: 255 }));
+        assert_eq!(parse_css_color("color-mix(in srgb, #ffffff 30%, rgb(0, 0, 0))"), Ok(ColorU { r: 77, g: 77, b: 77, a: 255 }));
+        assert_eq!(parse_css_color("color-mix(in srgb, 30% white, black 20%)"), Ok(ColorU { r: 153, g: 153, b: 153, a: 128 }));
+        assert_eq!(parse_css_color("color-mix(in srgb, red, transparent)"), Ok(ColorU { r: 255, g: 0, b: 0, a: 128 }));
+        assert_eq!(parse_css_color("color-mix(in hsl, red, blue)"), Err(CssColorParseError::UnsupportedColorSpace("in hsl")));
+        assert_eq!(parse_css_color("color-mix(in srgb, red)"), Err(CssColorParseError::InvalidColorMix("in srgb, red")));
+    }
+
+    #[test]
+    fn test_parse_css_color_lighten_darken() {
+        assert_eq!(parse_css_color("lighten(#000000, 50%)"), Ok(ColorU { r: 128, g: 128, b: 128, a: 255 }));
+        assert_eq!(parse_css_color("darken(red, 20%)"), Ok(ColorU { r: 153, g: 0, b: 0, a: 255 }));
+        assert_eq!(parse_css_color("lighten(white, 10%)"), Ok(ColorU { r: 255, g: 255, b: 255, a: 255 }));
+        assert_eq!(parse_css_color("darken(red)"), Err(CssColorParseError::MissingColorComponent(CssColorComponent::Lightness)));
+        assert_eq!(parse_css_color("transparent"), Ok(ColorU { r: 255, g: 255, b: 255, a: 0 }));
+    }
+
+    #[test]
+    fn test_parse_current_color_property() {
+        let placeholder = ColorU { r: 0, g: 0, b: 0, a: 255 };
+        assert_eq!(parse_current_color_property(CssPropertyType::Width, "500px"), None);
+        assert_eq!(parse_current_color_property(CssPropertyType::BackgroundColor, "currentColor"), Some(Ok(StyleBackgroundColor(placeholder).into())));
+        assert_eq!(parse_current_color_property(CssPropertyType::BorderTop, "1px solid currentcolor"), Some(Ok(StyleBorder {
+            top: Some(StyleBorderSide { border_width: PixelValue::px(1.0), border_style: BorderStyle::Solid, border_color: placeholder }),
+            .. Default::default()
+        }.into())));
+        assert_eq!(parse_current_color_property(CssPropertyType::Outline, "currentColor dashed 2px"), Some(Ok(StyleOutline {
+            width: Some(PixelValue::px(2.0)),
+            style: Some(BorderStyle::Dashed),
+            color: Some(placeholder),
+            offset: None,
+        }.into())));
+        assert_eq!(parse_current_color_property(CssPropertyType::Width, "currentColor"), Some(Err(CssParsingError::InvalidCurrentColor("currentColor"))));
+    }
+
     #[test]
     fn test_parse_pixel_value_1() {
         assert_eq!(parse_pixel_value("15px"), Ok(PixelValue::px(15.0)));
@@ -2727,6 +3558,139 @@ mod css_tests {
         )));
     }
 
+    #[test]
+    fn test_parse_multiple_backgrounds() {
+        assert_eq!(parse_style_backgrounds("image(\"icon\"), linear-gradient(rgba(0, 0, 0, 0.5) 0%, red 100%), blue"), Ok(StyleBackgrounds(vec![
+            StyleBackground::Image(CssImageId(String::from("icon"))),
+            StyleBackground::LinearGradient(LinearGradient {
+                direction: Direction::FromTo(DirectionCorner::Top, DirectionCorner::Bottom),
+                extend_mode: ExtendMode::Clamp,
+                stops: vec![
+                    GradientStopPre { offset: Some(PercentageValue::new(0.0)), color: ColorU { r: 0, g: 0, b: 0, a: 128 } },
+                    GradientStopPre { offset: Some(PercentageValue::new(100.0)), color: ColorU { r: 255, g: 0, b: 0, a: 255 } },
+                ],
+            }),
+            StyleBackground::Color(StyleBackgroundColor(ColorU { r: 0, g: 0, b: 255, a: 255 })),
+        ])));
+    }
+
+    #[test]
+    fn test_parse_background_layer_properties() {
+        assert_eq!(
+            parse_key_value_pair(CssPropertyType::BackgroundRepeat, "no-repeat, repeat-x"),
+            Ok(CssProperty::BackgroundRepeat(StyleBackgroundRepeats(vec![StyleBackgroundRepeat::NoRepeat, StyleBackgroundRepeat::RepeatX])))
+        );
+        assert_eq!(
+            parse_key_value_pair(CssPropertyType::BackgroundClip, "content-box"),
+            Ok(CssProperty::BackgroundClip(StyleBackgroundClips(vec![StyleBackgroundClip::ContentBox])))
+        );
+        assert!(parse_key_value_pair(CssPropertyType::BackgroundOrigin, "padding-box, margin-box").is_err());
+    }
+
+    #[test]
+    fn test_background_layer_values_repeat() {
+        let repeats = StyleBackgroundRepeats(vec![StyleBackgroundRepeat::NoRepeat, StyleBackgroundRepeat::RepeatX]);
+        assert_eq!(repeats.get(2), Some(&StyleBackgroundRepeat::NoRepeat));
+        assert_eq!(repeats.get(3), Some(&StyleBackgroundRepeat::RepeatX));
+        assert_eq!(StyleBackgroundRepeats(Vec::new()).get(0), None);
+    }
+
+    #[test]
+    fn test_parse_background_position_keywords() {
+        use azul_css::BackgroundPositionHorizontal as H;
+        use azul_css::BackgroundPositionVertical as V;
+        let pos = |horizontal, vertical| Ok(StyleBackgroundPosition { horizontal, vertical });
+        assert_eq!(parse_style_background_position("right bottom"), pos(H::Right, V::Bottom));
+        assert_eq!(parse_style_background_position("top left"), pos(H::Left, V::Top));
+        assert_eq!(parse_style_background_position("center"), pos(H::Center, V::Center));
+        assert_eq!(parse_style_background_position("bottom"), pos(H::Center, V::Bottom));
+        assert_eq!(parse_style_background_position("right"), pos(H::Right, V::Center));
+    }
+
+    #[test]
+    fn test_parse_background_position_lengths() {
+        use azul_css::BackgroundPositionHorizontal as H;
+        use azul_css::BackgroundPositionVertical as V;
+        assert_eq!(parse_style_background_position("10px 2em"), Ok(StyleBackgroundPosition {
+            horizontal: H::Exact(PixelValue::px(10.0)),
+            vertical: V::Exact(PixelValue::em(2.0)),
+        }));
+        assert_eq!(parse_style_background_position("5px bottom"), Ok(StyleBackgroundPosition {
+            horizontal: H::Exact(PixelValue::px(5.0)),
+            vertical: V::Bottom,
+        }));
+    }
+
+    #[test]
+    fn test_parse_background_position_invalid() {
+        assert_eq!(parse_style_background_position("left right"), Err(CssBackgroundPositionParseError::InvalidComponent("left")));
+        assert_eq!(parse_style_background_position("left top 5px"), Err(CssBackgroundPositionParseError::TooManyComponents("left top 5px")));
+        assert_eq!(parse_style_background_position(""), Err(CssBackgroundPositionParseError::NoPosition("")));
+    }
+
+    #[test]
+    fn test_parse_filter_functions() {
+        assert_eq!(parse_style_filter_functions("blur(5px) grayscale(50%) hue-rotate(0.5turn) brightness()"), Ok(vec![
+            StyleFilterFunction::Blur(PixelValue::px(5.0)),
+            StyleFilterFunction::Grayscale(PercentageValue::new(0.5)),
+            StyleFilterFunction::HueRotate(FloatValue::new(180.0)),
+            StyleFilterFunction::Brightness(PercentageValue::new(1.0)),
+        ]));
+        assert_eq!(parse_style_filter_functions("none"), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test_parse_filter_drop_shadow() {
+        let shadow = StyleDropShadow {
+            offset: [PixelValue::px(2.0), PixelValue::px(3.0)],
+            blur_radius: PixelValue::px(0.0),
+            color: ColorU { r: 0, g: 0, b: 0, a: 128 },
+        };
+        assert_eq!(parse_style_filter_functions("drop-shadow(2px 3px rgba(0, 0, 0, 0.5))"), Ok(vec![StyleFilterFunction::DropShadow(shadow)]));
+        assert_eq!(parse_style_filter_functions("drop-shadow(rgba(0, 0, 0, 0.5) 2px 3px)"), Ok(vec![StyleFilterFunction::DropShadow(shadow)]));
+    }
+
+    #[test]
+    fn test_parse_filter_invalid() {
+        assert_eq!(parse_style_filter_functions("blurry(5px)"), Err(CssStyleFilterParseError::InvalidFilter("blurry")));
+        assert_eq!(parse_style_filter_functions("blur(5px"), Err(CssStyleFilterParseError::UnclosedFilter("blur(5px")));
+        assert_eq!(parse_style_filter_functions("sepia(lots)"), Err(CssStyleFilterParseError::InvalidAmount("lots")));
+        assert_eq!(parse_style_filter_functions("drop-shadow(2px)"), Err(CssStyleFilterParseError::InvalidDropShadow("2px")));
+    }
+
+    #[test]
+    fn test_parse_conic_gradient() {
+        assert_eq!(parse_style_background("conic-gradient(from 0.25turn at right top, red, blue 90deg, green)"), Ok(StyleBackground::ConicGradient(ConicGradient {
+            angle: FloatValue::new(90.0),
+            center: StyleBackgroundPosition {
+                horizontal: BackgroundPositionHorizontal::Right,
+                vertical: BackgroundPositionVertical::Top,
+            },
+            extend_mode: ExtendMode::Clamp,
+            stops: vec![
+                GradientStopPre { offset: Some(PercentageValue::new(0.0)), color: ColorU { r: 255, g: 0, b: 0, a: 255 } },
+                GradientStopPre { offset: Some(PercentageValue::new(25.0)), color: ColorU { r: 0, g: 0, b: 255, a: 255 } },
+                GradientStopPre { offset: Some(PercentageValue::new(100.0)), color: ColorU { r: 0, g: 128, b: 0, a: 255 } },
+            ],
+        })));
+    }
+
+    #[test]
+    fn test_parse_repeating_conic_gradient() {
+        assert_eq!(parse_style_background("repeating-conic-gradient(black 0%, white 12.5%)"), Ok(StyleBackground::ConicGradient(ConicGradient {
+            angle: FloatValue::new(0.0),
+            center: StyleBackgroundPosition {
+                horizontal: BackgroundPositionHorizontal::Center,
+                vertical: BackgroundPositionVertical::Center,
+            },
+            extend_mode: ExtendMode::Repeat,
+            stops: vec![
+                GradientStopPre { offset: Some(PercentageValue::new(0.0)), color: ColorU { r: 0, g: 0, b: 0, a: 255 } },
+                GradientStopPre { offset: Some(PercentageValue::new(12.5)), color: ColorU { r: 255, g: 255, b: 255, a: 255 } },
+            ],
+        })));
+    }
+
     #[test]
     fn test_parse_padding_1() {
         assert_eq!(parse_layout_padding("10px"), Ok(LayoutPadding {
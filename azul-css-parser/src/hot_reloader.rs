@@ -4,6 +4,7 @@
 use azul_css::{HotReloadHandler, Css};
 use std::time::Duration;
 use std::path::PathBuf;
+use style_document::StyleDocumentFormat;
 
 pub const DEFAULT_RELOAD_INTERVAL: Duration = Duration::from_millis(500);
 
@@ -12,13 +13,15 @@ pub const DEFAULT_RELOAD_INTERVAL: Duration = Duration::from_millis(500);
 pub struct HotReloader {
     file_path: PathBuf,
     reload_interval: Duration,
+    /// Warnings of the last reload, see `HotReloadHandler::get_warnings`
+    warnings: Vec<String>,
 }
 
 impl HotReloader {
     /// Creates a HotReloader that will load a style directly from the CSS file
     /// at the given path.
     pub fn new<P: Into<PathBuf>>(file_path: P) -> Self {
-        Self { file_path: file_path.into(), reload_interval: DEFAULT_RELOAD_INTERVAL }
+        Self { file_path: file_path.into(), reload_interval: DEFAULT_RELOAD_INTERVAL, warnings: Vec::new() }
     }
 
     pub fn with_reload_interval(self, reload_interval: Duration) -> Self {
@@ -36,10 +39,86 @@ impl HotReloadHandler for HotReloader {
         let reloaded_css = fs::read_to_string(&self.file_path)
             .map_err(|e| format!("Io error: Could not load \"{}\" when loading file: \"{}\"", file_name, e))?;
 
-        css::new_from_str(&reloaded_css).map_err(|e| format!("{}: {}", file_name, e))
+        // Parse leniently, so that a typo only drops one property instead of the whole style
+        let (css, warnings) = css::new_from_str_lenient(&reloaded_css, Some(&file_name));
+        self.warnings = warnings.iter().map(|w| format!("{}: {}", file_name, w)).collect();
+
+        Ok(css)
     }
 
     fn get_reload_interval(&self) -> Duration {
         self.reload_interval
     }
+
+    fn get_warnings(&self) -> &[String] {
+        &self.warnings
+    }
+}
+
+/// Allows dynamic reloading of a JSON, TOML or RON style document (see the
+/// `style_document` module) at application runtime.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StyleDocumentHotReloader {
+    file_path: PathBuf,
+    format: StyleDocumentFormat,
+    reload_interval: Duration,
+}
+
+impl StyleDocumentHotReloader {
+    /// Creates a hot-reloader for the style document at the given path
+    pub fn new<P: Into<PathBuf>>(file_path: P, format: StyleDocumentFormat) -> Self {
+        Self { file_path: file_path.into(), format, reload_interval: DEFAULT_RELOAD_INTERVAL }
+    }
+
+    /// Same as `new`, but determines the format from the file extension.
+    /// Returns `None` if the extension isn't `.json`, `.toml` or `.ron`.
+    pub fn from_path<P: Into<PathBuf>>(file_path: P) -> Option<Self> {
+        let file_path = file_path.into();
+        let format = StyleDocumentFormat::from_extension(&file_path.extension()?.to_string_lossy())?;
+        Some(Self::new(file_path, format))
+    }
+
+    pub fn with_reload_interval(self, reload_interval: Duration) -> Self {
+        Self { reload_interval, .. self }
+    }
+}
+
+impl HotReloadHandler for StyleDocumentHotReloader {
+    fn reload_style(&mut self) -> Result<Css, String> {
+        use std::fs;
+        use style_document;
+
+        let file_name = self.file_path.file_name().map(|os_str| os_str.to_string_lossy()).unwrap_or_default();
+
+        let document = fs::read_to_string(&self.file_path)
+            .map_err(|e| format!("Io error: Could not load \"{}\" when loading file: \"{}\"", file_name, e))?;
+
+        style_document::new_from_style_document_str(&document, self.format)
+            .map_err(|e| format!("{}: {}", file_name, e))
+    }
+
+    fn get_reload_interval(&self) -> Duration {
+        self.reload_interval
+    }
+}
+
+#[test]
+fn test_hot_reloader_reports_warnings() {
+    use std::{env, fs};
+
+    let file_path = env::temp_dir().join("azul_test_hot_reloader_reports_warnings.css");
+    let mut hot_reloader = HotReloader::new(file_path.clone());
+
+    fs::write(&file_path, ".a { colr: red; width: 5px; }").unwrap();
+    let css = hot_reloader.reload_style().unwrap();
+    assert_eq!(css.stylesheets[0].rules[0].declarations.len(), 1);
+    assert_eq!(hot_reloader.get_warnings().len(), 1);
+    assert!(hot_reloader.get_warnings()[0].starts_with("azul_test_hot_reloader_reports_warnings.css: "));
+
+    // Fixing the style clears the warnings again
+    fs::write(&file_path, ".a { color: red; width: 5px; }").unwrap();
+    hot_reloader.reload_style().unwrap();
+    assert!(hot_reloader.get_warnings().is_empty());
+
+    fs::remove_file(&file_path).unwrap();
 }
@@ -13,27 +13,55 @@
 
 extern crate azul_css;
 extern crate simplecss;
-#[cfg(feature = "serde_serialization")]
+#[cfg(any(feature = "serde_serialization", feature = "style_json", feature = "style_toml", feature = "style_ron"))]
 extern crate serde;
+#[cfg(any(feature = "style_json", all(test, feature = "serde_serialization")))]
+extern crate serde_json;
+#[cfg(feature = "style_toml")]
+extern crate toml;
+#[cfg(feature = "style_ron")]
+extern crate ron;
 
 #[macro_use]
 mod macros;
 
 mod css_parser;
 mod css;
+mod css_lint;
 mod hot_reloader;
+pub mod style_document;
 
 pub use css::{
     new_from_str,
+    new_from_str_with_file_name,
+    new_from_str_lenient,
     parse_css_path,
     CssParseError,
+    CssParseErrorInner,
+    CssParseWarning,
+    ErrorLocation,
     CssPathParseError,
 };
 
 pub use css_parser::*;
 
+pub use css_lint::{
+    lint_css,
+    CssLintWarning,
+    CssLintWarningKind,
+};
+
 pub use hot_reloader::{
     HotReloader,
+    StyleDocumentHotReloader,
+};
+
+pub use style_document::{
+    new_from_style_document,
+    new_from_style_document_str,
+    StyleDocument,
+    StyleDocumentError,
+    StyleDocumentFormat,
 };
 
 
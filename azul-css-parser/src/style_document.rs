@@ -0,0 +1,382 @@
+//! Alternative style frontend: loads styles from structured documents (JSON, TOML or RON)
+//! instead of CSS. A style document maps selectors to maps of CSS properties:
+//!
+//! ```json
+//! {
+//!     ".button": { "color": "#ffffff", "padding": "5px" },
+//!     ".button:hover": { "background-color": "#4286f4" }
+//! }
+//! ```
+//!
+//! Property values are parsed exactly like their CSS counterparts. Numbers
+//! are accepted as unitless values (i.e. `"flex-grow": 1`).
+
+use azul_css::{Css, CssRuleBlock, CssPath, Stylesheet};
+use css::{parse_css_path, determine_static_or_dynamic_css_property};
+
+/// Source format of a style document
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StyleDocumentFormat {
+    /// Needs the `style_json` feature
+    Json,
+    /// Needs the `style_toml` feature
+    Toml,
+    /// Needs the `style_ron` feature
+    Ron,
+}
+
+impl StyleDocumentFormat {
+    /// Guesses the format from a file extension (`json`, `toml` or `ron`)
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        use self::StyleDocumentFormat::*;
+        match extension.to_lowercase().as_str() {
+            "json" => Some(Json),
+            "toml" => Some(Toml),
+            "ron" => Some(Ron),
+            _ => None,
+        }
+    }
+}
+
+impl_display!{ StyleDocumentFormat, {
+    Json => "JSON",
+    Toml => "TOML",
+    Ron => "RON",
+}}
+
+/// Selectors mapped to their properties, in document order
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct StyleDocument {
+    pub rules: Vec<StyleDocumentRule>,
+}
+
+/// One `"selector": { "key": "value" }` entry of a `StyleDocument`
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct StyleDocumentRule {
+    /// CSS selector, may contain multiple comma-separated paths
+    pub selector: String,
+    /// `(key, value)` pairs, in document order
+    pub declarations: Vec<(String, String)>,
+}
+
+/// Error that can happen when loading a `StyleDocument`. Errors in the document
+/// content carry the path to the offending entry (selector and property key).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum StyleDocumentError {
+    /// The feature that is necessary for parsing this format isn't enabled
+    UnsupportedFormat(StyleDocumentFormat),
+    /// The document isn't valid JSON / TOML / RON or isn't a map of maps
+    Syntax(StyleDocumentFormat, String),
+    /// The selector of a rule couldn't be parsed
+    InvalidSelector { selector: String, error: String },
+    /// The key of a property isn't a known CSS property
+    UnknownPropertyKey { selector: String, key: String },
+    /// The value of a property couldn't be parsed
+    InvalidPropertyValue { selector: String, key: String, error: String },
+}
+
+impl_display!{ StyleDocumentError, {
+    UnsupportedFormat(format) => format!("Loading {} styles requires the \"style_{}\" feature", format, format.to_string().to_lowercase()),
+    Syntax(format, e) => format!("Invalid {} style document: {}", format, e),
+    InvalidSelector { selector, error } => format!("[\"{}\"]: invalid selector: {}", selector, error),
+    UnknownPropertyKey { selector, key } => format!("[\"{}\"][\"{}\"]: unknown CSS key", selector, key),
+    InvalidPropertyValue { selector, key, error } => format!("[\"{}\"][\"{}\"]: {}", selector, key, error),
+}}
+
+/// Parses a style document in the given format into a `Css`
+pub fn new_from_style_document_str(input: &str, format: StyleDocumentFormat) -> Result<Css, StyleDocumentError> {
+    new_from_style_document(&parse_style_document(input, format)?)
+}
+
+/// Parses the raw document structure, without validating selectors and properties
+pub fn parse_style_document(input: &str, format: StyleDocumentFormat) -> Result<StyleDocument, StyleDocumentError> {
+    use self::StyleDocumentFormat::*;
+    match format {
+        Json => parse_json(input),
+        Toml => parse_toml(input),
+        Ron => parse_ron(input),
+    }
+}
+
+/// Converts a `StyleDocument` into a `Css`, stops at the first invalid selector or property
+pub fn new_from_style_document(document: &StyleDocument) -> Result<Css, StyleDocumentError> {
+
+    let css_property_map = azul_css::get_css_key_map();
+    let mut rules = Vec::new();
+
+    for rule in &document.rules {
+
+        let paths = split_selector_list(&rule.selector).into_iter().map(|path| {
+            parse_css_path(path).map_err(|e| StyleDocumentError::InvalidSelector {
+                selector: rule.selector.clone(),
+                error: format!("{:?}", e),
+            })
+        }).collect::<Result<Vec<CssPath>, StyleDocumentError>>()?;
+
+        let declarations = rule.declarations.iter().map(|(key, value)| {
+            let parsed_key = azul_css::CssPropertyType::from_str(key, &css_property_map)
+                .ok_or_else(|| StyleDocumentError::UnknownPropertyKey {
+                    selector: rule.selector.clone(),
+                    key: key.clone(),
+                })?;
+            determine_static_or_dynamic_css_property(parsed_key, value)
+                .map_err(|e| StyleDocumentError::InvalidPropertyValue {
+                    selector: rule.selector.clone(),
+                    key: key.clone(),
+                    error: e.to_string(),
+                })
+        }).collect::<Result<Vec<_>, StyleDocumentError>>()?;
+
+        rules.extend(paths.into_iter().map(|path| CssRuleBlock::new(path, declarations.clone())));
+    }
+
+    Ok(Css { stylesheets: vec![Stylesheet::from(rules)] })
+}
+
+/// Splits a selector list (i.e. `.a, .b`) at the commas that aren't nested
+/// inside of brackets or quotes, such as in `:not(.a, .b)` or `[title="a, b"]`
+fn split_selector_list(input: &str) -> Vec<&str> {
+    let mut selectors = Vec::new();
+    let mut depth = 0;
+    let mut quote = None;
+    let mut last = 0;
+    for (i, c) in input.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => { },
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (None, '(') | (None, '[') => depth += 1,
+            (None, ')') | (None, ']') => depth -= 1,
+            (None, ',') if depth == 0 => {
+                selectors.push(input[last..i].trim());
+                last = i + 1;
+            },
+            _ => { },
+        }
+    }
+    selectors.push(input[last..].trim());
+    selectors
+}
+
+#[cfg(feature = "style_json")]
+fn parse_json(input: &str) -> Result<StyleDocument, StyleDocumentError> {
+    ::serde_json::from_str(input).map_err(|e| StyleDocumentError::Syntax(StyleDocumentFormat::Json, e.to_string()))
+}
+
+#[cfg(not(feature = "style_json"))]
+fn parse_json(_input: &str) -> Result<StyleDocument, StyleDocumentError> {
+    Err(StyleDocumentError::UnsupportedFormat(StyleDocumentFormat::Json))
+}
+
+#[cfg(feature = "style_toml")]
+fn parse_toml(input: &str) -> Result<StyleDocument, StyleDocumentError> {
+    ::toml::from_str(input).map_err(|e| StyleDocumentError::Syntax(StyleDocumentFormat::Toml, e.to_string()))
+}
+
+#[cfg(not(feature = "style_toml"))]
+fn parse_toml(_input: &str) -> Result<StyleDocument, StyleDocumentError> {
+    Err(StyleDocumentError::UnsupportedFormat(StyleDocumentFormat::Toml))
+}
+
+#[cfg(feature = "style_ron")]
+fn parse_ron(input: &str) -> Result<StyleDocument, StyleDocumentError> {
+    ::ron::de::from_str(input).map_err(|e| StyleDocumentError::Syntax(StyleDocumentFormat::Ron, e.to_string()))
+}
+
+#[cfg(not(feature = "style_ron"))]
+fn parse_ron(_input: &str) -> Result<StyleDocument, StyleDocumentError> {
+    Err(StyleDocumentError::UnsupportedFormat(StyleDocumentFormat::Ron))
+}
+
+// Hand-written instead of derived, so that the order of the rules and declarations
+// is preserved (the order of rules with the same specificity matters for the cascade)
+#[cfg(any(feature = "style_json", feature = "style_toml", feature = "style_ron"))]
+mod deserialize {
+
+    use std::fmt;
+    use serde::de::{Deserialize, Deserializer, Visitor, MapAccess, Error};
+    use super::{StyleDocument, StyleDocumentRule};
+
+    struct StyleDocumentVisitor;
+
+    impl<'de> Visitor<'de> for StyleDocumentVisitor {
+        type Value = StyleDocument;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a map of selectors to maps of CSS properties")
+        }
+
+        fn visit_map<M: MapAccess<'de>>(self, mut map: M) -> Result<StyleDocument, M::Error> {
+            let mut rules = Vec::new();
+            while let Some((selector, declarations)) = map.next_entry::<String, Declarations>()? {
+                rules.push(StyleDocumentRule { selector, declarations: declarations.0 });
+            }
+            Ok(StyleDocument { rules })
+        }
+    }
+
+    impl<'de> Deserialize<'de> for StyleDocument {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_map(StyleDocumentVisitor)
+        }
+    }
+
+    struct Declarations(Vec<(String, String)>);
+
+    struct DeclarationsVisitor;
+
+    impl<'de> Visitor<'de> for DeclarationsVisitor {
+        type Value = Declarations;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a map of CSS property keys to values")
+        }
+
+        fn visit_map<M: MapAccess<'de>>(self, mut map: M) -> Result<Declarations, M::Error> {
+            let mut declarations = Vec::new();
+            while let Some((key, value)) = map.next_entry::<String, DeclarationValue>()? {
+                declarations.push((key, value.0));
+            }
+            Ok(Declarations(declarations))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Declarations {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_map(DeclarationsVisitor)
+        }
+    }
+
+    /// Property value, strings are taken as-is, numbers are converted to unitless values
+    struct DeclarationValue(String);
+
+    struct DeclarationValueVisitor;
+
+    impl<'de> Visitor<'de> for DeclarationValueVisitor {
+        type Value = DeclarationValue;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a string or a number")
+        }
+
+        fn visit_str<E: Error>(self, value: &str) -> Result<DeclarationValue, E> {
+            Ok(DeclarationValue(value.to_string()))
+        }
+
+        fn visit_string<E: Error>(self, value: String) -> Result<DeclarationValue, E> {
+            Ok(DeclarationValue(value))
+        }
+
+        fn visit_i64<E: Error>(self, value: i64) -> Result<DeclarationValue, E> {
+            Ok(DeclarationValue(value.to_string()))
+        }
+
+        fn visit_u64<E: Error>(self, value: u64) -> Result<DeclarationValue, E> {
+            Ok(DeclarationValue(value.to_string()))
+        }
+
+        fn visit_f64<E: Error>(self, value: f64) -> Result<DeclarationValue, E> {
+            Ok(DeclarationValue(value.to_string()))
+        }
+    }
+
+    impl<'de> Deserialize<'de> for DeclarationValue {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(DeclarationValueVisitor)
+        }
+    }
+}
+
+#[test]
+fn test_style_document_to_css() {
+    use azul_css::{CssDeclaration, CssProperty, StyleTextColor, ColorU, LayoutFlexGrow, FloatValue};
+
+    let document = StyleDocument {
+        rules: vec![
+            StyleDocumentRule {
+                selector: ".a, .b".into(),
+                declarations: vec![
+                    ("color".into(), "#ff0000".into()),
+                    ("flex-grow".into(), "1".into()),
+                ],
+            },
+        ],
+    };
+
+    let css = new_from_style_document(&document).unwrap();
+    let rules = css.rules().collect::<Vec<_>>();
+    assert_eq!(rules.len(), 2);
+    assert_eq!(rules[0].path, parse_css_path(".a").unwrap());
+    assert_eq!(rules[1].path, parse_css_path(".b").unwrap());
+    assert_eq!(rules[0].declarations, vec![
+        CssDeclaration::Static(CssProperty::TextColor(StyleTextColor(ColorU { r: 255, g: 0, b: 0, a: 255 }))),
+        CssDeclaration::Static(CssProperty::FlexGrow(LayoutFlexGrow(FloatValue::new(1.0)))),
+    ]);
+}
+
+#[test]
+fn test_split_selector_list() {
+    assert_eq!(split_selector_list(".a"), vec![".a"]);
+    assert_eq!(split_selector_list(".a, div > .b"), vec![".a", "div > .b"]);
+    assert_eq!(split_selector_list(".a:not(.b, .c), .d"), vec![".a:not(.b, .c)", ".d"]);
+    assert_eq!(split_selector_list("[title=\"a, (b\"], .d"), vec!["[title=\"a, (b\"]", ".d"]);
+}
+
+#[test]
+fn test_style_document_error_paths() {
+    let rule = |selector: &str, key: &str, value: &str| StyleDocument {
+        rules: vec![StyleDocumentRule {
+            selector: selector.into(),
+            declarations: vec![(key.into(), value.into())],
+        }],
+    };
+
+    assert_eq!(
+        new_from_style_document(&rule(".a", "colr", "red")).unwrap_err().to_string(),
+        "[\".a\"][\"colr\"]: unknown CSS key",
+    );
+    match new_from_style_document(&rule(".a", "width", "5 apples")) {
+        Err(StyleDocumentError::InvalidPropertyValue { ref selector, ref key, .. }) => {
+            assert_eq!((selector.as_str(), key.as_str()), (".a", "width"));
+        },
+        other => panic!("expected an invalid property value, got {:?}", other),
+    }
+    match new_from_style_document(&rule(".a:bogus", "color", "red")) {
+        Err(StyleDocumentError::InvalidSelector { ref selector, .. }) => assert_eq!(selector, ".a:bogus"),
+        other => panic!("expected an invalid selector, got {:?}", other),
+    }
+}
+
+#[cfg(feature = "style_json")]
+#[test]
+fn test_style_document_json() {
+    let json = r#"{ ".b": { "color": "red", "flex-grow": 2 }, ".a": { "color": "blue" } }"#;
+    let document = parse_style_document(json, StyleDocumentFormat::Json).unwrap();
+    // Document order is preserved, even though ".a" < ".b"
+    assert_eq!(document.rules[0].selector, ".b");
+    assert_eq!(document.rules[0].declarations[1], ("flex-grow".to_string(), "2".to_string()));
+    assert!(new_from_style_document(&document).is_ok());
+    match parse_style_document("{ \".a\": 5 }", StyleDocumentFormat::Json) {
+        Err(StyleDocumentError::Syntax(StyleDocumentFormat::Json, _)) => { },
+        other => panic!("expected a syntax error, got {:?}", other),
+    }
+}
+
+#[cfg(feature = "style_toml")]
+#[test]
+fn test_style_document_toml() {
+    let toml = "[\".a:hover\"]\ncolor = \"red\"\nflex-grow = 1.5\n";
+    let document = parse_style_document(toml, StyleDocumentFormat::Toml).unwrap();
+    assert_eq!(document.rules[0].selector, ".a:hover");
+    assert_eq!(document.rules[0].declarations[1], ("flex-grow".to_string(), "1.5".to_string()));
+    assert!(new_from_style_document(&document).is_ok());
+}
+
+#[cfg(feature = "style_ron")]
+#[test]
+fn test_style_document_ron() {
+    let ron = r##"{ ".a": { "color": "red" }, "#b": { "width": "10px" } }"##;
+    let document = parse_style_document(ron, StyleDocumentFormat::Ron).unwrap();
+    assert_eq!(document.rules.len(), 2);
+    assert!(new_from_style_document(&document).is_ok());
+}
@@ -5,14 +5,16 @@ use std::fmt;
 /// Css stylesheet - contains a parsed CSS stylesheet in "rule blocks",
 /// i.e. blocks of key-value pairs associated with a selector path.
 #[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct Css {
-    /// One CSS stylesheet can hold more than one sub-stylesheet:
-    /// For example, when overriding native styles, the `.sort_by_specificy()` function
-    /// should not mix the two stylesheets during sorting.
+    /// One CSS stylesheet can hold more than one sub-stylesheet, for example when
+    /// overriding native styles. Rules in later stylesheets win over rules with the
+    /// same specificity in earlier stylesheets.
     pub stylesheets: Vec<Stylesheet>,
 }
 
 #[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct Stylesheet {
     /// The style rules making up the document - for example, de-duplicated CSS rules
     pub rules: Vec<CssRuleBlock>,
@@ -26,11 +28,35 @@ impl From<Vec<CssRuleBlock>> for Stylesheet {
 
 /// Contains one parsed `key: value` pair, static or dynamic
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum CssDeclaration {
     /// Static key-value pair, such as `width: 500px`
     Static(CssProperty),
     /// Dynamic key-value pair with default value, such as `width: [[ my_id | 500px ]]`
     Dynamic(DynamicCssProperty),
+    /// Declaration marked with `!important`, such as `width: 500px !important` - takes
+    /// precedence over all normal declarations in the cascade. Never contains another
+    /// `Important` declaration, use `.into_important()` to create it.
+    Important(Box<CssDeclaration>),
+    /// CSS-wide keyword, such as `color: inherit` or `width: initial`. Keywords are
+    /// resolved during the cascade, see `CssWideKeyword`.
+    Keyword(CssPropertyType, CssWideKeyword),
+    /// Declaration that uses `currentColor` as its color, such as `border: 1px solid currentColor`.
+    /// The color of the property is replaced by the text color of the node during the cascade,
+    /// see `CssProperty::with_color`.
+    CurrentColor(CssProperty),
+}
+
+/// Keywords that can be used as the value of any property
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum CssWideKeyword {
+    /// Uses the value of the property on the parent node
+    Inherit,
+    /// Resets the property to its default value
+    Initial,
+    /// `inherit` for inheritable properties, `initial` for all other properties
+    Unset,
 }
 
 impl CssDeclaration {
@@ -41,6 +67,9 @@ impl CssDeclaration {
         match self {
             Static(s) => s.get_type().is_inheritable(),
             Dynamic(d) => d.is_inheritable(),
+            Important(i) => i.is_inheritable(),
+            Keyword(t, _) => t.is_inheritable(),
+            CurrentColor(p) => p.get_type().is_inheritable(),
         }
     }
 
@@ -51,6 +80,47 @@ impl CssDeclaration {
         match self {
             Static(s) => s.get_type().can_trigger_relayout(),
             Dynamic(d) => d.can_trigger_relayout(),
+            Important(i) => i.can_trigger_relayout(),
+            Keyword(t, _) => t.can_trigger_relayout(),
+            CurrentColor(p) => p.get_type().can_trigger_relayout(),
+        }
+    }
+
+    /// Returns the key of the declaration. Note that the sides of merged properties
+    /// are returned as the merged type (i.e. `padding-top: 5px` returns `Padding`),
+    /// unless the declaration is dynamic or a keyword.
+    pub fn get_type(&self) -> CssPropertyType {
+        use self::CssDeclaration::*;
+        match self {
+            Static(s) => s.get_type(),
+            Dynamic(d) => d.property_type,
+            Important(i) => i.get_type(),
+            Keyword(t, _) => *t,
+            CurrentColor(p) => p.get_type(),
+        }
+    }
+
+    /// Returns whether the declaration was marked with `!important`
+    pub fn is_important(&self) -> bool {
+        match self {
+            CssDeclaration::Important(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Marks the declaration as `!important`
+    pub fn into_important(self) -> Self {
+        match self {
+            CssDeclaration::Important(i) => CssDeclaration::Important(i),
+            other => CssDeclaration::Important(Box::new(other)),
+        }
+    }
+
+    /// Returns the `Static` or `Dynamic` declaration, without the `!important` flag
+    pub fn without_importance(&self) -> &Self {
+        match self {
+            CssDeclaration::Important(i) => i.without_importance(),
+            other => other,
         }
     }
 }
@@ -76,6 +146,7 @@ impl CssDeclaration {
 /// (i.e. `hover`, `focus`, etc.), thereby leading to cleaner code, since all of these
 /// special cases now use one single API.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct DynamicCssProperty {
     /// Key for this property
     pub property_type: CssPropertyType,
@@ -97,6 +168,7 @@ pub struct DynamicCssProperty {
 /// different from `auto`, since `auto` has its width determined by how much space there is
 /// available in the parent.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum DynamicCssPropertyDefault  {
     Exact(CssProperty),
     Auto,
@@ -115,15 +187,66 @@ impl DynamicCssProperty {
     }
 }
 
+/// Where a rule or declaration came from in the original stylesheet, for debugging
+/// and error messages. Lines and columns start at 1.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct CssSourceLocation {
+    /// Name of the file the stylesheet was loaded from, `None` if it was parsed from a string
+    pub file_name: Option<String>,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for CssSourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.file_name {
+            Some(file_name) => write!(f, "{}:{}:{}", file_name, self.line, self.column),
+            None => write!(f, "{}:{}", self.line, self.column),
+        }
+    }
+}
+
 /// One block of rules that applies a bunch of rules to a "path" in the style, i.e.
 /// `div#myid.myclass -> { ("justify-content", "center") }`
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct CssRuleBlock {
     /// The css path (full selector) of the style ruleset
     pub path: CssPath,
     /// `"justify-content: center"` =>
     /// `CssDeclaration::Static(CssProperty::JustifyContent(LayoutJustifyContent::Center))`
     pub declarations: Vec<CssDeclaration>,
+    /// Location of the selector in the source stylesheet, `None` if the rule wasn't parsed
+    pub location: Option<CssSourceLocation>,
+    /// Location of each declaration, indexed like `self.declarations`.
+    /// Empty if the rule wasn't parsed from a stylesheet.
+    pub declaration_locations: Vec<CssSourceLocation>,
+}
+
+// Source locations are only debug information - two rules that set the
+// same properties on the same path are equal, no matter where they came from
+impl PartialEq for CssRuleBlock {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.declarations == other.declarations
+    }
+}
+
+impl CssRuleBlock {
+    /// Creates a new rule block without any source location information
+    pub fn new(path: CssPath, declarations: Vec<CssDeclaration>) -> Self {
+        Self {
+            path,
+            declarations,
+            location: None,
+            declaration_locations: Vec::new(),
+        }
+    }
+
+    /// Returns where the declaration at `index` was defined in the source stylesheet
+    pub fn get_declaration_location(&self, index: usize) -> Option<&CssSourceLocation> {
+        self.declaration_locations.get(index)
+    }
 }
 
 pub type CssContentGroup<'a> = Vec<&'a CssPathSelector>;
@@ -131,6 +254,7 @@ pub type CssContentGroup<'a> = Vec<&'a CssPathSelector>;
 /// Signifies the type (i.e. the discriminant value) of a DOM node
 /// without carrying any of its associated data
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum NodeTypePath {
     Div,
     P,
@@ -194,6 +318,7 @@ impl fmt::Display for NodeTypePath {
 ///   CssPathSelector::PseudoSelector(CssPathPseudoSelector::Focus),
 /// ]
 #[derive(Clone, Hash, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct CssPath {
     pub selectors: Vec<CssPathSelector>,
 }
@@ -207,6 +332,23 @@ impl fmt::Display for CssPath {
     }
 }
 
+impl CssPath {
+    /// If the path ends with a `::-webkit-scrollbar*` pseudo-element, returns the
+    /// scrollbar part and the path of the element that the scrollbar belongs to.
+    pub fn split_scrollbar_pseudo_element(&self) -> Option<(CssScrollbarPseudoElement, CssPath)> {
+        match self.selectors.last() {
+            Some(CssPathSelector::PseudoSelector(CssPathPseudoSelector::Scrollbar(s))) => {
+                let mut selectors = self.selectors[..self.selectors.len() - 1].to_vec();
+                if selectors.is_empty() {
+                    selectors.push(CssPathSelector::Global);
+                }
+                Some((*s, CssPath { selectors }))
+            },
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Debug for CssPath {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
         write!(f, "{}", self)
@@ -214,6 +356,7 @@ impl fmt::Debug for CssPath {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum CssPathSelector {
     /// Represents the `*` selector
     Global,
@@ -253,6 +396,7 @@ impl fmt::Display for CssPathSelector {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum CssPathPseudoSelector {
     /// `:first`
     First,
@@ -266,9 +410,48 @@ pub enum CssPathPseudoSelector {
     Active,
     /// `:focus` - element has received focus
     Focus,
+    /// `::-webkit-scrollbar`, `::-webkit-scrollbar-thumb`, etc. - styles a part of the
+    /// scrollbar of the element instead of the element itself
+    Scrollbar(CssScrollbarPseudoElement),
+}
+
+/// Part of a scrollbar that can be styled via a `::-webkit-scrollbar*` pseudo-element
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum CssScrollbarPseudoElement {
+    /// `::-webkit-scrollbar` - the whole scrollbar (width, padding, track background)
+    Scrollbar,
+    /// `::-webkit-scrollbar-track`
+    Track,
+    /// `::-webkit-scrollbar-track-piece` - treated the same as the track
+    TrackPiece,
+    /// `::-webkit-scrollbar-thumb`
+    Thumb,
+    /// `::-webkit-scrollbar-button`
+    Button,
+    /// `::-webkit-scrollbar-corner`
+    Corner,
+    /// `::-webkit-resizer`
+    Resizer,
+}
+
+impl fmt::Display for CssScrollbarPseudoElement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::CssScrollbarPseudoElement::*;
+        match &self {
+            Scrollbar => write!(f, "-webkit-scrollbar"),
+            Track => write!(f, "-webkit-scrollbar-track"),
+            TrackPiece => write!(f, "-webkit-scrollbar-track-piece"),
+            Thumb => write!(f, "-webkit-scrollbar-thumb"),
+            Button => write!(f, "-webkit-scrollbar-button"),
+            Corner => write!(f, "-webkit-scrollbar-corner"),
+            Resizer => write!(f, "-webkit-resizer"),
+        }
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum CssNthChildSelector {
     Number(usize),
     Even,
@@ -283,7 +466,7 @@ impl fmt::Display for CssNthChildSelector {
             Number(u) => write!(f, "{}", u),
             Even => write!(f, "even"),
             Odd => write!(f, "odd"),
-            Pattern { repeat, offset } => write!(f, "{}n + {}", repeat, offset),
+            Pattern { repeat, offset } => write!(f, "{}n+{}", repeat, offset),
         }
     }
 }
@@ -298,6 +481,8 @@ impl fmt::Display for CssPathPseudoSelector {
             Hover => write!(f, "hover"),
             Active => write!(f, "active"),
             Focus => write!(f, "focus"),
+            // Pseudo-elements are prefixed with `::`, the first `:` comes from the CssPathSelector
+            Scrollbar(s) => write!(f, ":{}", s),
         }
     }
 }
@@ -319,10 +504,16 @@ impl Css {
         self.stylesheets.push(styles);
     }
 
+    /// Sorts the rules of all stylesheets by their specificity, so that the rules are applied
+    /// in the correct order. Rules with the same specificity keep their source order, rules of
+    /// later stylesheets come after rules of earlier stylesheets.
+    ///
+    /// Since the order has to hold across stylesheets, this merges all stylesheets into one.
     pub fn sort_by_specificity(&mut self) {
-        for stylesheet in &mut self.stylesheets {
-            stylesheet.sort_by_specificity()
-        }
+        let rules = self.stylesheets.drain(..).flat_map(|s| s.rules.into_iter()).collect::<Vec<_>>();
+        let mut stylesheet = Stylesheet::from(rules);
+        stylesheet.sort_by_specificity();
+        self.stylesheets = vec![stylesheet];
     }
 
     pub fn rules<'a>(&'a self) -> RuleIterator<'a> {
@@ -368,27 +559,40 @@ impl Stylesheet {
 
     /// Sort the style rules by their weight, so that the rules are applied in the correct order.
     /// Should always be called when a new style is loaded from an external source.
+    ///
+    /// The sort is stable, rules with the same specificity stay in source order.
     pub fn sort_by_specificity(&mut self) {
-        self.rules.sort_by(|a, b| get_specificity(&a.path).cmp(&get_specificity(&b.path)));
+        self.rules.sort_by_key(|rule| get_specificity(&rule.path));
     }
 }
 
-/// Returns specificity of the given css path. Further information can be found on
-/// [the w3 website](http://www.w3.org/TR/selectors/#specificity).
-fn get_specificity(path: &CssPath) -> (usize, usize, usize, usize) {
+/// Returns the (a, b, c) specificity of the given css path: the number of IDs, the number of
+/// classes and pseudo-classes and the number of node types and pseudo-elements.
+/// `*` and combinators don't count.
+/// Further information can be found on [the w3 website](http://www.w3.org/TR/selectors/#specificity).
+fn get_specificity(path: &CssPath) -> (usize, usize, usize) {
     let id_count = path.selectors.iter().filter(|x|     if let CssPathSelector::Id(_) = x {     true } else { false }).count();
-    let class_count = path.selectors.iter().filter(|x|  if let CssPathSelector::Class(_) = x {  true } else { false }).count();
-    let div_count = path.selectors.iter().filter(|x|    if let CssPathSelector::Type(_) = x {   true } else { false }).count();
-    (id_count, class_count, div_count, path.selectors.len())
+    let class_count = path.selectors.iter().filter(|x|  match x {
+        CssPathSelector::PseudoSelector(CssPathPseudoSelector::Scrollbar(_)) => false,
+        CssPathSelector::Class(_) | CssPathSelector::PseudoSelector(_) => true,
+        _ => false,
+    }).count();
+    let div_count = path.selectors.iter().filter(|x|    match x {
+        CssPathSelector::Type(_) | CssPathSelector::PseudoSelector(CssPathPseudoSelector::Scrollbar(_)) => true,
+        _ => false,
+    }).count();
+    (id_count, class_count, div_count)
 }
 
 #[test]
 fn test_specificity() {
     use self::CssPathSelector::*;
-    assert_eq!(get_specificity(&CssPath { selectors: vec![Id("hello".into())] }), (1, 0, 0, 1));
-    assert_eq!(get_specificity(&CssPath { selectors: vec![Class("hello".into())] }), (0, 1, 0, 1));
-    assert_eq!(get_specificity(&CssPath { selectors: vec![Type(NodeTypePath::Div)] }), (0, 0, 1, 1));
-    assert_eq!(get_specificity(&CssPath { selectors: vec![Id("hello".into()), Type(NodeTypePath::Div)] }), (1, 0, 1, 2));
+    assert_eq!(get_specificity(&CssPath { selectors: vec![Id("hello".into())] }), (1, 0, 0));
+    assert_eq!(get_specificity(&CssPath { selectors: vec![Class("hello".into())] }), (0, 1, 0));
+    assert_eq!(get_specificity(&CssPath { selectors: vec![Type(NodeTypePath::Div)] }), (0, 0, 1));
+    assert_eq!(get_specificity(&CssPath { selectors: vec![Id("hello".into()), Type(NodeTypePath::Div)] }), (1, 0, 1));
+    assert_eq!(get_specificity(&CssPath { selectors: vec![Global, Type(NodeTypePath::Div), DirectChildren, Class("a".into()), PseudoSelector(CssPathPseudoSelector::Hover)] }), (0, 2, 1));
+    assert_eq!(get_specificity(&CssPath { selectors: vec![Class("a".into()), PseudoSelector(CssPathPseudoSelector::Scrollbar(CssScrollbarPseudoElement::Thumb))] }), (0, 1, 1));
 }
 
 // Assert that order of the style items is correct (in order of CSS path specificity, lowest-to-highest)
@@ -400,11 +604,11 @@ fn test_specificity_sort() {
     let mut input_style = Stylesheet {
         rules: vec![
             // Rules are sorted from lowest-specificity to highest specificity
-            CssRuleBlock { path: CssPath { selectors: vec![Global] }, declarations: Vec::new() },
-            CssRuleBlock { path: CssPath { selectors: vec![Global, Type(Div), Class("my_class".into()), Id("my_id".into())] }, declarations: Vec::new() },
-            CssRuleBlock { path: CssPath { selectors: vec![Global, Type(Div), Id("my_id".into())] }, declarations: Vec::new() },
-            CssRuleBlock { path: CssPath { selectors: vec![Global, Id("my_id".into())] }, declarations: Vec::new() },
-            CssRuleBlock { path: CssPath { selectors: vec![Type(Div), Class("my_class".into()), Class("specific".into()), Id("my_id".into())] }, declarations: Vec::new() },
+            CssRuleBlock::new(CssPath { selectors: vec![Global] }, Vec::new()),
+            CssRuleBlock::new(CssPath { selectors: vec![Global, Type(Div), Class("my_class".into()), Id("my_id".into())] }, Vec::new()),
+            CssRuleBlock::new(CssPath { selectors: vec![Global, Type(Div), Id("my_id".into())] }, Vec::new()),
+            CssRuleBlock::new(CssPath { selectors: vec![Global, Id("my_id".into())] }, Vec::new()),
+            CssRuleBlock::new(CssPath { selectors: vec![Type(Div), Class("my_class".into()), Class("specific".into()), Id("my_id".into())] }, Vec::new()),
         ],
     };
 
@@ -413,13 +617,49 @@ fn test_specificity_sort() {
     let expected_style = Stylesheet {
         rules: vec![
             // Rules are sorted from lowest-specificity to highest specificity
-            CssRuleBlock { path: CssPath { selectors: vec![Global] }, declarations: Vec::new() },
-            CssRuleBlock { path: CssPath { selectors: vec![Global, Id("my_id".into())] }, declarations: Vec::new() },
-            CssRuleBlock { path: CssPath { selectors: vec![Global, Type(Div), Id("my_id".into())] }, declarations: Vec::new() },
-            CssRuleBlock { path: CssPath { selectors: vec![Global, Type(Div), Class("my_class".into()), Id("my_id".into())] }, declarations: Vec::new() },
-            CssRuleBlock { path: CssPath { selectors: vec![Type(Div), Class("my_class".into()), Class("specific".into()), Id("my_id".into())] }, declarations: Vec::new() },
+            CssRuleBlock::new(CssPath { selectors: vec![Global] }, Vec::new()),
+            CssRuleBlock::new(CssPath { selectors: vec![Global, Id("my_id".into())] }, Vec::new()),
+            CssRuleBlock::new(CssPath { selectors: vec![Global, Type(Div), Id("my_id".into())] }, Vec::new()),
+            CssRuleBlock::new(CssPath { selectors: vec![Global, Type(Div), Class("my_class".into()), Id("my_id".into())] }, Vec::new()),
+            CssRuleBlock::new(CssPath { selectors: vec![Type(Div), Class("my_class".into()), Class("specific".into()), Id("my_id".into())] }, Vec::new()),
         ],
     };
 
     assert_eq!(input_style, expected_style);
-}
\ No newline at end of file
+}
+
+// Rules of later stylesheets only win if their specificity is at least as high
+#[test]
+fn test_specificity_sort_across_stylesheets() {
+    use self::CssPathSelector::*;
+    use css_properties::{LayoutWidth, PixelValue};
+
+    // the width is only used to tell the rules apart
+    let rule = |selectors, width| CssRuleBlock::new(
+        CssPath { selectors },
+        vec![CssDeclaration::Static(CssProperty::Width(LayoutWidth(PixelValue::px(width))))],
+    );
+
+    let native = Stylesheet::from(vec![
+        rule(vec![Class("button".into()), PseudoSelector(CssPathPseudoSelector::Hover)], 1.0),
+        rule(vec![Class("button".into())], 2.0),
+    ]);
+    let user = Stylesheet::from(vec![
+        rule(vec![Id("my_button".into())], 3.0),
+        rule(vec![Class("button".into())], 4.0),
+        rule(vec![Type(NodeTypePath::Div)], 5.0),
+    ]);
+
+    let mut css = Css { stylesheets: vec![native.clone(), user.clone()] };
+    css.sort_by_specificity();
+
+    let expected = Stylesheet::from(vec![
+        user.rules[2].clone(),
+        native.rules[1].clone(),
+        user.rules[1].clone(),
+        native.rules[0].clone(),
+        user.rules[0].clone(),
+    ]);
+
+    assert_eq!(css, Css { stylesheets: vec![expected] });
+}
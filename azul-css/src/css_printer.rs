@@ -0,0 +1,666 @@
+//! Serializes a `Css` back into a CSS string (the inverse of `azul_css_parser::new_from_str`)
+
+use std::fmt::Write;
+use css::{
+    Css, Stylesheet, CssRuleBlock, CssDeclaration, CssPath, CssPathSelector,
+    DynamicCssProperty, DynamicCssPropertyDefault, CssWideKeyword,
+};
+use css_properties::*;
+
+impl Css {
+
+    /// Formats all stylesheets back into a string that can be parsed again
+    /// by `azul_css_parser::new_from_str`.
+    ///
+    /// If `pretty` is set, every declaration is printed on its own line
+    /// (indented by 4 spaces), otherwise all optional whitespace is stripped.
+    ///
+    /// Note: Stylesheets are concatenated in order, so parsing the output will
+    /// yield a `Css` with only one stylesheet containing all rules.
+    pub fn to_css_string(&self, pretty: bool) -> String {
+        let mut output = String::new();
+        for stylesheet in &self.stylesheets {
+            stylesheet.write_css_string(&mut output, pretty);
+        }
+        output
+    }
+}
+
+impl Stylesheet {
+
+    /// Same as `Css::to_css_string`, but for a single stylesheet
+    pub fn to_css_string(&self, pretty: bool) -> String {
+        let mut output = String::new();
+        self.write_css_string(&mut output, pretty);
+        output
+    }
+
+    fn write_css_string(&self, output: &mut String, pretty: bool) {
+        for rule in &self.rules {
+            rule.write_css_string(output, pretty);
+        }
+    }
+}
+
+impl CssRuleBlock {
+
+    /// Formats the rule block as `path { key: value; }`
+    pub fn to_css_string(&self, pretty: bool) -> String {
+        let mut output = String::new();
+        self.write_css_string(&mut output, pretty);
+        output
+    }
+
+    fn write_css_string(&self, output: &mut String, pretty: bool) {
+
+        output.push_str(&format_css_path(&self.path, pretty));
+
+        if pretty {
+            output.push_str(" {\n");
+        } else {
+            output.push('{');
+        }
+
+        for declaration in &self.declarations {
+            for (key, value) in declaration.to_css_key_value_pairs(pretty) {
+                if pretty {
+                    let _ = writeln!(output, "    {}: {};", key, value);
+                } else {
+                    let _ = write!(output, "{}:{};", key, value);
+                }
+            }
+        }
+
+        if pretty {
+            output.push_str("}\n\n");
+        } else {
+            output.push('}');
+        }
+    }
+}
+
+impl CssDeclaration {
+
+    /// Returns the `key: value` pairs necessary to represent this declaration in CSS.
+    ///
+    /// A dynamic declaration always results in exactly one pair, a static declaration
+    /// may expand to multiple pairs, see `CssProperty::to_css_key_value_pairs`.
+    pub fn to_css_key_value_pairs(&self, pretty: bool) -> Vec<(CssPropertyType, String)> {
+        match self {
+            CssDeclaration::Static(property) => property.to_css_key_value_pairs(),
+            CssDeclaration::Dynamic(dynamic) => vec![(dynamic.property_type, format_dynamic_property(dynamic, pretty))],
+            CssDeclaration::Important(declaration) => {
+                let important = if pretty { " !important" } else { "!important" };
+                declaration.to_css_key_value_pairs(pretty).into_iter()
+                    .map(|(key, value)| (key, format!("{}{}", value, important)))
+                    .collect()
+            },
+            CssDeclaration::Keyword(property_type, keyword) => vec![(*property_type, format_css_wide_keyword(keyword).into())],
+            CssDeclaration::CurrentColor(property) => format_current_color(property),
+        }
+    }
+}
+
+impl CssProperty {
+
+    /// Returns the `key: value` pairs necessary to represent this property in CSS.
+    ///
+    /// Usually this is only one pair, however, properties that combine multiple
+    /// sides (`border`, `box-shadow`, `padding`, `margin`, `overflow`) are split into
+    /// `border-top`, `border-left`, etc. if not all sides have the same value.
+    pub fn to_css_key_value_pairs(&self) -> Vec<(CssPropertyType, String)> {
+        use self::CssProperty::*;
+        match self {
+            BorderRadius(r)     => vec![(CssPropertyType::BorderRadius, format_border_radius(r))],
+            BackgroundColor(c)  => vec![(CssPropertyType::BackgroundColor, format_color(c.0))],
+            BackgroundSize(s)   => vec![(CssPropertyType::BackgroundSize, format_list(&s.0, |s| format_background_size(s).into()))],
+            BackgroundRepeat(r) => vec![(CssPropertyType::BackgroundRepeat, format_list(&r.0, |r| format_background_repeat(r).into()))],
+            BackgroundPosition(p) => vec![(CssPropertyType::BackgroundPosition, format_list(&p.0, format_background_position))],
+            BackgroundOrigin(o) => vec![(CssPropertyType::BackgroundOrigin, format_list(&o.0, |o| format_background_origin(o).into()))],
+            BackgroundClip(c)   => vec![(CssPropertyType::BackgroundClip, format_list(&c.0, |c| format_background_clip(c).into()))],
+            BackgroundAttachment(a) => vec![(CssPropertyType::BackgroundAttachment, format_list(&a.0, |a| format_background_attachment(a).into()))],
+            TextColor(c)        => vec![(CssPropertyType::TextColor, format_color(c.0))],
+            Border(b)           => format_border(b),
+            Background(b)       => vec![(CssPropertyType::Background, format_list(&b.0, format_background))],
+            FontSize(s)         => vec![(CssPropertyType::FontSize, format_pixel_value(&s.0))],
+            FontFamily(f)       => vec![(CssPropertyType::FontFamily, format_font_family(f))],
+            TextAlign(a)        => vec![(CssPropertyType::TextAlign, format_text_align(a).into())],
+            LetterSpacing(l)    => vec![(CssPropertyType::LetterSpacing, format_pixel_value(&l.0))],
+            BoxShadow(b)        => format_box_shadow(b),
+            Outline(o)          => format_outline(o),
+            Filter(f)           => vec![(CssPropertyType::Filter, format_filter_functions(&f.0))],
+            BackdropFilter(f)   => vec![(CssPropertyType::BackdropFilter, format_filter_functions(&f.0))],
+            LineHeight(l)       => vec![(CssPropertyType::LineHeight, format_float_value(l.0.get()))],
+            WordSpacing(w)      => vec![(CssPropertyType::WordSpacing, format_pixel_value(&w.0))],
+            TabWidth(t)         => vec![(CssPropertyType::TabWidth, format_float_value(t.0.get()))],
+            Cursor(c)           => vec![(CssPropertyType::Cursor, format_cursor(c).into())],
+            Width(w)            => vec![(CssPropertyType::Width, format_pixel_value(&w.0))],
+            Height(h)           => vec![(CssPropertyType::Height, format_pixel_value(&h.0))],
+            MinWidth(w)         => vec![(CssPropertyType::MinWidth, format_pixel_value(&w.0))],
+            MinHeight(h)        => vec![(CssPropertyType::MinHeight, format_pixel_value(&h.0))],
+            MaxWidth(w)         => vec![(CssPropertyType::MaxWidth, format_pixel_value(&w.0))],
+            MaxHeight(h)        => vec![(CssPropertyType::MaxHeight, format_pixel_value(&h.0))],
+            AspectRatio(a)      => vec![(CssPropertyType::AspectRatio, format!("{} / {}", format_float_value(a.width.get()), format_float_value(a.height.get())))],
+            Position(p)         => vec![(CssPropertyType::Position, format_position(p).into())],
+            Top(t)              => vec![(CssPropertyType::Top, format_pixel_value(&t.0))],
+            Right(r)            => vec![(CssPropertyType::Right, format_pixel_value(&r.0))],
+            Left(l)             => vec![(CssPropertyType::Left, format_pixel_value(&l.0))],
+            Bottom(b)           => vec![(CssPropertyType::Bottom, format_pixel_value(&b.0))],
+            Padding(p)          => format_padding(p),
+            Margin(m)           => format_margin(m),
+            FlexWrap(w)         => vec![(CssPropertyType::FlexWrap, format_wrap(w).into())],
+            FlexDirection(d)    => vec![(CssPropertyType::FlexDirection, format_direction(d).into())],
+            FlexGrow(g)         => vec![(CssPropertyType::FlexGrow, format_float_value(g.0.get()))],
+            FlexShrink(s)       => vec![(CssPropertyType::FlexShrink, format_float_value(s.0.get()))],
+            JustifyContent(j)   => vec![(CssPropertyType::JustifyContent, format_justify_content(j).into())],
+            AlignItems(a)       => vec![(CssPropertyType::AlignItems, format_align_items(a).into())],
+            AlignContent(a)     => vec![(CssPropertyType::AlignContent, format_align_content(a).into())],
+            Overflow(o)         => format_overflow(o),
+        }
+    }
+}
+
+fn format_css_path(path: &CssPath, pretty: bool) -> String {
+    let mut output = String::new();
+    for selector in &path.selectors {
+        match selector {
+            CssPathSelector::DirectChildren if pretty => output.push_str(" > "),
+            other => { let _ = write!(output, "{}", other); },
+        }
+    }
+    output
+}
+
+/// Formats a dynamic property as `[[ id | default ]]`
+fn format_dynamic_property(dynamic: &DynamicCssProperty, pretty: bool) -> String {
+
+    let default = match &dynamic.default {
+        DynamicCssPropertyDefault::Auto => String::from("auto"),
+        DynamicCssPropertyDefault::Exact(property) => {
+            // The default value has to be written for the key of the dynamic property,
+            // i.e. "padding-top: [[ id | 5px ]]" instead of "padding-top: [[ id | 5px 5px 5px 5px ]]"
+            let mut pairs = property.to_css_key_value_pairs();
+            let position = pairs.iter().position(|(key, _)| *key == dynamic.property_type).unwrap_or(0);
+            if pairs.is_empty() { String::new() } else { pairs.swap_remove(position).1 }
+        },
+    };
+
+    if pretty {
+        format!("[[ {} | {} ]]", dynamic.dynamic_id, default)
+    } else {
+        format!("[[{}|{}]]", dynamic.dynamic_id, default)
+    }
+}
+
+/// Formats a float without trailing zeros, i.e. `5.0` => `"5"`, `0.5` => `"0.5"`
+fn format_float_value(value: f32) -> String {
+    format!("{}", value)
+}
+
+fn format_pixel_value(value: &PixelValue) -> String {
+    let metric = match value.metric {
+        SizeMetric::Px => "px",
+        SizeMetric::Pt => "pt",
+        SizeMetric::Em => "em",
+    };
+    format!("{}{}", format_float_value(value.number.get()), metric)
+}
+
+/// Formats a color as `#rrggbb` or `#rrggbbaa` (if the color is not fully opaque)
+fn format_color(color: ColorU) -> String {
+    if color.a == 255 {
+        format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+    } else {
+        format!("#{:02x}{:02x}{:02x}{:02x}", color.r, color.g, color.b, color.a)
+    }
+}
+
+/// Note: CSS allows elliptical corners (`5px / 10px`), however, the parser only
+/// supports circular corners, so only the width of each corner is written.
+fn format_border_radius(radius: &StyleBorderRadius) -> String {
+    let BorderRadius { top_left, top_right, bottom_right, bottom_left } = radius.0;
+    format!("{} {} {} {}",
+        format_pixel_value(&top_left.width),
+        format_pixel_value(&top_right.width),
+        format_pixel_value(&bottom_right.width),
+        format_pixel_value(&bottom_left.width),
+    )
+}
+
+fn format_font_family(family: &StyleFontFamily) -> String {
+    family.fonts.iter()
+        .map(|font| format!("\"{}\"", font.get_str()))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Formats the `top`, `right`, `bottom`, `left` fields of a padding, margin, border or box-shadow
+/// as one shorthand property if possible, otherwise as one property per (set) side.
+///
+/// `$four_values` is set if the shorthand accepts `top right bottom left` (i.e. `padding` and `margin`)
+macro_rules! format_sides {($fn_name:ident, $struct_name:ident, $four_values:expr, $all:ident, $top:ident, $right:ident, $bottom:ident, $left:ident, $format_fn:ident) => (
+fn $fn_name(input: &$struct_name) -> Vec<(CssPropertyType, String)> {
+    if let (Some(top), Some(right), Some(bottom), Some(left)) = (&input.top, &input.right, &input.bottom, &input.left) {
+        if top == right && top == bottom && top == left {
+            return vec![(CssPropertyType::$all, $format_fn(top))];
+        } else if $four_values {
+            let value = format!("{} {} {} {}", $format_fn(top), $format_fn(right), $format_fn(bottom), $format_fn(left));
+            return vec![(CssPropertyType::$all, value)];
+        }
+    }
+
+    let mut pairs = Vec::new();
+    if let Some(top) = &input.top { pairs.push((CssPropertyType::$top, $format_fn(top))); }
+    if let Some(right) = &input.right { pairs.push((CssPropertyType::$right, $format_fn(right))); }
+    if let Some(bottom) = &input.bottom { pairs.push((CssPropertyType::$bottom, $format_fn(bottom))); }
+    if let Some(left) = &input.left { pairs.push((CssPropertyType::$left, $format_fn(left))); }
+    pairs
+}
+)}
+
+format_sides!(format_padding, LayoutPadding, true, Padding, PaddingTop, PaddingRight, PaddingBottom, PaddingLeft, format_pixel_value);
+format_sides!(format_margin, LayoutMargin, true, Margin, MarginTop, MarginRight, MarginBottom, MarginLeft, format_pixel_value);
+format_sides!(format_border, StyleBorder, false, Border, BorderTop, BorderRight, BorderBottom, BorderLeft, format_border_side);
+format_sides!(format_box_shadow, StyleBoxShadow, false, BoxShadow, BoxShadowTop, BoxShadowRight, BoxShadowBottom, BoxShadowLeft, format_box_shadow_side);
+
+fn format_border_side(side: &StyleBorderSide) -> String {
+    format!("{} {} {}",
+        format_pixel_value(&side.border_width),
+        format_border_style(&side.border_style),
+        format_color(side.border_color),
+    )
+}
+
+/// Formats a list of box shadows as `h_offset v_offset blur spread color inset|outset, ...` (or `none`)
+fn format_box_shadow_side(shadows: &StyleBoxShadows) -> String {
+    if shadows.0.is_empty() {
+        return String::from("none");
+    }
+    format_list(&shadows.0, format_single_box_shadow)
+}
+
+fn format_single_box_shadow(shadow: &BoxShadowPreDisplayItem) -> String {
+    format!("{} {} {} {} {} {}",
+        format_pixel_value(&shadow.offset[0]),
+        format_pixel_value(&shadow.offset[1]),
+        format_pixel_value(&shadow.blur_radius),
+        format_pixel_value(&shadow.spread_radius),
+        format_color(shadow.color),
+        match shadow.clip_mode {
+            BoxShadowClipMode::Outset => "outset",
+            BoxShadowClipMode::Inset => "inset",
+        }
+    )
+}
+
+/// Formats an outline as `outline: width style [color]` if possible, otherwise
+/// as the individual `outline-*` properties
+fn format_outline(outline: &StyleOutline) -> Vec<(CssPropertyType, String)> {
+    let mut pairs = Vec::new();
+
+    match (outline.width, outline.style) {
+        (Some(width), Some(style)) => {
+            let mut value = format!("{} {}", format_pixel_value(&width), format_border_style(&style));
+            if let Some(color) = outline.color {
+                value.push(' ');
+                value.push_str(&format_color(color));
+            }
+            pairs.push((CssPropertyType::Outline, value));
+        },
+        (width, style) => {
+            if let Some(width) = width { pairs.push((CssPropertyType::OutlineWidth, format_pixel_value(&width))); }
+            if let Some(style) = style { pairs.push((CssPropertyType::OutlineStyle, format_border_style(&style).into())); }
+            if let Some(color) = outline.color { pairs.push((CssPropertyType::OutlineColor, format_color(color))); }
+        },
+    }
+
+    if let Some(offset) = outline.offset {
+        pairs.push((CssPropertyType::OutlineOffset, format_pixel_value(&offset)));
+    }
+
+    pairs
+}
+
+fn format_overflow(overflow: &LayoutOverflow) -> Vec<(CssPropertyType, String)> {
+    match (overflow.horizontal, overflow.vertical) {
+        (Some(h), Some(v)) if h == v => vec![(CssPropertyType::Overflow, format_overflow_value(&h).into())],
+        (h, v) => {
+            let mut pairs = Vec::new();
+            if let Some(h) = h { pairs.push((CssPropertyType::OverflowX, format_overflow_value(&h).into())); }
+            if let Some(v) = v { pairs.push((CssPropertyType::OverflowY, format_overflow_value(&v).into())); }
+            pairs
+        }
+    }
+}
+
+fn format_background(background: &StyleBackground) -> String {
+    use self::StyleBackground::*;
+    match background {
+        LinearGradient(gradient) => {
+            let function = match gradient.extend_mode {
+                ExtendMode::Clamp => "linear-gradient",
+                ExtendMode::Repeat => "repeating-linear-gradient",
+            };
+            format!("{}({}, {})", function, format_gradient_direction(&gradient.direction), format_gradient_stops(&gradient.stops))
+        },
+        RadialGradient(gradient) => {
+            let function = match gradient.extend_mode {
+                ExtendMode::Clamp => "radial-gradient",
+                ExtendMode::Repeat => "repeating-radial-gradient",
+            };
+            let shape = match gradient.shape {
+                Shape::Ellipse => "ellipse",
+                Shape::Circle => "circle",
+            };
+            format!("{}({}, {})", function, shape, format_gradient_stops(&gradient.stops))
+        },
+        ConicGradient(gradient) => {
+            let function = match gradient.extend_mode {
+                ExtendMode::Clamp => "conic-gradient",
+                ExtendMode::Repeat => "repeating-conic-gradient",
+            };
+            format!("{}(from {}deg at {}, {})",
+                function,
+                format_float_value(gradient.angle.get()),
+                format_background_position(&gradient.center),
+                format_gradient_stops(&gradient.stops),
+            )
+        },
+        Image(id) => format!("image(\"{}\")", id.0),
+        Color(c) => format_color(c.0),
+        NoBackground => String::from("none"),
+    }
+}
+
+/// Formats a gradient direction as `"90deg"` or `"to bottom right"`.
+///
+/// Note: The parser always starts the gradient at the opposite corner
+/// of the end corner, so only the end corner is written.
+fn format_gradient_direction(direction: &Direction) -> String {
+    match direction {
+        Direction::Angle(deg) => format!("{}deg", format_float_value(deg.get())),
+        Direction::FromTo(_, to) => format!("to {}", format_direction_corner(to)),
+    }
+}
+
+fn format_direction_corner(corner: &DirectionCorner) -> &'static str {
+    use self::DirectionCorner::*;
+    match corner {
+        Right => "right",
+        Left => "left",
+        Top => "top",
+        Bottom => "bottom",
+        TopRight => "top right",
+        TopLeft => "top left",
+        BottomRight => "bottom right",
+        BottomLeft => "bottom left",
+    }
+}
+
+fn format_gradient_stops(stops: &[GradientStopPre]) -> String {
+    stops.iter().map(|stop| match stop.offset {
+        Some(offset) => format!("{} {}%", format_color(stop.color), format_float_value(offset.get())),
+        None => format_color(stop.color),
+    }).collect::<Vec<String>>().join(", ")
+}
+
+fn format_border_style(style: &BorderStyle) -> &'static str {
+    use self::BorderStyle::*;
+    match style {
+        None => "none",
+        Solid => "solid",
+        Double => "double",
+        Dotted => "dotted",
+        Dashed => "dashed",
+        Hidden => "hidden",
+        Groove => "groove",
+        Ridge => "ridge",
+        Inset => "inset",
+        Outset => "outset",
+    }
+}
+
+fn format_background_size(size: &StyleBackgroundSize) -> &'static str {
+    match size {
+        StyleBackgroundSize::Contain => "contain",
+        StyleBackgroundSize::Cover => "cover",
+    }
+}
+
+fn format_background_repeat(repeat: &StyleBackgroundRepeat) -> &'static str {
+    use self::StyleBackgroundRepeat::*;
+    match repeat {
+        NoRepeat => "no-repeat",
+        Repeat => "repeat",
+        RepeatX => "repeat-x",
+        RepeatY => "repeat-y",
+    }
+}
+
+fn format_background_position(position: &StyleBackgroundPosition) -> String {
+    let horizontal = match position.horizontal {
+        BackgroundPositionHorizontal::Left => String::from("left"),
+        BackgroundPositionHorizontal::Center => String::from("center"),
+        BackgroundPositionHorizontal::Right => String::from("right"),
+        BackgroundPositionHorizontal::Exact(p) => format_pixel_value(&p),
+    };
+    let vertical = match position.vertical {
+        BackgroundPositionVertical::Top => String::from("top"),
+        BackgroundPositionVertical::Center => String::from("center"),
+        BackgroundPositionVertical::Bottom => String::from("bottom"),
+        BackgroundPositionVertical::Exact(p) => format_pixel_value(&p),
+    };
+    format!("{} {}", horizontal, vertical)
+}
+
+/// Formats a list of filter functions, separated by spaces (or `none` if the list is empty)
+fn format_filter_functions(filters: &[StyleFilterFunction]) -> String {
+    use self::StyleFilterFunction::*;
+
+    if filters.is_empty() {
+        return String::from("none");
+    }
+
+    filters.iter().map(|filter| match filter {
+        Blur(radius) => format!("blur({})", format_pixel_value(radius)),
+        Brightness(amount) => format!("brightness({})", format_float_value(amount.get())),
+        Contrast(amount) => format!("contrast({})", format_float_value(amount.get())),
+        DropShadow(shadow) => format!("drop-shadow({} {} {} {})",
+            format_pixel_value(&shadow.offset[0]),
+            format_pixel_value(&shadow.offset[1]),
+            format_pixel_value(&shadow.blur_radius),
+            format_color(shadow.color),
+        ),
+        Grayscale(amount) => format!("grayscale({})", format_float_value(amount.get())),
+        HueRotate(degrees) => format!("hue-rotate({}deg)", format_float_value(degrees.get())),
+        Invert(amount) => format!("invert({})", format_float_value(amount.get())),
+        Opacity(amount) => format!("opacity({})", format_float_value(amount.get())),
+        Saturate(amount) => format!("saturate({})", format_float_value(amount.get())),
+        Sepia(amount) => format!("sepia({})", format_float_value(amount.get())),
+    }).collect::<Vec<_>>().join(" ")
+}
+
+fn format_background_origin(origin: &StyleBackgroundOrigin) -> &'static str {
+    use self::StyleBackgroundOrigin::*;
+    match origin {
+        BorderBox => "border-box",
+        PaddingBox => "padding-box",
+        ContentBox => "content-box",
+    }
+}
+
+fn format_background_clip(clip: &StyleBackgroundClip) -> &'static str {
+    use self::StyleBackgroundClip::*;
+    match clip {
+        BorderBox => "border-box",
+        PaddingBox => "padding-box",
+        ContentBox => "content-box",
+    }
+}
+
+fn format_background_attachment(attachment: &StyleBackgroundAttachment) -> &'static str {
+    use self::StyleBackgroundAttachment::*;
+    match attachment {
+        Scroll => "scroll",
+        Fixed => "fixed",
+        Local => "local",
+    }
+}
+
+/// Formats the values of a multi-layer property (such as `background`), separated by commas
+fn format_list<T, F: Fn(&T) -> String>(items: &[T], format_item: F) -> String {
+    items.iter().map(format_item).collect::<Vec<_>>().join(", ")
+}
+
+fn format_text_align(align: &StyleTextAlignmentHorz) -> &'static str {
+    use self::StyleTextAlignmentHorz::*;
+    match align {
+        Left => "left",
+        Center => "center",
+        Right => "right",
+    }
+}
+
+fn format_css_wide_keyword(keyword: &CssWideKeyword) -> &'static str {
+    use css::CssWideKeyword::*;
+    match keyword {
+        Inherit => "inherit",
+        Initial => "initial",
+        Unset => "unset",
+    }
+}
+
+/// Formats a declaration that uses `currentColor` - the color is always the last
+/// component of the formatted value, so it gets replaced by `currentColor`
+fn format_current_color(property: &CssProperty) -> Vec<(CssPropertyType, String)> {
+    property.to_css_key_value_pairs().into_iter().map(|(key, value)| match key {
+        CssPropertyType::OutlineWidth | CssPropertyType::OutlineStyle | CssPropertyType::OutlineOffset => (key, value),
+        _ => match value.rsplitn(2, ' ').nth(1) {
+            Some(rest) => (key, format!("{} currentColor", rest)),
+            None => (key, "currentColor".into()),
+        },
+    }).collect()
+}
+
+fn format_position(position: &LayoutPosition) -> &'static str {
+    use self::LayoutPosition::*;
+    match position {
+        Static => "static",
+        Relative => "relative",
+        Absolute => "absolute",
+        Fixed => "fixed",
+        Sticky => "sticky",
+    }
+}
+
+fn format_wrap(wrap: &LayoutWrap) -> &'static str {
+    match wrap {
+        LayoutWrap::Wrap => "wrap",
+        LayoutWrap::NoWrap => "nowrap",
+    }
+}
+
+fn format_direction(direction: &LayoutDirection) -> &'static str {
+    use self::LayoutDirection::*;
+    match direction {
+        Row => "row",
+        RowReverse => "row-reverse",
+        Column => "column",
+        ColumnReverse => "column-reverse",
+    }
+}
+
+fn format_justify_content(justify: &LayoutJustifyContent) -> &'static str {
+    use self::LayoutJustifyContent::*;
+    match justify {
+        Start => "flex-start",
+        End => "flex-end",
+        Center => "center",
+        SpaceBetween => "space-between",
+        SpaceAround => "space-around",
+    }
+}
+
+fn format_align_items(align: &LayoutAlignItems) -> &'static str {
+    use self::LayoutAlignItems::*;
+    match align {
+        Stretch => "stretch",
+        Center => "center",
+        Start => "flex-start",
+        End => "flex-end",
+    }
+}
+
+fn format_align_content(align: &LayoutAlignContent) -> &'static str {
+    use self::LayoutAlignContent::*;
+    match align {
+        Stretch => "stretch",
+        Center => "center",
+        Start => "flex-start",
+        End => "flex-end",
+        SpaceBetween => "space-between",
+        SpaceAround => "space-around",
+    }
+}
+
+fn format_overflow_value(overflow: &Overflow) -> &'static str {
+    use self::Overflow::*;
+    match overflow {
+        Scroll => "scroll",
+        Auto => "auto",
+        Hidden => "hidden",
+        Visible => "visible",
+    }
+}
+
+fn format_cursor(cursor: &StyleCursor) -> &'static str {
+    use self::StyleCursor::*;
+    match cursor {
+        Alias => "alias",
+        AllScroll => "all-scroll",
+        Cell => "cell",
+        ColResize => "col-resize",
+        ContextMenu => "context-menu",
+        Copy => "copy",
+        Crosshair => "crosshair",
+        Default => "default",
+        EResize => "e-resize",
+        EwResize => "ew-resize",
+        Grab => "grab",
+        Grabbing => "grabbing",
+        Help => "help",
+        Move => "move",
+        NResize => "n-resize",
+        NsResize => "ns-resize",
+        NeswResize => "nesw-resize",
+        NwseResize => "nwse-resize",
+        Pointer => "pointer",
+        Progress => "progress",
+        RowResize => "row-resize",
+        SResize => "s-resize",
+        SeResize => "se-resize",
+        Text => "text",
+        Unset => "unset",
+        VerticalText => "vertical-text",
+        WResize => "w-resize",
+        Wait => "wait",
+        ZoomIn => "zoom-in",
+        ZoomOut => "zoom-out",
+    }
+}
+
+#[test]
+fn test_format_css_values() {
+    assert_eq!(format_pixel_value(&PixelValue::px(5.0)), "5px");
+    assert_eq!(format_pixel_value(&PixelValue::em(1.5)), "1.5em");
+    assert_eq!(format_color(ColorU { r: 255, g: 0, b: 16, a: 255 }), "#ff0010");
+    assert_eq!(format_color(ColorU { r: 0, g: 0, b: 0, a: 0 }), "#00000000");
+    assert_eq!(
+        CssProperty::Padding(LayoutPadding::all(PixelValue::px(5.0))).to_css_key_value_pairs(),
+        vec![(CssPropertyType::Padding, String::from("5px"))]
+    );
+    assert_eq!(
+        CssProperty::Padding(LayoutPadding { top: Some(PixelValue::px(5.0)), .. Default::default() }).to_css_key_value_pairs(),
+        vec![(CssPropertyType::PaddingTop, String::from("5px"))]
+    );
+}
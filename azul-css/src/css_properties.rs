@@ -3,8 +3,8 @@
 use std::collections::BTreeMap;
 use std::fmt;
 
-/// Currently hard-coded: Height of one em in pixels
-const EM_HEIGHT: f32 = 16.0;
+/// Height of one em in pixels, used if neither the node nor any of its parents has a `font-size`
+pub const EM_HEIGHT: f32 = 16.0;
 /// WebRender measures in points, not in pixels!
 const PT_TO_PX: f32 = 96.0 / 72.0;
 
@@ -13,12 +13,15 @@ const PT_TO_PX: f32 = 96.0 / 72.0;
 
 /// Only used for calculations: Rectangle (x, y, width, height) in layout space.
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct LayoutRect { pub origin: LayoutPoint, pub size: LayoutSize }
 /// Only used for calculations: Size (width, height) in layout space.
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct LayoutSize { pub width: f32, pub height: f32 }
 /// Only used for calculations: Point coordinate (x, y) in layout space.
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct LayoutPoint { pub x: f32, pub y: f32 }
 
 impl LayoutSize {
@@ -35,6 +38,7 @@ impl LayoutSize {
 
 /// Represents a parsed pair of `5px, 10px` values - useful for border radius calculation
 #[derive(Debug, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct PixelSize { pub width: PixelValue, pub height: PixelValue }
 
 impl PixelSize {
@@ -53,6 +57,7 @@ impl PixelSize {
 
 /// Offsets of the border-width calculations
 #[derive(Debug, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct LayoutSideOffsets {
     pub top: FloatValue,
     pub right: FloatValue,
@@ -62,10 +67,12 @@ pub struct LayoutSideOffsets {
 
 /// u8-based color, range 0 to 255 (similar to webrenders ColorU)
 #[derive(Debug, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct ColorU { pub r: u8, pub g: u8, pub b: u8, pub a: u8 }
 
 /// f32-based color, range 0.0 to 1.0 (similar to webrenders ColorF)
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct ColorF { pub r: f32, pub g: f32, pub b: f32, pub a: f32 }
 
 impl From<ColorU> for ColorF {
@@ -91,6 +98,7 @@ impl From<ColorF> for ColorU {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct BorderRadius {
     pub top_left: PixelSize,
     pub top_right: PixelSize,
@@ -121,6 +129,7 @@ impl BorderRadius {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum BorderDetails {
     Normal(NormalBorder),
     NinePatch(NinePatchBorder),
@@ -128,6 +137,7 @@ pub enum BorderDetails {
 
 /// Represents a normal `border` property (no image border / nine-patch border)
 #[derive(Debug, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct NormalBorder {
     pub left: BorderSide,
     pub right: BorderSide,
@@ -137,6 +147,7 @@ pub struct NormalBorder {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct BorderSide {
     pub color: ColorU,
     pub style: BorderStyle,
@@ -144,6 +155,7 @@ pub struct BorderSide {
 
 /// What direction should a `box-shadow` be clipped in (inset or outset)
 #[derive(Debug, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum BoxShadowClipMode {
     Outset,
     Inset,
@@ -151,6 +163,7 @@ pub enum BoxShadowClipMode {
 
 /// Whether a `gradient` should be repeated or clamped to the edges.
 #[derive(Debug, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum ExtendMode {
     Clamp,
     Repeat,
@@ -158,6 +171,7 @@ pub enum ExtendMode {
 
 /// Style of a `border`: solid, double, dash, ridge, etc.
 #[derive(Debug, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum BorderStyle {
     None,
     Solid,
@@ -172,6 +186,7 @@ pub enum BorderStyle {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Ord, PartialOrd, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct NinePatchBorder {
     // not implemented or parse-able yet, so no fields!
 }
@@ -220,11 +235,15 @@ macro_rules! impl_float_value{($struct:ident) => (
 )}
 
 /// Map between CSS keys and a statically typed enum
-const CSS_PROPERTY_KEY_MAP: [(CssPropertyType, &'static str);55] = [
+const CSS_PROPERTY_KEY_MAP: [(CssPropertyType, &'static str);67] = [
     (CssPropertyType::BorderRadius,     "border-radius"),
     (CssPropertyType::BackgroundColor,  "background-color"),
     (CssPropertyType::BackgroundSize,   "background-size"),
     (CssPropertyType::BackgroundRepeat, "background-repeat"),
+    (CssPropertyType::BackgroundPosition, "background-position"),
+    (CssPropertyType::BackgroundOrigin, "background-origin"),
+    (CssPropertyType::BackgroundClip,   "background-clip"),
+    (CssPropertyType::BackgroundAttachment, "background-attachment"),
     (CssPropertyType::TextColor,        "color"),
     (CssPropertyType::Background,       "background"),
     (CssPropertyType::FontSize,         "font-size"),
@@ -241,6 +260,7 @@ const CSS_PROPERTY_KEY_MAP: [(CssPropertyType, &'static str);55] = [
     (CssPropertyType::MinHeight,        "min-height"),
     (CssPropertyType::MaxWidth,         "max-width"),
     (CssPropertyType::MaxHeight,        "max-height"),
+    (CssPropertyType::AspectRatio,      "aspect-ratio"),
     (CssPropertyType::Position,         "position"),
     (CssPropertyType::Top,              "top"),
     (CssPropertyType::Right,            "right"),
@@ -276,6 +296,13 @@ const CSS_PROPERTY_KEY_MAP: [(CssPropertyType, &'static str);55] = [
     (CssPropertyType::BoxShadowLeft,    "box-shadow-left"),
     (CssPropertyType::BoxShadowRight,   "box-shadow-right"),
     (CssPropertyType::BoxShadowBottom,  "box-shadow-bottom"),
+    (CssPropertyType::Outline,          "outline"),
+    (CssPropertyType::OutlineWidth,     "outline-width"),
+    (CssPropertyType::OutlineStyle,     "outline-style"),
+    (CssPropertyType::OutlineColor,     "outline-color"),
+    (CssPropertyType::OutlineOffset,    "outline-offset"),
+    (CssPropertyType::Filter,           "filter"),
+    (CssPropertyType::BackdropFilter,   "backdrop-filter"),
 ];
 
 /// Returns a map useful for parsing the keys of CSS stylesheets
@@ -286,11 +313,16 @@ pub fn get_css_key_map() -> BTreeMap<&'static str, CssPropertyType> {
 /// Represents a CSS key (for example `"border-radius"` => `BorderRadius`).
 /// You can also derive this key from a `CssProperty` by calling `CssProperty::get_type()`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum CssPropertyType {
     BorderRadius,
     BackgroundColor,
     BackgroundSize,
     BackgroundRepeat,
+    BackgroundPosition,
+    BackgroundOrigin,
+    BackgroundClip,
+    BackgroundAttachment,
     TextColor,
     Background,
     FontSize,
@@ -307,6 +339,7 @@ pub enum CssPropertyType {
     MinHeight,
     MaxWidth,
     MaxHeight,
+    AspectRatio,
     Position,
     Top,
     Right,
@@ -347,6 +380,15 @@ pub enum CssPropertyType {
     BoxShadowLeft,
     BoxShadowRight,
     BoxShadowBottom,
+
+    Outline,
+    OutlineWidth,
+    OutlineStyle,
+    OutlineColor,
+    OutlineOffset,
+
+    Filter,
+    BackdropFilter,
 }
 
 impl CssPropertyType {
@@ -380,11 +422,31 @@ impl CssPropertyType {
             | FontFamily
             | FontSize
             | LineHeight
-            | TextAlign => true,
+            | LetterSpacing
+            | WordSpacing
+            | TabWidth
+            | TextAlign
+            | Cursor => true,
             _ => false,
         }
     }
 
+    /// Returns the type of the `CssProperty` that a value for this key is parsed into:
+    /// The sides of `padding`, `margin`, `border`, `box-shadow` and `overflow` as well as
+    /// the parts of `outline` are merged into one property (i.e. `padding-top` => `Padding`)
+    pub fn get_merged_type(&self) -> CssPropertyType {
+        use self::CssPropertyType::*;
+        match self {
+            PaddingTop | PaddingLeft | PaddingRight | PaddingBottom => Padding,
+            MarginTop | MarginLeft | MarginRight | MarginBottom => Margin,
+            BorderTop | BorderLeft | BorderRight | BorderBottom => Border,
+            BoxShadowTop | BoxShadowLeft | BoxShadowRight | BoxShadowBottom => BoxShadow,
+            OverflowX | OverflowY => Overflow,
+            OutlineWidth | OutlineStyle | OutlineColor | OutlineOffset => Outline,
+            other => *other,
+        }
+    }
+
     /// Returns whether this property can trigger a re-layout (important for incremental layout and caching layouted DOMs).
     pub fn can_trigger_relayout(&self) -> bool {
 
@@ -401,6 +463,10 @@ impl CssPropertyType {
             | BackgroundColor
             | BackgroundSize
             | BackgroundRepeat
+            | BackgroundPosition
+            | BackgroundOrigin
+            | BackgroundClip
+            | BackgroundAttachment
             | TextColor
             | Background
             | TextAlign
@@ -409,6 +475,13 @@ impl CssPropertyType {
             | BoxShadowLeft
             | BoxShadowBottom
             | BoxShadowRight
+            | Outline
+            | OutlineWidth
+            | OutlineStyle
+            | OutlineColor
+            | OutlineOffset
+            | Filter
+            | BackdropFilter
             | Cursor => false,
             _ => true,
         }
@@ -417,26 +490,34 @@ impl CssPropertyType {
 
 impl fmt::Display for CssPropertyType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let key = CSS_PROPERTY_KEY_MAP.iter().find(|(v, _)| *v == *self).and_then(|(k, _)| Some(k)).unwrap();
+        let key = CSS_PROPERTY_KEY_MAP.iter().find(|(v, _)| *v == *self).and_then(|(_, k)| Some(k)).unwrap();
         write!(f, "{}", key)
     }
 }
 
 /// Represents one parsed CSS key-value pair, such as `"width: 20px"` => `CssProperty::Width(LayoutWidth::px(20.0))`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum CssProperty {
     BorderRadius(StyleBorderRadius),
     BackgroundColor(StyleBackgroundColor),
-    BackgroundSize(StyleBackgroundSize),
-    BackgroundRepeat(StyleBackgroundRepeat),
+    BackgroundSize(StyleBackgroundSizes),
+    BackgroundRepeat(StyleBackgroundRepeats),
+    BackgroundPosition(StyleBackgroundPositions),
+    BackgroundOrigin(StyleBackgroundOrigins),
+    BackgroundClip(StyleBackgroundClips),
+    BackgroundAttachment(StyleBackgroundAttachments),
     TextColor(StyleTextColor),
     Border(StyleBorder),
-    Background(StyleBackground),
+    Background(StyleBackgrounds),
     FontSize(StyleFontSize),
     FontFamily(StyleFontFamily),
     TextAlign(StyleTextAlignmentHorz),
     LetterSpacing(StyleLetterSpacing),
     BoxShadow(StyleBoxShadow),
+    Outline(StyleOutline),
+    Filter(StyleFilter),
+    BackdropFilter(StyleBackdropFilter),
     LineHeight(StyleLineHeight),
     WordSpacing(StyleWordSpacing),
     TabWidth(StyleTabWidth),
@@ -447,6 +528,7 @@ pub enum CssProperty {
     MinHeight(LayoutMinHeight),
     MaxWidth(LayoutMaxWidth),
     MaxHeight(LayoutMaxHeight),
+    AspectRatio(LayoutAspectRatio),
     Position(LayoutPosition),
     Top(LayoutTop),
     Right(LayoutRight),
@@ -473,6 +555,10 @@ impl CssProperty {
             CssProperty::BackgroundColor(_) => CssPropertyType::BackgroundColor,
             CssProperty::BackgroundSize(_) => CssPropertyType::BackgroundSize,
             CssProperty::BackgroundRepeat(_) => CssPropertyType::BackgroundRepeat,
+            CssProperty::BackgroundPosition(_) => CssPropertyType::BackgroundPosition,
+            CssProperty::BackgroundOrigin(_) => CssPropertyType::BackgroundOrigin,
+            CssProperty::BackgroundClip(_) => CssPropertyType::BackgroundClip,
+            CssProperty::BackgroundAttachment(_) => CssPropertyType::BackgroundAttachment,
             CssProperty::TextColor(_) => CssPropertyType::TextColor,
             CssProperty::Border(_) => CssPropertyType::Border,
             CssProperty::Background(_) => CssPropertyType::Background,
@@ -483,6 +569,9 @@ impl CssProperty {
             CssProperty::WordSpacing(_) => CssPropertyType::WordSpacing,
             CssProperty::TabWidth(_) => CssPropertyType::TabWidth,
             CssProperty::BoxShadow(_) => CssPropertyType::BoxShadow,
+            CssProperty::Outline(_) => CssPropertyType::Outline,
+            CssProperty::Filter(_) => CssPropertyType::Filter,
+            CssProperty::BackdropFilter(_) => CssPropertyType::BackdropFilter,
             CssProperty::LineHeight(_) => CssPropertyType::LineHeight,
             CssProperty::Cursor(_) => CssPropertyType::Cursor,
             CssProperty::Width(_) => CssPropertyType::Width,
@@ -491,6 +580,7 @@ impl CssProperty {
             CssProperty::MinHeight(_) => CssPropertyType::MinHeight,
             CssProperty::MaxWidth(_) => CssPropertyType::MaxWidth,
             CssProperty::MaxHeight(_) => CssPropertyType::MaxHeight,
+            CssProperty::AspectRatio(_) => CssPropertyType::AspectRatio,
             CssProperty::Position(_) => CssPropertyType::Position,
             CssProperty::Top(_) => CssPropertyType::Top,
             CssProperty::Right(_) => CssPropertyType::Right,
@@ -508,11 +598,56 @@ impl CssProperty {
             CssProperty::Overflow(_) => CssPropertyType::Overflow,
         }
     }
+
+    /// Returns the property with its color replaced by `color`, used to resolve `currentColor`.
+    /// Returns `None` for properties that don't have a single color.
+    pub fn with_color(&self, color: ColorU) -> Option<CssProperty> {
+        match self {
+            CssProperty::TextColor(_) => Some(CssProperty::TextColor(StyleTextColor(color))),
+            CssProperty::BackgroundColor(_) => Some(CssProperty::BackgroundColor(StyleBackgroundColor(color))),
+            CssProperty::Outline(outline) => Some(CssProperty::Outline(StyleOutline { color: Some(color), .. *outline })),
+            CssProperty::Border(border) => {
+                let mut border = *border;
+                for side in &mut [&mut border.top, &mut border.left, &mut border.bottom, &mut border.right] {
+                    if let Some(ref mut side) = **side {
+                        side.border_color = color;
+                    }
+                }
+                Some(CssProperty::Border(border))
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the property with only the parts for which `keep` returns `true`, or `None` if
+    /// no part is left. The parts of merged properties are their sides (i.e. `PaddingTop` or
+    /// `OverflowX`), all other properties only consist of one part: their own type.
+    pub fn filter_parts<F: Fn(CssPropertyType) -> bool>(&self, keep: F) -> Option<CssProperty> {
+
+        macro_rules! filter_sides {($variant:ident, $value:expr, $($field:ident: $part:ident),*) => ({
+            let mut value = $value.clone();
+            $(if !keep(CssPropertyType::$part) { value.$field = None; })*
+            if $(value.$field.is_none() &&)* true { None } else { Some(CssProperty::$variant(value)) }
+        })}
+
+        match self {
+            CssProperty::Padding(p) => filter_sides!(Padding, p, top: PaddingTop, left: PaddingLeft, bottom: PaddingBottom, right: PaddingRight),
+            CssProperty::Margin(m) => filter_sides!(Margin, m, top: MarginTop, left: MarginLeft, bottom: MarginBottom, right: MarginRight),
+            CssProperty::Border(b) => filter_sides!(Border, b, top: BorderTop, left: BorderLeft, bottom: BorderBottom, right: BorderRight),
+            CssProperty::BoxShadow(b) => filter_sides!(BoxShadow, b, top: BoxShadowTop, left: BoxShadowLeft, bottom: BoxShadowBottom, right: BoxShadowRight),
+            CssProperty::Overflow(o) => filter_sides!(Overflow, o, horizontal: OverflowX, vertical: OverflowY),
+            CssProperty::Outline(o) => filter_sides!(Outline, o, width: OutlineWidth, style: OutlineStyle, color: OutlineColor, offset: OutlineOffset),
+            other => if keep(other.get_type()) { Some(other.clone()) } else { None },
+        }
+    }
 }
 
 impl_from!(StyleBorderRadius, CssProperty::BorderRadius);
-impl_from!(StyleBackground, CssProperty::Background);
+impl_from!(StyleBackgrounds, CssProperty::Background);
 impl_from!(StyleBoxShadow, CssProperty::BoxShadow);
+impl_from!(StyleOutline, CssProperty::Outline);
+impl_from!(StyleFilter, CssProperty::Filter);
+impl_from!(StyleBackdropFilter, CssProperty::BackdropFilter);
 impl_from!(StyleBorder, CssProperty::Border);
 impl_from!(StyleFontSize, CssProperty::FontSize);
 impl_from!(StyleFontFamily, CssProperty::FontFamily);
@@ -522,8 +657,12 @@ impl_from!(StyleTabWidth, CssProperty::TabWidth);
 impl_from!(StyleWordSpacing, CssProperty::WordSpacing);
 impl_from!(StyleLetterSpacing, CssProperty::LetterSpacing);
 impl_from!(StyleBackgroundColor, CssProperty::BackgroundColor);
-impl_from!(StyleBackgroundSize, CssProperty::BackgroundSize);
-impl_from!(StyleBackgroundRepeat, CssProperty::BackgroundRepeat);
+impl_from!(StyleBackgroundSizes, CssProperty::BackgroundSize);
+impl_from!(StyleBackgroundRepeats, CssProperty::BackgroundRepeat);
+impl_from!(StyleBackgroundPositions, CssProperty::BackgroundPosition);
+impl_from!(StyleBackgroundOrigins, CssProperty::BackgroundOrigin);
+impl_from!(StyleBackgroundClips, CssProperty::BackgroundClip);
+impl_from!(StyleBackgroundAttachments, CssProperty::BackgroundAttachment);
 impl_from!(StyleTextColor, CssProperty::TextColor);
 impl_from!(StyleCursor, CssProperty::Cursor);
 
@@ -547,6 +686,7 @@ impl_from!(LayoutMargin, CssProperty::Margin);
 impl_from!(LayoutWrap, CssProperty::FlexWrap);
 impl_from!(LayoutDirection, CssProperty::FlexDirection);
 impl_from!(LayoutFlexGrow, CssProperty::FlexGrow);
+impl_from!(LayoutAspectRatio, CssProperty::AspectRatio);
 impl_from!(LayoutFlexShrink, CssProperty::FlexShrink);
 impl_from!(LayoutJustifyContent, CssProperty::JustifyContent);
 impl_from!(LayoutAlignItems, CssProperty::AlignItems);
@@ -562,6 +702,7 @@ const FP_PRECISION_MULTIPLIER_CONST: isize = FP_PRECISION_MULTIPLIER as isize;
 
 /// FloatValue, but associated with a certain metric (i.e. px, em, etc.)
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct PixelValue {
     pub metric: SizeMetric,
     pub number: FloatValue,
@@ -654,11 +795,22 @@ impl PixelValue {
             SizeMetric::Em => { (self.number.get()) * EM_HEIGHT },
         }
     }
+
+    /// Converts an `em` value into pixels, relative to the given font size in pixels.
+    /// `px` and `pt` values are returned unchanged.
+    #[inline]
+    pub fn resolve_em(&self, font_size_px: f32) -> Self {
+        match self.metric {
+            SizeMetric::Em => PixelValue::px(self.number.get() * font_size_px),
+            _ => *self,
+        }
+    }
 }
 
 /// Wrapper around FloatValue, represents a percentage instead
 /// of just being a regular floating-point value, i.e `5` = `5%`
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct PercentageValue {
     number: FloatValue,
 }
@@ -689,6 +841,7 @@ impl PercentageValue {
 /// Wrapper around an f32 value that is internally casted to an isize,
 /// in order to provide hash-ability (to avoid numerical instability).
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct FloatValue {
     pub number: isize,
 }
@@ -718,6 +871,7 @@ impl From<f32> for FloatValue {
 
 /// Enum representing the metric associated with a number (px, pt, em, etc.)
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum SizeMetric {
     Px,
     Pt,
@@ -725,6 +879,7 @@ pub enum SizeMetric {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct StyleBorderRadius(pub BorderRadius);
 
 impl StyleBorderRadius {
@@ -735,6 +890,7 @@ impl StyleBorderRadius {
 
 /// Represents a `background-color` attribute
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct StyleBackgroundColor(pub ColorU);
 
 impl Default for StyleBackgroundColor {
@@ -746,6 +902,7 @@ impl Default for StyleBackgroundColor {
 
 /// Represents a `background-size` attribute
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum StyleBackgroundSize {
     Contain,
     Cover,
@@ -753,6 +910,7 @@ pub enum StyleBackgroundSize {
 
 /// Represents a `background-repeat` attribute
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum StyleBackgroundRepeat {
     NoRepeat,
     Repeat,
@@ -766,12 +924,131 @@ impl Default for StyleBackgroundRepeat {
     }
 }
 
+/// Represents one value of a `background-position` attribute, i.e. `right bottom` or `10px 20px`.
+/// Exact values are offsets from the top left corner of the background positioning area.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct StyleBackgroundPosition {
+    pub horizontal: BackgroundPositionHorizontal,
+    pub vertical: BackgroundPositionVertical,
+}
+
+impl Default for StyleBackgroundPosition {
+    fn default() -> Self {
+        StyleBackgroundPosition {
+            horizontal: BackgroundPositionHorizontal::Left,
+            vertical: BackgroundPositionVertical::Top,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum BackgroundPositionHorizontal {
+    Left,
+    Center,
+    Right,
+    Exact(PixelValue),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum BackgroundPositionVertical {
+    Top,
+    Center,
+    Bottom,
+    Exact(PixelValue),
+}
+
+/// Represents a `background-origin` attribute: which box of the
+/// node the background is positioned and sized relative to
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum StyleBackgroundOrigin {
+    BorderBox,
+    PaddingBox,
+    ContentBox,
+}
+
+impl Default for StyleBackgroundOrigin {
+    fn default() -> Self {
+        StyleBackgroundOrigin::PaddingBox
+    }
+}
+
+/// Represents a `background-clip` attribute: which box of the node the background is clipped to
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum StyleBackgroundClip {
+    BorderBox,
+    PaddingBox,
+    ContentBox,
+}
+
+impl Default for StyleBackgroundClip {
+    fn default() -> Self {
+        StyleBackgroundClip::BorderBox
+    }
+}
+
+/// Represents a `background-attachment` attribute. `fixed` backgrounds are positioned
+/// relative to the window instead of the node. Since backgrounds are drawn
+/// inside of the scroll frame of the node, `local` behaves like `scroll`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum StyleBackgroundAttachment {
+    Scroll,
+    Fixed,
+    Local,
+}
+
+impl Default for StyleBackgroundAttachment {
+    fn default() -> Self {
+        StyleBackgroundAttachment::Scroll
+    }
+}
+
+// Wrapper for the comma-separated values of a background property, one value per background layer
+macro_rules! impl_background_layers {($struct_name:ident, $item:ident) => (
+
+    #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+    pub struct $struct_name(pub Vec<$item>);
+
+    impl $struct_name {
+        /// Returns the value for the background layer at `layer_index`. Like in CSS,
+        /// the values are repeated if there are more layers than values.
+        pub fn get(&self, layer_index: usize) -> Option<&$item> {
+            if self.0.is_empty() {
+                None
+            } else {
+                self.0.get(layer_index % self.0.len())
+            }
+        }
+    }
+
+    impl From<$item> for $struct_name {
+        fn from(item: $item) -> Self {
+            $struct_name(vec![item])
+        }
+    }
+)}
+
+impl_background_layers!(StyleBackgroundSizes, StyleBackgroundSize);
+impl_background_layers!(StyleBackgroundRepeats, StyleBackgroundRepeat);
+impl_background_layers!(StyleBackgroundPositions, StyleBackgroundPosition);
+impl_background_layers!(StyleBackgroundOrigins, StyleBackgroundOrigin);
+impl_background_layers!(StyleBackgroundClips, StyleBackgroundClip);
+impl_background_layers!(StyleBackgroundAttachments, StyleBackgroundAttachment);
+
 /// Represents a `color` attribute
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct StyleTextColor(pub ColorU);
 
 /// Represents a `padding` attribute
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct LayoutPadding {
     pub top: Option<PixelValue>,
     pub bottom: Option<PixelValue>,
@@ -814,6 +1091,7 @@ struct_all!(LayoutMargin, PixelValue);
 
 /// Represents a parsed `padding` attribute
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct LayoutMargin {
     pub top: Option<PixelValue>,
     pub bottom: Option<PixelValue>,
@@ -823,6 +1101,7 @@ pub struct LayoutMargin {
 
 /// Wrapper for the `overflow-{x,y}` + `overflow` property
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct LayoutOverflow {
     pub horizontal: Option<Overflow>,
     pub vertical: Option<Overflow>,
@@ -863,9 +1142,18 @@ impl LayoutOverflow {
     pub fn is_vertical_overflow_visible(&self) -> bool {
         self.vertical.unwrap_or_default().is_overflow_visible()
     }
+
+    pub fn allows_horizontal_scrolling(&self) -> bool {
+        self.horizontal.unwrap_or_default().allows_scrolling()
+    }
+
+    pub fn allows_vertical_scrolling(&self) -> bool {
+        self.vertical.unwrap_or_default().allows_scrolling()
+    }
 }
 
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct StyleBorder {
     pub top: Option<StyleBorderSide>,
     pub left: Option<StyleBorderSide>,
@@ -928,26 +1216,155 @@ const DEFAULT_BORDER_STYLE: BorderStyle = BorderStyle::Solid;
 const DEFAULT_BORDER_COLOR: ColorU = ColorU { r: 0, g: 0, b: 0, a: 255 };
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct StyleBorderSide {
     pub border_width: PixelValue,
     pub border_style: BorderStyle,
     pub border_color: ColorU,
 }
 
-/// Represents a `box-shadow` attribute.
+/// Represents the `outline` attribute and its `outline-width`, `outline-style`,
+/// `outline-color` and `outline-offset` longhands. The outline is drawn outside
+/// of the border box and does not affect the layout.
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct StyleOutline {
+    pub width: Option<PixelValue>,
+    pub style: Option<BorderStyle>,
+    /// If not set, the outline has the same color as the text
+    pub color: Option<ColorU>,
+    pub offset: Option<PixelValue>,
+}
+
+/// Width of an `outline` without an explicit width (`medium`)
+pub const DEFAULT_OUTLINE_WIDTH: PixelValue = PixelValue::const_px(3);
+
+impl StyleOutline {
+
+    // "merges" two StyleOutline properties
+    pub fn merge(a: &mut Option<Self>, b: &Self) {
+        if let Some(ref mut existing) = a {
+            if b.width.is_some() { existing.width = b.width; }
+            if b.style.is_some() { existing.style = b.style; }
+            if b.color.is_some() { existing.color = b.color; }
+            if b.offset.is_some() { existing.offset = b.offset; }
+        } else {
+            *a = Some(*b);
+        }
+    }
+
+    /// Returns the border that is drawn around the border box (expanded by `get_outset()`),
+    /// `None` if the outline is not visible
+    pub fn get_border_side(&self, text_color: ColorU) -> Option<StyleBorderSide> {
+        let border_style = self.style.unwrap_or(BorderStyle::None);
+        let border_width = self.width.unwrap_or(DEFAULT_OUTLINE_WIDTH);
+
+        if border_style == BorderStyle::None || border_style == BorderStyle::Hidden || border_width.to_pixels() <= 0.0 {
+            return None;
+        }
+
+        Some(StyleBorderSide {
+            border_width,
+            border_style,
+            border_color: self.color.unwrap_or(text_color),
+        })
+    }
+
+    /// Returns how far the outer edge of the outline lies outside of the border box
+    pub fn get_outset(&self) -> f32 {
+        self.width.unwrap_or(DEFAULT_OUTLINE_WIDTH).to_pixels() +
+        self.offset.map(|o| o.to_pixels()).unwrap_or(0.0)
+    }
+}
+
+/// Represents a `box-shadow` attribute.
+#[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct StyleBoxShadow {
-    pub top: Option<Option<BoxShadowPreDisplayItem>>,
-    pub left: Option<Option<BoxShadowPreDisplayItem>>,
-    pub bottom: Option<Option<BoxShadowPreDisplayItem>>,
-    pub right: Option<Option<BoxShadowPreDisplayItem>>,
+    pub top: Option<StyleBoxShadows>,
+    pub left: Option<StyleBoxShadows>,
+    pub bottom: Option<StyleBoxShadows>,
+    pub right: Option<StyleBoxShadows>,
+}
+
+impl StyleBoxShadow {
+    /// Sets all of the fields (top, left, right, bottom) to `Some(field)`
+    pub fn all(field: StyleBoxShadows) -> Self {
+        Self {
+            top: Some(field.clone()),
+            right: Some(field.clone()),
+            left: Some(field.clone()),
+            bottom: Some(field),
+        }
+    }
+
+    pub fn merge(a: &mut Option<StyleBoxShadow>, b: &StyleBoxShadow) {
+        if let Some(ref mut existing) = a {
+            if b.top.is_some() { existing.top = b.top.clone(); }
+            if b.bottom.is_some() { existing.bottom = b.bottom.clone(); }
+            if b.left.is_some() { existing.left = b.left.clone(); }
+            if b.right.is_some() { existing.right = b.right.clone(); }
+        } else {
+            *a = Some(b.clone());
+        }
+    }
 }
 
-merge_struct!(StyleBoxShadow);
-struct_all!(StyleBoxShadow, Option<BoxShadowPreDisplayItem>);
+/// Comma-separated list of shadows of one side of a `box-shadow`, i.e.
+/// `box-shadow: 0px 1px 3px black, 0px 1px 2px gray`. The first shadow is
+/// drawn on top, `box-shadow: none` is an empty list.
+#[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct StyleBoxShadows(pub Vec<BoxShadowPreDisplayItem>);
+
+impl From<BoxShadowPreDisplayItem> for StyleBoxShadows {
+    fn from(shadow: BoxShadowPreDisplayItem) -> Self {
+        StyleBoxShadows(vec![shadow])
+    }
+}
+
+/// Represents a `filter` attribute, i.e. `filter: blur(5px) grayscale(100%)`.
+/// The filters are applied in order, `filter: none` is an empty list.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct StyleFilter(pub Vec<StyleFilterFunction>);
+
+/// Represents a `backdrop-filter` attribute: the filters are applied
+/// to the content behind the node instead of the node itself.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct StyleBackdropFilter(pub Vec<StyleFilterFunction>);
+
+/// A single filter function, such as `blur(5px)`. Amounts are stored as
+/// fractions, i.e. `grayscale(50%)` is stored as `Grayscale(0.5)`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub enum StyleFilterFunction {
+    Blur(PixelValue),
+    Brightness(PercentageValue),
+    Contrast(PercentageValue),
+    DropShadow(StyleDropShadow),
+    Grayscale(PercentageValue),
+    /// Rotation in degrees
+    HueRotate(FloatValue),
+    Invert(PercentageValue),
+    Opacity(PercentageValue),
+    Saturate(PercentageValue),
+    Sepia(PercentageValue),
+}
+
+/// Arguments of a `drop-shadow()` filter, i.e. `drop-shadow(2px 2px 5px black)`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct StyleDropShadow {
+    pub offset: [PixelValue;2],
+    pub blur_radius: PixelValue,
+    pub color: ColorU,
+}
 
 // missing StyleBorderRadius & LayoutRect
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct BoxShadowPreDisplayItem {
     pub offset: [PixelValue;2],
     pub color: ColorU,
@@ -957,9 +1374,11 @@ pub struct BoxShadowPreDisplayItem {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum StyleBackground {
     LinearGradient(LinearGradient),
     RadialGradient(RadialGradient),
+    ConicGradient(ConicGradient),
     Image(CssImageId),
     Color(StyleBackgroundColor),
     NoBackground,
@@ -981,7 +1400,21 @@ impl<'a> From<CssImageId> for StyleBackground {
     }
 }
 
+/// Represents the layers of a `background` attribute, i.e.
+/// `background: image("icon"), linear-gradient(red, blue)`.
+/// Like in CSS, the first layer is drawn on top.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct StyleBackgrounds(pub Vec<StyleBackground>);
+
+impl From<StyleBackground> for StyleBackgrounds {
+    fn from(background: StyleBackground) -> Self {
+        StyleBackgrounds(vec![background])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct LinearGradient {
     pub direction: Direction,
     pub extend_mode: ExtendMode,
@@ -989,15 +1422,30 @@ pub struct LinearGradient {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct RadialGradient {
     pub shape: Shape,
     pub extend_mode: ExtendMode,
     pub stops: Vec<GradientStopPre>,
 }
 
+/// Gradient that rotates around a center point, i.e. `conic-gradient(from 90deg at center, red, blue)`.
+/// Stop offsets are percentages of a full turn, so `red 90deg` is stored as `red 25%`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct ConicGradient {
+    /// Rotation of the gradient start in degrees, clockwise from the top
+    pub angle: FloatValue,
+    /// Center of the gradient, relative to the background positioning area
+    pub center: StyleBackgroundPosition,
+    pub extend_mode: ExtendMode,
+    pub stops: Vec<GradientStopPre>,
+}
+
 /// CSS direction (necessary for gradients). Can either be a fixed angle or
 /// a direction ("to right" / "to left", etc.).
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum Direction {
     Angle(FloatValue),
     FromTo(DirectionCorner, DirectionCorner),
@@ -1072,12 +1520,14 @@ impl Direction {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum Shape {
     Ellipse,
     Circle,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum StyleCursor {
     /// `alias`
     Alias,
@@ -1148,6 +1598,7 @@ impl Default for StyleCursor {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum DirectionCorner {
     Right,
     Left,
@@ -1203,11 +1654,14 @@ impl DirectionCorner {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum BackgroundType {
     LinearGradient,
     RepeatingLinearGradient,
     RadialGradient,
     RepeatingRadialGradient,
+    ConicGradient,
+    RepeatingConicGradient,
     Image,
 }
 
@@ -1218,9 +1672,11 @@ pub enum BackgroundType {
 /// of the original source text. For example, when parsing a style
 /// from CSS, the original string can be deallocated afterwards.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct CssImageId(pub String);
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct GradientStopPre {
     // this is set to None if there was no offset that could be parsed
     pub offset: Option<PercentageValue>,
@@ -1229,41 +1685,78 @@ pub struct GradientStopPre {
 
 /// Represents a `width` attribute
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct LayoutWidth(pub PixelValue);
 /// Represents a `min-width` attribute
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct LayoutMinWidth(pub PixelValue);
 /// Represents a `max-width` attribute
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct LayoutMaxWidth(pub PixelValue);
 /// Represents a `height` attribute
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct LayoutHeight(pub PixelValue);
 /// Represents a `min-height` attribute
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct LayoutMinHeight(pub PixelValue);
 /// Represents a `max-height` attribute
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct LayoutMaxHeight(pub PixelValue);
 
+/// Represents an `aspect-ratio` attribute, i.e. `16 / 9`: The preferred ratio of the width
+/// to the height of a node, used to derive the height from the width (or vice versa)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
+pub struct LayoutAspectRatio {
+    pub width: FloatValue,
+    pub height: FloatValue,
+}
+
+impl LayoutAspectRatio {
+    pub fn new(width: f32, height: f32) -> Self {
+        Self { width: FloatValue::new(width), height: FloatValue::new(height) }
+    }
+
+    /// Returns `width / height` or `None` if either side isn't a positive number
+    pub fn get(&self) -> Option<f32> {
+        let (width, height) = (self.width.get(), self.height.get());
+        if width > 0.0 && height > 0.0 {
+            Some(width / height)
+        } else {
+            None
+        }
+    }
+}
+
 /// Represents a `top` attribute
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct LayoutTop(pub PixelValue);
 /// Represents a `left` attribute
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct LayoutLeft(pub PixelValue);
 /// Represents a `right` attribute
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct LayoutRight(pub PixelValue);
 /// Represents a `bottom` attribute
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct LayoutBottom(pub PixelValue);
 
 /// Represents a `flex-grow` attribute
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct LayoutFlexGrow(pub FloatValue);
 /// Represents a `flex-shrink` attribute
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct LayoutFlexShrink(pub FloatValue);
 
 impl_float_value!(LayoutFlexGrow);
@@ -1271,6 +1764,7 @@ impl_float_value!(LayoutFlexShrink);
 
 /// Represents a `flex-direction` attribute - default: `Column`
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum LayoutDirection {
     Row,
     RowReverse,
@@ -1301,15 +1795,19 @@ impl LayoutDirection {
 
 /// Represents a `line-height` attribute
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct StyleLineHeight(pub PercentageValue);
 /// Represents a `tab-width` attribute
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct StyleTabWidth(pub PercentageValue);
 /// Represents a `letter-spacing` attribute
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct StyleLetterSpacing(pub PixelValue);
 /// Represents a `word-spacing` attribute
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct StyleWordSpacing(pub PixelValue);
 
 impl_percentage_value!(StyleTabWidth);
@@ -1319,6 +1817,7 @@ impl_percentage_value!(StyleLineHeight);
 /// makes decisions based on horizontal / vertical direction easier to write.
 /// Use `LayoutDirection::get_axis()` to get the axis for a given `LayoutDirection`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum LayoutAxis {
     Horizontal,
     Vertical,
@@ -1328,10 +1827,15 @@ pub enum LayoutAxis {
 ///
 /// NOTE: No inline positioning is supported.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum LayoutPosition {
     Static,
     Relative,
     Absolute,
+    Fixed,
+    /// Positioned like `relative`, but sticks to the `top` / `bottom` / `left` / `right`
+    /// edge of the nearest scroll frame while it is scrolled
+    Sticky,
 }
 
 impl Default for LayoutPosition {
@@ -1340,8 +1844,21 @@ impl Default for LayoutPosition {
     }
 }
 
+impl LayoutPosition {
+    /// Returns whether the node is taken out of the regular content flow,
+    /// i.e. whether it is `position: absolute` or `position: fixed`
+    pub fn is_out_of_flow(&self) -> bool {
+        use self::LayoutPosition::*;
+        match self {
+            Absolute | Fixed => true,
+            Static | Relative | Sticky => false,
+        }
+    }
+}
+
 /// Represents a `flex-wrap` attribute - default: `Wrap`
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum LayoutWrap {
     Wrap,
     NoWrap,
@@ -1355,6 +1872,7 @@ impl Default for LayoutWrap {
 
 /// Represents a `justify-content` attribute
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum LayoutJustifyContent {
     /// Default value. Items are positioned at the beginning of the container
     Start,
@@ -1376,6 +1894,7 @@ impl Default for LayoutJustifyContent {
 
 /// Represents a `align-items` attribute
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum LayoutAlignItems {
     /// Items are stretched to fit the container
     Stretch,
@@ -1395,6 +1914,7 @@ impl Default for LayoutAlignItems {
 
 /// Represents a `align-content` attribute
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum LayoutAlignContent {
     /// Default value. Lines stretch to take up the remaining space
     Stretch,
@@ -1413,10 +1933,13 @@ pub enum LayoutAlignContent {
 /// Represents a `overflow-x` or `overflow-y` property, see
 /// [`TextOverflowBehaviour`](./struct.TextOverflowBehaviour.html) - default: `Auto`
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum Overflow {
     /// Always shows a scroll bar, overflows on scroll
     Scroll,
-    /// Does not show a scroll bar by default, only when text is overflowing
+    /// Does not show a scroll bar by default, only when text is overflowing.
+    /// The scroll bar is then drawn on top of the content (overlay scroll bar),
+    /// it doesn't take up any space in the layout
     Auto,
     /// Never shows a scroll bar, simply clips text
     Hidden,
@@ -1451,10 +1974,16 @@ impl Overflow {
     pub fn is_overflow_visible(&self) -> bool {
         *self == Overflow::Visible
     }
+
+    /// Returns whether the user can scroll the content (`overflow:scroll` or `overflow:auto`)
+    pub fn allows_scrolling(&self) -> bool {
+        *self == Overflow::Scroll || *self == Overflow::Auto
+    }
 }
 
 /// Horizontal text alignment enum (left, center, right) - default: `Center`
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum StyleTextAlignmentHorz {
     Left,
     Center,
@@ -1469,6 +1998,7 @@ impl Default for StyleTextAlignmentHorz {
 
 /// Vertical text alignment enum (top, center, bottom) - default: `Center`
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub enum StyleTextAlignmentVert {
     Top,
     Center,
@@ -1484,17 +2014,32 @@ impl Default for StyleTextAlignmentVert {
 /// Options of a cascaded (styled) DOM node that are only relevant
 /// for styling and don't affect the layout of the rectangle
 #[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct RectStyle {
     /// Background color of this rectangle
     pub background_color: Option<StyleBackgroundColor>,
     /// Background size of this rectangle
-    pub background_size: Option<StyleBackgroundSize>,
+    pub background_size: Option<StyleBackgroundSizes>,
     /// Background repetition
-    pub background_repeat: Option<StyleBackgroundRepeat>,
+    pub background_repeat: Option<StyleBackgroundRepeats>,
+    /// Position of each background layer
+    pub background_position: Option<StyleBackgroundPositions>,
+    /// Box that the background layers are positioned in
+    pub background_origin: Option<StyleBackgroundOrigins>,
+    /// Box that the background layers are clipped to
+    pub background_clip: Option<StyleBackgroundClips>,
+    /// Whether the background layers scroll with the node or are fixed to the window
+    pub background_attachment: Option<StyleBackgroundAttachments>,
     /// Shadow color
     pub box_shadow: Option<StyleBoxShadow>,
-    /// Gradient (location) + stops
-    pub background: Option<StyleBackground>,
+    /// Outline, drawn outside of the border
+    pub outline: Option<StyleOutline>,
+    /// Filters applied to the node and its children
+    pub filter: Option<StyleFilter>,
+    /// Filters applied to the content behind the node
+    pub backdrop_filter: Option<StyleBackdropFilter>,
+    /// Background layers (gradients, images or colors), first layer on top
+    pub background: Option<StyleBackgrounds>,
     /// Border
     pub border: Option<StyleBorder>,
     /// Border radius
@@ -1515,6 +2060,8 @@ pub struct RectStyle {
     pub word_spacing: Option<StyleWordSpacing>,
     /// `tab-width` property
     pub tab_width: Option<StyleTabWidth>,
+    /// Scrollbar style, set by `::-webkit-scrollbar*` rules
+    pub scrollbar: Option<Box<ScrollbarInfo>>,
 }
 
 impl_pixel_value!(StyleLetterSpacing);
@@ -1522,17 +2069,68 @@ impl_pixel_value!(StyleWordSpacing);
 
 impl RectStyle {
 
+    /// Returns the style of the horizontal scrollbar, the default scrollbar if the node has no
+    /// `::-webkit-scrollbar*` rules. Both scrollbars share the same style, like in browsers.
     pub fn get_horizontal_scrollbar_style(&self) -> ScrollbarInfo {
-        ScrollbarInfo::default()
+        self.scrollbar.as_ref().map(|s| (**s).clone()).unwrap_or_default()
     }
 
+    /// Returns the style of the vertical scrollbar, see `get_horizontal_scrollbar_style`
     pub fn get_vertical_scrollbar_style(&self) -> ScrollbarInfo {
-        ScrollbarInfo::default()
+        self.get_horizontal_scrollbar_style()
+    }
+
+    /// Converts all `em` values (except for the `font-size` itself) into pixels,
+    /// relative to the computed font size of the node
+    pub fn resolve_em_values(&mut self, font_size_px: f32) {
+
+        if let Some(ref mut letter_spacing) = self.letter_spacing {
+            letter_spacing.0 = letter_spacing.0.resolve_em(font_size_px);
+        }
+
+        if let Some(ref mut word_spacing) = self.word_spacing {
+            word_spacing.0 = word_spacing.0.resolve_em(font_size_px);
+        }
+
+        if let Some(ref mut border) = self.border {
+            for side in &mut [&mut border.top, &mut border.left, &mut border.bottom, &mut border.right] {
+                if let Some(ref mut side) = **side {
+                    side.border_width = side.border_width.resolve_em(font_size_px);
+                }
+            }
+        }
+
+        if let Some(ref mut border_radius) = self.border_radius {
+            let radius = &mut border_radius.0;
+            for corner in &mut [&mut radius.top_left, &mut radius.top_right, &mut radius.bottom_left, &mut radius.bottom_right] {
+                corner.width = corner.width.resolve_em(font_size_px);
+                corner.height = corner.height.resolve_em(font_size_px);
+            }
+        }
+
+        if let Some(ref mut outline) = self.outline {
+            resolve_em_option(&mut outline.width, font_size_px);
+            resolve_em_option(&mut outline.offset, font_size_px);
+        }
+
+        if let Some(ref mut box_shadow) = self.box_shadow {
+            for side in &mut [&mut box_shadow.top, &mut box_shadow.left, &mut box_shadow.bottom, &mut box_shadow.right] {
+                if let Some(ref mut shadows) = **side {
+                    for shadow in shadows.0.iter_mut() {
+                        shadow.offset[0] = shadow.offset[0].resolve_em(font_size_px);
+                        shadow.offset[1] = shadow.offset[1].resolve_em(font_size_px);
+                        shadow.blur_radius = shadow.blur_radius.resolve_em(font_size_px);
+                        shadow.spread_radius = shadow.spread_radius.resolve_em(font_size_px);
+                    }
+                }
+            }
+        }
     }
 }
 
 /// Holds info necessary for layouting / styling scrollbars (-webkit-scrollbar)
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct ScrollbarInfo {
     /// Total width (or height for vertical scrollbars) of the scrollbar in pixels
     pub width: LayoutWidth,
@@ -1588,6 +2186,7 @@ impl Default for ScrollbarInfo {
 
 /// Options of a cascaded (styled) DOM node that are relevant for constructing the layout of a div
 #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct RectLayout {
 
     pub width: Option<LayoutWidth>,
@@ -1596,6 +2195,7 @@ pub struct RectLayout {
     pub min_height: Option<LayoutMinHeight>,
     pub max_width: Option<LayoutMaxWidth>,
     pub max_height: Option<LayoutMaxHeight>,
+    pub aspect_ratio: Option<LayoutAspectRatio>,
 
     pub position: Option<LayoutPosition>,
     pub top: Option<LayoutTop>,
@@ -1649,6 +2249,36 @@ impl RectLayout {
     pub fn is_vertical_overflow_visible(&self) -> bool {
         self.overflow.unwrap_or_default().is_vertical_overflow_visible()
     }
+
+    /// Converts all `em` values into pixels, relative to the computed font size of the node
+    pub fn resolve_em_values(&mut self, font_size_px: f32) {
+
+        macro_rules! resolve_em {($($field:ident),*) => ($(
+            if let Some(ref mut value) = self.$field {
+                value.0 = value.0.resolve_em(font_size_px);
+            }
+        )*)}
+
+        resolve_em!(width, height, min_width, min_height, max_width, max_height, top, bottom, right, left);
+
+        if let Some(ref mut padding) = self.padding {
+            for side in &mut [&mut padding.top, &mut padding.bottom, &mut padding.left, &mut padding.right] {
+                resolve_em_option(side, font_size_px);
+            }
+        }
+
+        if let Some(ref mut margin) = self.margin {
+            for side in &mut [&mut margin.top, &mut margin.bottom, &mut margin.left, &mut margin.right] {
+                resolve_em_option(side, font_size_px);
+            }
+        }
+    }
+}
+
+fn resolve_em_option(value: &mut Option<PixelValue>, font_size_px: f32) {
+    if let Some(ref mut value) = *value {
+        *value = value.resolve_em(font_size_px);
+    }
 }
 
 impl_pixel_value!(LayoutWidth);
@@ -1664,6 +2294,7 @@ impl_pixel_value!(LayoutLeft);
 
 /// Represents a `font-size` attribute
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct StyleFontSize(pub PixelValue);
 
 impl_pixel_value!(StyleFontSize);
@@ -1676,12 +2307,14 @@ impl StyleFontSize {
 
 /// Represents a `font-family` attribute
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct StyleFontFamily {
     // fonts in order of precedence, i.e. "Webly Sleeky UI", "monospace", etc.
     pub fonts: Vec<FontId>
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde_serialization", derive(Serialize, Deserialize))]
 pub struct FontId(pub String);
 
 impl FontId {
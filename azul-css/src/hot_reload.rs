@@ -16,6 +16,12 @@ pub trait HotReloadHandler {
     fn reload_style(&mut self) -> Result<Css, String>;
     /// Returns how quickly the hot-reloader should reload the source format.
     fn get_reload_interval(&self) -> Duration;
+    /// Returns the non-fatal warnings of the last successful reload (for example properties
+    /// that were skipped because they couldn't be parsed). The hot-reload loop prints these
+    /// whenever they change. By default, a handler doesn't report any warnings.
+    fn get_warnings(&self) -> &[String] {
+        &[]
+    }
 }
 
 /// Custom hot-reloader combinator that can be used to merge hot-reloaded styles onto a base style.
@@ -53,4 +59,8 @@ impl HotReloadHandler for HotReloadOverrideHandler {
     fn get_reload_interval(&self) -> Duration {
         self.hot_reloader.get_reload_interval()
     }
+
+    fn get_warnings(&self) -> &[String] {
+        self.hot_reloader.get_warnings()
+    }
 }
@@ -1,9 +1,16 @@
 //! Provides datatypes used to describe an application's style using the Azul GUI framework.
 
+#[cfg(feature = "serde_serialization")]
+extern crate serde;
+#[cfg(feature = "serde_serialization")]
+#[macro_use]
+extern crate serde_derive;
+
 #[macro_use]
 mod macros;
 mod css;
 mod css_properties;
+mod css_printer;
 mod hot_reload;
 
 pub use css::*;
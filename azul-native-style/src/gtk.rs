@@ -0,0 +1,513 @@
+//! Derives the Linux native style from the colors of the active GTK theme.
+//!
+//! The theme is looked up via the `GTK_THEME` environment variable, `gsettings` and the
+//! GTK `settings.ini` (which KDE also writes for GTK applications). The `@define-color`
+//! values of the themes `gtk.css` are then mapped onto the built-in `native_linux.css`.
+
+use std::{
+    env,
+    fs,
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Command,
+};
+use azul_css::{Css, ColorU};
+use azul_css_parser::{self, CssColor};
+use NATIVE_CSS;
+
+/// Maximum depth of nested `@import` rules that get followed when loading a `gtk.css`
+const MAX_IMPORT_DEPTH: usize = 8;
+
+/// Theme-related settings of GTK, see `GtkSettings::from_ini`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GtkSettings {
+    /// Name of the theme, i.e. `Adwaita`
+    pub theme_name: Option<String>,
+    /// Whether the dark variant of the theme (`gtk-dark.css`) should be used
+    pub prefer_dark_theme: bool,
+}
+
+impl GtkSettings {
+
+    /// Parses the `[Settings]` section of a GTK `settings.ini`
+    pub fn from_ini(input: &str) -> Self {
+        let mut settings = GtkSettings::default();
+        let mut in_settings_section = false;
+
+        for line in input.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if line.starts_with('[') {
+                in_settings_section = line == "[Settings]";
+                continue;
+            }
+            if !in_settings_section {
+                continue;
+            }
+            let mut key_value = line.splitn(2, '=');
+            let (key, value) = match (key_value.next(), key_value.next()) {
+                (Some(k), Some(v)) => (k.trim(), v.trim().trim_matches('"')),
+                _ => continue,
+            };
+            match key {
+                "gtk-theme-name" if !value.is_empty() => settings.theme_name = Some(value.to_string()),
+                "gtk-application-prefer-dark-theme" => settings.prefer_dark_theme = value == "1" || value == "true",
+                _ => { },
+            }
+        }
+
+        settings
+    }
+
+    /// Parses the `GTK_THEME` environment variable format, i.e. `Adwaita:dark`
+    pub fn from_gtk_theme_env(input: &str) -> Option<Self> {
+        let mut parts = input.trim().splitn(2, ':');
+        let theme_name = parts.next().filter(|name| !name.is_empty())?;
+        Some(GtkSettings {
+            theme_name: Some(theme_name.to_string()),
+            prefer_dark_theme: parts.next() == Some("dark"),
+        })
+    }
+}
+
+/// Colors of a GTK theme that are relevant for the native widgets
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct GtkThemeColors {
+    /// `theme_fg_color`: text color of windows and buttons
+    pub fg: ColorU,
+    /// `theme_bg_color`: background of windows and buttons
+    pub bg: ColorU,
+    /// `theme_base_color`: background of text inputs and tables
+    pub base: ColorU,
+    /// `theme_text_color`: text color of text inputs and tables
+    pub text: ColorU,
+    /// `theme_selected_bg_color`: background of selections, also used for focus highlights
+    pub selected_bg: ColorU,
+    /// `theme_selected_fg_color`: text color of selections
+    pub selected_fg: ColorU,
+    /// `borders`: color of widget borders
+    pub borders: ColorU,
+}
+
+impl GtkThemeColors {
+
+    /// Picks the theme colors out of the `@define-color` values of a theme. Both the GTK 3
+    /// (`theme_bg_color`) and the older (`bg_color`) names are accepted. Returns `None` if
+    /// the theme doesn't define at least a foreground and a background color.
+    pub fn from_defined_colors(colors: &BTreeMap<String, ColorU>) -> Option<Self> {
+        let get = |name: &str| colors.get(&format!("theme_{}", name)).or_else(|| colors.get(name)).cloned();
+
+        let fg = get("fg_color")?;
+        let bg = get("bg_color")?;
+        let base = get("base_color").unwrap_or(bg);
+        let text = get("text_color").unwrap_or(fg);
+        let selected_bg = get("selected_bg_color").unwrap_or(ColorU { r: 66, g: 134, b: 244, a: 255 });
+        let selected_fg = get("selected_fg_color").unwrap_or(ColorU { r: 255, g: 255, b: 255, a: 255 });
+        let borders = colors.get("borders").cloned().unwrap_or_else(|| shade(bg, 0.7));
+
+        Some(GtkThemeColors { fg, bg, base, text, selected_bg, selected_fg, borders })
+    }
+
+    /// Returns the CSS that overrides the colors of `native_linux.css` with the theme colors
+    pub fn to_css(&self) -> String {
+        let hex = |color: ColorU| CssColor::from(color).to_string(true);
+        let fg = hex(self.fg);
+        let bg = hex(self.bg);
+        let base = hex(self.base);
+        let text = hex(self.text);
+        let selected_bg = hex(self.selected_bg);
+        let selected_fg = hex(self.selected_fg);
+        let borders = hex(self.borders);
+        let button_top = hex(shade(self.bg, 1.04));
+        let button_bottom = hex(shade(self.bg, 0.96));
+
+        format!("
+* {{
+    color: {fg};
+}}
+
+.__azul-native-button {{
+    border: 1px solid {borders};
+    background: linear-gradient({button_top}, {button_bottom});
+}}
+
+.__azul-native-input-text {{
+    background-color: {base};
+    color: {text};
+    border: 1px solid {borders};
+}}
+
+.__azul-native-input-text:hover {{
+    border: 1px solid {selected_bg};
+}}
+
+.__azul-native-table-column {{
+    border-right: 1px solid {borders};
+}}
+
+.__azul-native-table-top-left-rect {{
+    background-color: {bg};
+    border-bottom: 1px solid {borders};
+    border-right: 1px solid {borders};
+}}
+
+.__azul-native-table-cell {{
+    color: {text};
+    border-bottom: 1px solid {borders};
+}}
+
+.__azul-native-table-row {{
+    border-bottom: 0.6px solid {borders};
+}}
+
+.__azul-native-table-column-name {{
+    color: {fg};
+    background-color: {bg};
+    border-right: 0.6px solid {borders};
+}}
+
+.__azul-native-table-row-numbers {{
+    color: {fg};
+    background-color: {bg};
+}}
+
+.__azul-native-table-selection {{
+    border: 2px solid {selected_bg};
+}}
+
+.__azul-native-table-selection-handle {{
+    background-color: {selected_bg};
+    color: {selected_fg};
+}}
+",
+            fg = fg, bg = bg, base = base, text = text,
+            selected_bg = selected_bg, selected_fg = selected_fg, borders = borders,
+            button_top = button_top, button_bottom = button_bottom,
+        )
+    }
+}
+
+/// Returns the native Linux style, colored like the currently active GTK theme.
+/// Falls back to `native()` if no theme (or no theme colors) could be found.
+pub fn native_gtk() -> Css {
+    native_from_gtk_theme(&gtk_theme_directories(), &current_gtk_settings())
+}
+
+/// Same as `native_gtk()`, but loads the theme named in `settings` from the given theme
+/// directories (each containing `<theme name>/gtk-3.0/gtk.css`) instead of the system ones.
+pub fn native_from_gtk_theme(theme_dirs: &[PathBuf], settings: &GtkSettings) -> Css {
+    let theme_colors = load_gtk_theme_css(theme_dirs, settings)
+        .and_then(|css| GtkThemeColors::from_defined_colors(&parse_defined_colors(&css)));
+
+    let css = match theme_colors {
+        Some(colors) => format!("{}\n{}", NATIVE_CSS, colors.to_css()),
+        None => NATIVE_CSS.to_string(),
+    };
+
+    azul_css_parser::new_from_str(&css).unwrap()
+}
+
+/// Returns the GTK settings of the current user: `GTK_THEME` takes precedence over
+/// `gsettings`, which takes precedence over `~/.config/gtk-3.0/settings.ini`.
+pub fn current_gtk_settings() -> GtkSettings {
+
+    let mut settings = config_directories().into_iter()
+        .filter_map(|dir| fs::read_to_string(dir.join("gtk-3.0").join("settings.ini")).ok())
+        .map(|ini| GtkSettings::from_ini(&ini))
+        .next()
+        .unwrap_or_default();
+
+    // Execute "gsettings get org.gnome.desktop.interface gtk-theme" and parse the output
+    let gsettings_theme =
+        Command::new("gsettings")
+            .arg("get")
+            .arg("org.gnome.desktop.interface")
+            .arg("gtk-theme")
+            .output()
+            .ok().map(|output| output.stdout)
+            .and_then(|stdout_bytes| String::from_utf8(stdout_bytes).ok())
+            .map(|stdout_string| stdout_string.trim().trim_matches('\'').to_string())
+            .filter(|theme_name| !theme_name.is_empty());
+
+    if let Some(theme_name) = gsettings_theme {
+        settings.theme_name = Some(theme_name);
+    }
+
+    if let Some(env_settings) = env::var("GTK_THEME").ok().and_then(|s| GtkSettings::from_gtk_theme_env(&s)) {
+        settings = env_settings;
+    }
+
+    settings
+}
+
+/// Returns the directories GTK searches for themes, in order of precedence
+pub fn gtk_theme_directories() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    let data_home = env::var_os("XDG_DATA_HOME").map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share")));
+    if let Some(data_home) = data_home {
+        dirs.push(data_home.join("themes"));
+    }
+
+    if let Some(home) = env::var_os("HOME") {
+        dirs.push(PathBuf::from(home).join(".themes"));
+    }
+
+    let data_dirs = env::var("XDG_DATA_DIRS").unwrap_or_else(|_| "/usr/local/share:/usr/share".to_string());
+    dirs.extend(data_dirs.split(':').filter(|dir| !dir.is_empty()).map(|dir| PathBuf::from(dir).join("themes")));
+
+    dirs
+}
+
+/// Returns `$XDG_CONFIG_HOME` (or `~/.config`)
+fn config_directories() -> Vec<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME").map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .into_iter()
+        .collect()
+}
+
+/// Loads the `gtk.css` (or `gtk-dark.css`) of the theme from the first theme directory that
+/// contains it, with all relative `@import`s inlined
+pub fn load_gtk_theme_css(theme_dirs: &[PathBuf], settings: &GtkSettings) -> Option<String> {
+    let theme_name = settings.theme_name.as_ref()?;
+
+    let file_names: &[&str] = if settings.prefer_dark_theme {
+        &["gtk-dark.css", "gtk.css"]
+    } else {
+        &["gtk.css"]
+    };
+
+    theme_dirs.iter()
+        .map(|dir| dir.join(theme_name).join("gtk-3.0"))
+        .flat_map(|dir| file_names.iter().map(move |file_name| dir.join(file_name)))
+        .filter_map(|path| load_css_with_imports(&path, 0))
+        .next()
+}
+
+fn load_css_with_imports(path: &Path, depth: usize) -> Option<String> {
+    if depth > MAX_IMPORT_DEPTH {
+        return None;
+    }
+
+    let css = strip_comments(&fs::read_to_string(path).ok()?);
+    let mut output = String::new();
+
+    for statement in css.split_terminator(';') {
+        match parse_import_url(statement) {
+            Some(url) => {
+                // "resource:///" imports point into the compiled GTK library, skip them
+                if url.contains("://") && !url.starts_with("file://") {
+                    continue;
+                }
+                let import_path = path.parent()?.join(url.trim_start_matches("file://"));
+                if let Some(imported) = load_css_with_imports(&import_path, depth + 1) {
+                    output.push_str(&imported);
+                }
+            },
+            None => {
+                output.push_str(statement);
+                output.push(';');
+            },
+        }
+    }
+
+    Some(output)
+}
+
+// '@import url("gtk-main.css")' => gtk-main.css
+fn parse_import_url(statement: &str) -> Option<&str> {
+    let statement = statement.trim();
+    if !statement.starts_with("@import") {
+        return None;
+    }
+    let url = statement["@import".len()..].trim();
+    let url = if url.starts_with("url(") && url.ends_with(')') { &url[4..url.len() - 1] } else { url };
+    Some(url.trim().trim_matches('"').trim_matches('\''))
+}
+
+fn strip_comments(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find("/*") {
+        output.push_str(&rest[..start]);
+        rest = match rest[start..].find("*/") {
+            Some(end) => &rest[start + end + 2..],
+            None => "",
+        };
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Parses all `@define-color name value;` statements of a GTK stylesheet. Values can
+/// be CSS colors, `@references` to previously defined colors or one of the GTK color
+/// functions `shade()`, `alpha()`, `mix()`, `lighter()` and `darker()`.
+/// Colors that can't be parsed are ignored.
+pub fn parse_defined_colors(css: &str) -> BTreeMap<String, ColorU> {
+    let mut colors = BTreeMap::new();
+
+    for statement in strip_comments(css).split(';') {
+        let statement = statement.trim();
+        if !statement.starts_with("@define-color") {
+            continue;
+        }
+        let mut name_value = statement["@define-color".len()..].trim().splitn(2, char::is_whitespace);
+        let (name, value) = match (name_value.next(), name_value.next()) {
+            (Some(n), Some(v)) => (n, v),
+            _ => continue,
+        };
+        if let Some(color) = parse_gtk_color(value, &colors) {
+            colors.insert(name.to_string(), color);
+        }
+    }
+
+    colors
+}
+
+/// Parses a GTK color expression, see `parse_defined_colors`
+pub fn parse_gtk_color(input: &str, colors: &BTreeMap<String, ColorU>) -> Option<ColorU> {
+    let input = input.trim();
+
+    if input.starts_with('@') {
+        return colors.get(&input[1..]).cloned();
+    }
+
+    let function = input.find('(').filter(|_| input.ends_with(')')).map(|brace| {
+        (input[..brace].trim(), split_arguments(&input[brace + 1..input.len() - 1]))
+    });
+
+    let factor = |input: &str| input.trim().parse::<f32>().ok();
+
+    match function {
+        Some(("shade", ref args)) if args.len() == 2 => Some(shade(parse_gtk_color(args[0], colors)?, factor(args[1])?)),
+        Some(("lighter", ref args)) if args.len() == 1 => Some(shade(parse_gtk_color(args[0], colors)?, 1.3)),
+        Some(("darker", ref args)) if args.len() == 1 => Some(shade(parse_gtk_color(args[0], colors)?, 0.7)),
+        Some(("alpha", ref args)) if args.len() == 2 => {
+            let color = parse_gtk_color(args[0], colors)?;
+            let alpha = (color.a as f32 * factor(args[1])?).max(0.0).min(255.0).round() as u8;
+            Some(ColorU { a: alpha, .. color })
+        },
+        Some(("mix", ref args)) if args.len() == 3 => {
+            let a = parse_gtk_color(args[0], colors)?;
+            let b = parse_gtk_color(args[1], colors)?;
+            let t = factor(args[2])?.max(0.0).min(1.0);
+            let channel = |x: u8, y: u8| (x as f32 + (y as f32 - x as f32) * t).round() as u8;
+            Some(ColorU { r: channel(a.r, b.r), g: channel(a.g, b.g), b: channel(a.b, b.b), a: channel(a.a, b.a) })
+        },
+        _ => azul_css_parser::parse_css_color(input).ok(),
+    }
+}
+
+/// Splits the arguments of a function call at the commas that are not inside of braces
+fn split_arguments(input: &str) -> Vec<&str> {
+    let mut arguments = Vec::new();
+    let mut depth = 0;
+    let mut last = 0;
+    for (i, c) in input.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                arguments.push(input[last..i].trim());
+                last = i + 1;
+            },
+            _ => { },
+        }
+    }
+    arguments.push(input[last..].trim());
+    arguments
+}
+
+/// Approximates the GTK `shade()` function: factors below 1.0 darken the
+/// color towards black, factors above 1.0 lighten it towards white.
+fn shade(color: ColorU, factor: f32) -> ColorU {
+    let channel = |c: u8| {
+        let c = c as f32;
+        let shaded = if factor <= 1.0 { c * factor } else { c + (255.0 - c) * (factor - 1.0) };
+        shaded.max(0.0).min(255.0).round() as u8
+    };
+    ColorU { r: channel(color.r), g: channel(color.g), b: channel(color.b), a: color.a }
+}
+
+#[cfg(test)]
+fn fixture_theme_dirs() -> Vec<PathBuf> {
+    vec![PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("gtk-themes")]
+}
+
+#[test]
+fn test_gtk_settings_from_ini() {
+    let ini = "
+        [Settings]
+        gtk-theme-name=Breeze
+        gtk-application-prefer-dark-theme=1
+        gtk-font-name=Noto Sans,  10
+    ";
+    assert_eq!(GtkSettings::from_ini(ini), GtkSettings {
+        theme_name: Some("Breeze".into()),
+        prefer_dark_theme: true,
+    });
+    assert_eq!(GtkSettings::from_ini("[Other]\ngtk-theme-name=Breeze"), GtkSettings::default());
+    assert_eq!(GtkSettings::from_gtk_theme_env("Adwaita:dark"), Some(GtkSettings {
+        theme_name: Some("Adwaita".into()),
+        prefer_dark_theme: true,
+    }));
+}
+
+#[test]
+fn test_parse_defined_colors() {
+    let css = "
+        /* @define-color commented_out #000000; */
+        @define-color theme_bg_color #f6f5f4;
+        @define-color theme_fg_color rgb(46, 52, 54);
+        @define-color borders shade(@theme_bg_color, 0.5);
+        @define-color unfocused alpha(@theme_fg_color, 0.5);
+        @define-color halfway mix(#000000, #ffffff, 0.5);
+        @define-color unknown @does_not_exist;
+    ";
+    let colors = parse_defined_colors(css);
+    assert_eq!(colors.get("commented_out"), None);
+    assert_eq!(colors.get("unknown"), None);
+    assert_eq!(colors["theme_bg_color"], ColorU { r: 246, g: 245, b: 244, a: 255 });
+    assert_eq!(colors["theme_fg_color"], ColorU { r: 46, g: 52, b: 54, a: 255 });
+    assert_eq!(colors["borders"], ColorU { r: 123, g: 123, b: 122, a: 255 });
+    assert_eq!(colors["unfocused"], ColorU { r: 46, g: 52, b: 54, a: 128 });
+    assert_eq!(colors["halfway"], ColorU { r: 128, g: 128, b: 128, a: 255 });
+}
+
+#[test]
+fn test_load_gtk_theme_from_fixtures() {
+    let dirs = fixture_theme_dirs();
+    let light = GtkSettings { theme_name: Some("Fixture".into()), prefer_dark_theme: false };
+    let dark = GtkSettings { theme_name: Some("Fixture".into()), prefer_dark_theme: true };
+
+    let light_colors = load_gtk_theme_css(&dirs, &light)
+        .and_then(|css| GtkThemeColors::from_defined_colors(&parse_defined_colors(&css)))
+        .unwrap();
+    assert_eq!(light_colors.bg, ColorU { r: 246, g: 245, b: 244, a: 255 });
+    assert_eq!(light_colors.base, ColorU { r: 255, g: 255, b: 255, a: 255 });
+    assert_eq!(light_colors.selected_bg, ColorU { r: 53, g: 132, b: 228, a: 255 });
+
+    let dark_colors = load_gtk_theme_css(&dirs, &dark)
+        .and_then(|css| GtkThemeColors::from_defined_colors(&parse_defined_colors(&css)))
+        .unwrap();
+    assert_eq!(dark_colors.bg, ColorU { r: 53, g: 53, b: 53, a: 255 });
+    assert_eq!(dark_colors.fg, ColorU { r: 238, g: 238, b: 236, a: 255 });
+
+    assert!(native_from_gtk_theme(&dirs, &light) != native_from_gtk_theme(&dirs, &dark));
+}
+
+#[test]
+fn test_native_from_gtk_theme_fallback() {
+    let dirs = fixture_theme_dirs();
+    let fallback = azul_css_parser::new_from_str(NATIVE_CSS).unwrap();
+    let without_colors = GtkSettings { theme_name: Some("ResourceOnly".into()), prefer_dark_theme: false };
+    let missing = GtkSettings { theme_name: Some("DoesNotExist".into()), prefer_dark_theme: false };
+
+    assert_eq!(native_from_gtk_theme(&dirs, &without_colors), fallback);
+    assert_eq!(native_from_gtk_theme(&dirs, &missing), fallback);
+    assert_eq!(native_from_gtk_theme(&dirs, &GtkSettings::default()), fallback);
+}
@@ -5,6 +5,9 @@ use azul_css::Css;
 
 extern crate azul_css_parser;
 
+#[cfg(target_os="linux")]
+pub mod gtk;
+
 /// CSS mimicking the OS-native look - Windows: `styles/native_windows.css`
 #[cfg(target_os="windows")]
 pub const NATIVE_CSS: &str = concat!(
@@ -26,7 +29,79 @@ pub const NATIVE_CSS: &str = concat!(
     include_str!("styles/shared/table.css"),
 );
 
+/// Overrides applied on top of `NATIVE_CSS` for the dark theme: `styles/themes/dark.css`
+pub const DARK_THEME_CSS: &str = include_str!("styles/themes/dark.css");
+
+/// Overrides applied on top of `NATIVE_CSS` for the high-contrast theme:
+/// `styles/themes/high_contrast.css`
+pub const HIGH_CONTRAST_THEME_CSS: &str = include_str!("styles/themes/high_contrast.css");
+
+/// Color scheme variant of the native style
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NativeTheme {
+    /// The default (light) OS look, same as `native()`
+    Light,
+    /// Light text on dark widgets
+    Dark,
+    /// White-on-black with thicker borders, for accessibility
+    HighContrast,
+}
+
+impl Default for NativeTheme {
+    fn default() -> Self {
+        NativeTheme::Light
+    }
+}
+
+impl NativeTheme {
+    /// Returns the CSS that gets appended to `NATIVE_CSS` for this theme.
+    /// Since rules are sorted by specificity with a stable sort, the
+    /// overrides win over the base rules they replace.
+    pub fn overrides(&self) -> &'static str {
+        match self {
+            NativeTheme::Light => "",
+            NativeTheme::Dark => DARK_THEME_CSS,
+            NativeTheme::HighContrast => HIGH_CONTRAST_THEME_CSS,
+        }
+    }
+}
+
 /// Returns the native style for the OS
 pub fn native() -> Css {
     azul_css_parser::new_from_str(NATIVE_CSS).unwrap()
 }
+
+/// Returns the native style for the OS in the given color scheme.
+///
+/// Use `Window::set_css` / `FakeWindow::set_css` to switch between themes at runtime.
+pub fn native_with_theme(theme: NativeTheme) -> Css {
+    let css = format!("{}\n{}", NATIVE_CSS, theme.overrides());
+    azul_css_parser::new_from_str(&css).unwrap()
+}
+
+#[test]
+fn test_native_themes_parse() {
+    for theme in &[NativeTheme::Light, NativeTheme::Dark, NativeTheme::HighContrast] {
+        assert!(azul_css_parser::new_from_str(theme.overrides()).is_ok());
+        native_with_theme(*theme);
+    }
+    assert_eq!(native(), native_with_theme(NativeTheme::Light));
+}
+
+// Lint all stylesheets that ship with this crate, so that typos don't go unnoticed
+#[test]
+fn test_lint_bundled_stylesheets() {
+    let stylesheets = [
+        ("native_windows.css", include_str!("styles/native_windows.css")),
+        ("native_linux.css", include_str!("styles/native_linux.css")),
+        ("native_macos.css", include_str!("styles/native_macos.css")),
+        ("table.css", include_str!("styles/shared/table.css")),
+        ("dark.css", DARK_THEME_CSS),
+        ("high_contrast.css", HIGH_CONTRAST_THEME_CSS),
+    ];
+
+    for (file_name, css) in stylesheets.iter() {
+        let lints = azul_css_parser::lint_css(css);
+        assert!(lints.is_empty(), "{}: {:?}", file_name, lints);
+    }
+}
@@ -319,6 +319,8 @@ impl<T: Layout> App<T> {
             state: window.state.clone(),
             default_callbacks: DefaultCallbackSystem::new(),
             read_only_window: window.display.clone(),
+            pending_css: None,
+            css_variables: FastHashMap::default(),
         };
 
         self.app_state.windows.insert(window_id, fake_window);
@@ -386,6 +388,8 @@ impl<T: Layout> App<T> {
         let mut last_style_reload = Instant::now();
         #[cfg(debug_assertions)]
         let mut should_print_css_error = true;
+        #[cfg(debug_assertions)]
+        let mut printed_css_warnings = Vec::new();
 
         while !self.windows.is_empty() {
 
@@ -444,10 +448,13 @@ impl<T: Layout> App<T> {
                     &mut self.windows,
                     &mut last_style_reload,
                     &mut should_print_css_error,
+                    &mut printed_css_warnings,
                     &mut awakened_task
                 )?;
             }
 
+            swap_pending_css(&mut self.windows, &mut self.app_state, &mut awakened_task)?;
+
             // Close windows if necessary
             closed_windows.into_iter().for_each(|closed_window_id| {
                 ui_state_cache.remove(&closed_window_id);
@@ -647,6 +654,15 @@ fn render_single_window_content<T: Layout>(
     // Scroll for the scrolled amount for each node that registered a scroll state.
     let should_scroll_render = update_scroll_state(window, hit_test_results, &mut app_state.resources);
 
+    // Drag the scrollbar thumbs / jump to the clicked position on the scrollbar track
+    let scrollbars_changed = update_scrollbar_state(window, events);
+
+    // The position of the scrollbar thumbs is part of the display list,
+    // so the display list has to be rebuilt if a node with scrollbars was scrolled
+    if scrollbars_changed || (should_scroll_render && window.internal.last_scrolled_nodes.has_scrollbars()) {
+        frame_event_info.should_redraw_window = true;
+    }
+
     if frame_event_info.is_resize_event {
         // This is a hack because during a resize event, winit eats the "awakened"
         // event. So what we do is that we call the layout-and-render again, to
@@ -736,6 +752,7 @@ fn hot_reload_css<T: Layout>(
     windows: &mut BTreeMap<GliumWindowId, Window<T>>,
     last_style_reload: &mut Instant,
     should_print_error: &mut bool,
+    printed_warnings: &mut Vec<String>,
     awakened_tasks: &mut BTreeMap<GliumWindowId, bool>)
 -> Result<(), RuntimeError<T>>
 {
@@ -757,7 +774,18 @@ fn hot_reload_css<T: Layout>(
             Ok(mut new_css) => {
                 new_css.sort_by_specificity();
                 window.css = new_css;
-                if !(*should_print_error) {
+
+                // Only print the warnings when they changed (or after an error, so that they
+                // don't get lost in between the error messages)
+                let warnings = hot_reloader.get_warnings();
+                if !(*should_print_error) || warnings != &printed_warnings[..] {
+                    for warning in warnings {
+                        println!("{}", warning);
+                    }
+                    *printed_warnings = warnings.to_vec();
+                }
+
+                if !(*should_print_error) && warnings.is_empty() {
                     println!("--- OK: CSS parsed without errors, continuing hot-reload.");
                 }
                 *last_style_reload = Instant::now();
@@ -778,6 +806,55 @@ fn hot_reload_css<T: Layout>(
     Ok(())
 }
 
+/// Applies the styles that were set via `FakeWindow::set_css` during this frame
+fn swap_pending_css<T: Layout>(
+    windows: &mut BTreeMap<GliumWindowId, Window<T>>,
+    app_state: &mut AppState<T>,
+    awakened_tasks: &mut BTreeMap<GliumWindowId, bool>)
+-> Result<(), RuntimeError<T>>
+{
+    use self::RuntimeError::*;
+    for (window_id, window) in windows.iter_mut() {
+        let fake_window = match app_state.windows.get_mut(window_id) {
+            None => continue,
+            Some(s) => s,
+        };
+        if apply_pending_css(&mut fake_window.pending_css, |css| window.set_css(css)) {
+            *awakened_tasks.get_mut(window_id).ok_or(WindowIndexError)? = true;
+        }
+    }
+
+    Ok(())
+}
+
+/// Takes the pending style of a window (so that it is only applied once) and passes it
+/// to `set_css`, returns whether there was a pending style and the window needs a relayout
+fn apply_pending_css<F: FnOnce(Css)>(pending_css: &mut Option<Css>, set_css: F) -> bool {
+    match pending_css.take() {
+        Some(css) => { set_css(css); true },
+        None => false,
+    }
+}
+
+#[test]
+fn test_apply_pending_css() {
+    use azul_css::{CssRuleBlock, CssPath, CssPathSelector};
+
+    let new_css = Css {
+        stylesheets: vec![vec![CssRuleBlock::new(CssPath { selectors: vec![CssPathSelector::Global] }, Vec::new())].into()],
+    };
+
+    let mut window_css = Css::default();
+    let mut pending_css = Some(new_css.clone());
+
+    // the pending style is applied and cleared, so the next frame doesn't apply it again
+    assert!(apply_pending_css(&mut pending_css, |css| window_css = css));
+    assert_eq!(window_css, new_css);
+    assert_eq!(pending_css, None);
+
+    assert!(!apply_pending_css(&mut pending_css, |_| panic!("no style is pending")));
+}
+
 /// Returns the currently hit-tested results, in back-to-front order
 fn do_hit_test<T: Layout>(window: &Window<T>, app_resources: &AppResources) -> Option<HitTestResult> {
 
@@ -1029,26 +1106,138 @@ fn update_scroll_state<T: Layout>(
         return false;
     }
 
-    let mut should_scroll_render = false;
-
     let scrolled_nodes = &window.internal.last_scrolled_nodes;
     let scroll_states = &mut window.scroll_states;
 
-    for scroll_node in hit_test_results.items.iter()
+    // The hit-test items are sorted back-to-front: Scroll the innermost node that
+    // can still be scrolled in this direction, like browsers do
+    for scroll_node in hit_test_results.items.iter().rev()
         .filter_map(|item| scrolled_nodes.tags_to_node_ids.get(&ScrollTagId(item.tag.0)))
         .filter_map(|node_id| scrolled_nodes.overflowing_nodes.get(&node_id)) {
 
         // The external scroll ID is constructed from the DOM hash
         let scroll_id = scroll_node.parent_external_scroll_id;
 
-        if scroll_states.0.contains_key(&scroll_id) {
-            // TODO: make scroll speed configurable (system setting?)
-            scroll_states.scroll_node(&scroll_id, scroll_x as f32, scroll_y as f32);
-            should_scroll_render = true;
+        // TODO: make scroll speed configurable (system setting?)
+        if scroll_states.scroll_node(&scroll_id, scroll_x as f32, scroll_y as f32) {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Handles mouse events on the scrollbars of the last frame: Pressing the left mouse button on
+/// a thumb starts dragging it, pressing it on the track scrolls by one page towards the cursor.
+///
+/// Returns whether any scroll state has changed.
+fn update_scrollbar_state<T: Layout>(window: &mut Window<T>, events: &[WindowEvent]) -> bool {
+
+    use glium::glutin::{ElementState, MouseButton};
+    use display_list::ScrollbarAxis;
+    use window::ScrollbarDrag;
+
+    let mut scroll_state_changed = false;
+
+    for event in events {
+        match event {
+            WindowEvent::MouseInput { state: ElementState::Pressed, button: MouseButton::Left, .. } => {
+
+                let window_cursor = match window.state.internal.mouse_state.cursor_pos {
+                    Some(c) => LayoutPoint::new(c.x as f32, c.y as f32),
+                    None => continue,
+                };
+
+                // Nested nodes have higher node IDs, so the innermost scrollbar wins
+                let hit_scrollbar = window.internal.last_scrolled_nodes.overflowing_nodes.values().rev().filter_map(|node| {
+                    // The scrollbars of nested scroll frames move with the content of the parent
+                    let cursor = window_cursor + node.parent_scroll_offset;
+                    let horizontal = node.horizontal_scrollbar.map(|g| (ScrollbarAxis::Horizontal, g));
+                    let vertical = node.vertical_scrollbar.map(|g| (ScrollbarAxis::Vertical, g));
+                    horizontal.into_iter().chain(vertical.into_iter())
+                        .find(|(_, geometry)| geometry.track.contains(&cursor))
+                        .map(|(axis, geometry)| (node.parent_external_scroll_id, axis, geometry, cursor))
+                }).next();
+
+                let (scroll_id, axis, geometry, cursor) = match hit_scrollbar {
+                    Some(s) => s,
+                    None => continue,
+                };
+
+                let (scroll_x, scroll_y) = match window.scroll_states.get_scroll_amount(&scroll_id) {
+                    Some(s) => s,
+                    None => continue,
+                };
+
+                let (cursor_pos, thumb_start, thumb_end, scroll_amount) = match axis {
+                    ScrollbarAxis::Horizontal => (cursor.x, geometry.thumb.min_x(), geometry.thumb.max_x(), scroll_x),
+                    ScrollbarAxis::Vertical => (cursor.y, geometry.thumb.min_y(), geometry.thumb.max_y(), scroll_y),
+                };
+
+                if cursor_pos >= thumb_start && cursor_pos <= thumb_end {
+                    window.internal.scrollbar_drag = Some(ScrollbarDrag {
+                        scroll_id,
+                        axis,
+                        start_cursor_pos: cursor_pos,
+                        start_scroll_amount: scroll_amount,
+                        scroll_per_thumb_px: geometry.scroll_per_thumb_px,
+                    });
+                } else {
+                    let page = if cursor_pos < thumb_start { -geometry.page_size } else { geometry.page_size };
+                    let (scroll_by_x, scroll_by_y) = match axis {
+                        ScrollbarAxis::Horizontal => (page, 0.0),
+                        ScrollbarAxis::Vertical => (0.0, page),
+                    };
+                    if window.scroll_states.scroll_node(&scroll_id, scroll_by_x, scroll_by_y) {
+                        scroll_state_changed = true;
+                    }
+                }
+            },
+            WindowEvent::MouseInput { state: ElementState::Released, button: MouseButton::Left, .. } => {
+                window.internal.scrollbar_drag = None;
+            },
+            WindowEvent::CursorMoved { .. } => {
+
+                let drag = match window.internal.scrollbar_drag {
+                    Some(d) => d,
+                    None => continue,
+                };
+
+                // The event position isn't DPI-adjusted, so use the adjusted cursor position instead
+                let position = match window.state.internal.mouse_state.cursor_pos {
+                    Some(p) => p,
+                    None => continue,
+                };
+
+                let (scroll_x, scroll_y) = match window.scroll_states.get_scroll_amount(&drag.scroll_id) {
+                    Some(s) => s,
+                    None => {
+                        // The scrolled node doesn't exist anymore
+                        window.internal.scrollbar_drag = None;
+                        continue;
+                    },
+                };
+
+                let cursor_pos = match drag.axis {
+                    ScrollbarAxis::Horizontal => position.x as f32,
+                    ScrollbarAxis::Vertical => position.y as f32,
+                };
+
+                let new_scroll_amount = drag.start_scroll_amount + (cursor_pos - drag.start_cursor_pos) * drag.scroll_per_thumb_px;
+                let (new_x, new_y) = match drag.axis {
+                    ScrollbarAxis::Horizontal => (new_scroll_amount, scroll_y),
+                    ScrollbarAxis::Vertical => (scroll_x, new_scroll_amount),
+                };
+
+                if window.scroll_states.set_scroll_amount(&drag.scroll_id, new_x, new_y) {
+                    scroll_state_changed = true;
+                }
+            },
+            _ => { },
         }
     }
 
-    should_scroll_render
+    scroll_state_changed
 }
 
 fn clean_up_unused_opengl_textures(pipeline_info: PipelineInfo) {
@@ -8,6 +8,7 @@ use webrender::api::{
     ResourceUpdate, AddFont, AddFontInstance,
 };
 use app_units::Au;
+use azul_css::ConicGradient;
 use clipboard2::{Clipboard, ClipboardError, SystemClipboard};
 use {
     FastHashMap, FastHashSet,
@@ -68,6 +69,11 @@ pub struct AppResources {
     text_cache: TextCache,
     /// Keyboard clipboard storage and retrieval functionality
     clipboard: SystemClipboard,
+    /// WebRender can't draw conic gradients, so they are rasterized into images, keyed by
+    /// the gradient and the size of the image (see `display_list::rasterize_conic_gradient`)
+    conic_gradient_images: FastHashMap<(ConicGradient, usize, usize), ImageKey>,
+    /// Conic gradient images that were used during this frame, the others get deleted
+    used_conic_gradient_images: FastHashSet<(ConicGradient, usize, usize)>,
 }
 
 static TEXT_ID_COUNTER: AtomicUsize = AtomicUsize::new(0);
@@ -297,6 +303,8 @@ impl AppResources {
             pending_frame_font_keys: FastHashMap::default(),
             text_cache: TextCache::default(),
             clipboard: SystemClipboard::new().unwrap(),
+            conic_gradient_images: FastHashMap::default(),
+            used_conic_gradient_images: FastHashSet::default(),
         })
     }
 
@@ -468,7 +476,8 @@ impl AppResources {
     /// needs to be reloaded from the `FontSource`.
     pub(crate) fn garbage_collect_fonts_and_images(&mut self) {
         let delete_font_keys = build_delete_font_resource_updates(self);
-        let delete_image_keys = build_delete_image_resource_updates(self);
+        let mut delete_image_keys = build_delete_image_resource_updates(self);
+        delete_image_keys.extend(build_delete_conic_gradient_resource_updates(self));
 
         delete_resources(self, delete_font_keys, delete_image_keys);
 
@@ -477,6 +486,39 @@ impl AppResources {
         self.pending_frame_font_keys.clear();
         self.pending_frame_image_keys.clear();
     }
+
+    /// Returns the image key of a rasterized conic gradient with the given size in pixels.
+    /// If the gradient isn't registered yet, `rasterize` is called to get the BGRA8 pixels.
+    pub(crate) fn get_or_add_conic_gradient_image<F: FnOnce() -> Vec<u8>>(
+        &mut self,
+        gradient: &ConicGradient,
+        width: usize,
+        height: usize,
+        rasterize: F,
+    ) -> ImageKey {
+
+        use webrender::api::{ImageData, AddImage};
+
+        let cache_key = (gradient.clone(), width, height);
+        self.used_conic_gradient_images.insert(cache_key.clone());
+
+        if let Some(image_key) = self.conic_gradient_images.get(&cache_key) {
+            return *image_key;
+        }
+
+        let pixels = rasterize();
+        let opaque = is_image_opaque(RawImageFormat::BGRA8, &pixels[..]);
+        let allow_mipmaps = false;
+        let descriptor = ImageDescriptor::new(width as i32, height as i32, RawImageFormat::BGRA8, opaque, allow_mipmaps);
+        let image_key = self.fake_display.render_api.generate_image_key();
+
+        self.fake_display.render_api.update_resources(vec![ResourceUpdate::AddImage(
+            AddImage { key: image_key, descriptor, data: ImageData::new(pixels), tiling: None }
+        )]);
+
+        self.conic_gradient_images.insert(cache_key, image_key);
+        image_key
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -532,14 +574,18 @@ fn scan_ui_description_for_image_keys<'a, T: Layout>(
     display_list.rectangles
     .iter()
     .zip(display_list.ui_descr.ui_descr_arena.node_data.iter())
-    .filter_map(|(display_rect, node_data)| {
+    .flat_map(|(display_rect, node_data)| {
         match node_data.node_type {
-            Image(id) => Some(id),
+            Image(id) => vec![id],
             _ => {
-                let background = display_rect.style.background.as_ref()?;
-                let css_image_id = background.get_css_image_id()?;
-                let image_id = app_resources.get_css_image_id(&css_image_id.0)?;
-                Some(*image_id)
+                // Every background layer can reference an image
+                display_rect.style.background.iter()
+                .flat_map(|backgrounds| backgrounds.0.iter())
+                .filter_map(|background| {
+                    let css_image_id = background.get_css_image_id()?;
+                    let image_id = app_resources.get_css_image_id(&css_image_id.0)?;
+                    Some(*image_id)
+                }).collect()
             }
         }
     }).collect()
@@ -871,6 +917,27 @@ fn build_delete_image_resource_updates(
     resource_updates
 }
 
+/// Deletes all conic gradient images that weren't used during the last frame
+fn build_delete_conic_gradient_resource_updates(
+    app_resources: &mut AppResources
+) -> Vec<ResourceUpdate> {
+
+    let used_conic_gradient_images = &app_resources.used_conic_gradient_images;
+    let unused_conic_gradients = app_resources.conic_gradient_images.keys()
+        .filter(|key| !used_conic_gradient_images.contains(key))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let resource_updates = unused_conic_gradients.iter()
+        .filter_map(|key| app_resources.conic_gradient_images.remove(key))
+        .map(ResourceUpdate::DeleteImage)
+        .collect();
+
+    app_resources.used_conic_gradient_images.clear();
+
+    resource_updates
+}
+
 fn delete_resources(
     app_resources: &mut AppResources,
     mut delete_font_resources: Vec<ResourceUpdate>,
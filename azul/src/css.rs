@@ -19,6 +19,7 @@
 //! | `cursor`                                           |              |             |            |                  |
 //! | `width`, `min-width`, `max-width`                  |              |             |            |                  |
 //! | `height`, `min-height`, `max-height`               |              |             |            |                  |
+//! | `aspect-ratio`                                     |              |             |            |                  |
 //! | `position`                                         |              |             |            |                  |
 //! | `top`, `right`, `left`, `bottom`                   |              |             |            |                  |
 //! | `flex-wrap`                                        |              |             |            |                  |
@@ -64,6 +65,12 @@ pub fn native() -> azul_css::Css {
     azul_native_style::native()
 }
 
+/// Same as `native()`, but in the given light / dark / high-contrast color scheme.
+#[cfg(feature = "native_style")]
+pub fn native_with_theme(theme: azul_native_style::NativeTheme) -> azul_css::Css {
+    azul_native_style::native_with_theme(theme)
+}
+
 /// Parses CSS stylesheet from a string. Convenience wrapper for `azul-css-parser::new_from_str`.
 #[cfg(feature = "css_parser")]
 pub fn from_str(input: &str) -> Result<Css, CssParseError> {
@@ -266,6 +273,30 @@ pub(crate) mod webrender_translate {
         }
     }
 
+    use azul_css::StyleFilterFunction as CssFilterFunction;
+    use webrender::api::FilterOp as WrFilterOp;
+
+    #[inline(always)]
+    pub fn wr_translate_filter_function(input: CssFilterFunction) -> WrFilterOp {
+        use webrender::api::{LayoutVector2D, PropertyBinding};
+        match input {
+            CssFilterFunction::Blur(radius)         => WrFilterOp::Blur(radius.to_pixels()),
+            CssFilterFunction::Brightness(amount)   => WrFilterOp::Brightness(amount.get()),
+            CssFilterFunction::Contrast(amount)     => WrFilterOp::Contrast(amount.get()),
+            CssFilterFunction::DropShadow(shadow)   => WrFilterOp::DropShadow(
+                LayoutVector2D::new(shadow.offset[0].to_pixels(), shadow.offset[1].to_pixels()),
+                shadow.blur_radius.to_pixels(),
+                wr_translate_color_u(shadow.color).into(),
+            ),
+            CssFilterFunction::Grayscale(amount)    => WrFilterOp::Grayscale(amount.get()),
+            CssFilterFunction::HueRotate(degrees)   => WrFilterOp::HueRotate(degrees.get()),
+            CssFilterFunction::Invert(amount)       => WrFilterOp::Invert(amount.get()),
+            CssFilterFunction::Opacity(amount)      => WrFilterOp::Opacity(PropertyBinding::Value(amount.get()), amount.get()),
+            CssFilterFunction::Saturate(amount)     => WrFilterOp::Saturate(amount.get()),
+            CssFilterFunction::Sepia(amount)        => WrFilterOp::Sepia(amount.get()),
+        }
+    }
+
     use azul_css::StyleCursor as CssCursor;
     use glium::glutin::MouseCursor as WinitCursor;
 
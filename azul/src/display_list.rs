@@ -2,10 +2,11 @@
 
 use std::{
     fmt,
+    borrow::Cow,
     sync::{Arc, Mutex},
     collections::BTreeMap,
 };
-use euclid::{TypedRect, TypedSize2D};
+use euclid::{TypedRect, TypedSize2D, TypedPoint2D};
 use webrender::api::{
     LayoutPixel, DisplayListBuilder, PrimitiveInfo, GradientStop,
     ColorF, PipelineId, Epoch, ImageData, ImageDescriptor,
@@ -14,13 +15,18 @@ use webrender::api::{
     ComplexClipRegion, LayoutPrimitiveInfo, ExternalImageId,
     ExternalImageData, ImageFormat, ExternalImageType, TextureTarget,
     ImageRendering, AlphaType, FontInstanceFlags, FontRenderMode,
+    LayoutVector2D, ClipId,
 };
 use azul_css::{
-    Css, LayoutPosition,CssProperty, LayoutOverflow,
+    Css, LayoutPosition, CssProperty, LayoutOverflow,
     StyleBorderRadius, LayoutMargin, LayoutPadding, BoxShadowClipMode,
     StyleTextColor, StyleBackground, StyleBoxShadow, StyleBackgroundColor,
-    StyleBackgroundSize, StyleBackgroundRepeat, StyleBorder, BoxShadowPreDisplayItem,
+    StyleBackgroundSize, StyleBackgroundRepeat, StyleBorder, BoxShadowPreDisplayItem, StyleOutline,
+    StyleBackgroundPosition, StyleBackgroundOrigin, StyleBackgroundClip, StyleBackgroundAttachment,
+    BackgroundPositionHorizontal, BackgroundPositionVertical, ExtendMode,
+    ConicGradient as StyleConicGradient,
     RectStyle, RectLayout, ColorU as StyleColorU, DynamicCssPropertyDefault,
+    CssDeclaration, CssScrollbarPseudoElement, ScrollbarInfo, StyleFontSize, EM_HEIGHT,
 };
 use {
     FastHashMap,
@@ -42,7 +48,7 @@ use {
     window_state::WindowSize,
 };
 
-const DEFAULT_FONT_COLOR: StyleTextColor = StyleTextColor(StyleColorU { r: 0, b: 0, g: 0, a: 255 });
+pub(crate) const DEFAULT_FONT_COLOR: StyleTextColor = StyleTextColor(StyleColorU { r: 0, b: 0, g: 0, a: 255 });
 
 pub(crate) struct DisplayList<'a, T: Layout + 'a> {
     pub(crate) ui_descr: &'a UiDescription<T>,
@@ -94,14 +100,16 @@ impl<'a, T: Layout + 'a> DisplayList<'a, T> {
     pub(crate) fn new_from_ui_description(ui_description: &'a UiDescription<T>, ui_state: &UiState<T>) -> Self {
         let arena = &ui_description.ui_descr_arena;
 
-        let display_rect_arena = arena.node_data.transform(|node, node_id| {
+        let mut display_rect_arena = arena.node_data.transform(|node, node_id| {
             let style = ui_description.styled_nodes.get(&node_id).unwrap_or(&ui_description.default_style_of_node);
             let tag = ui_state.node_ids_to_tag_ids.get(&node_id).and_then(|tag| Some(*tag));
             let mut rect = DisplayRectangle::new(tag, style);
-            populate_css_properties(&mut rect, node_id, &ui_description.dynamic_css_overrides);
+            populate_css_properties(&mut rect, node_id, &ui_description.dynamic_css_overrides, &ui_description.window_css_overrides);
             rect
         });
 
+        resolve_em_values(&arena.node_layout, &mut display_rect_arena);
+
         Self {
             ui_descr: ui_description,
             rectangles: display_rect_arena,
@@ -227,7 +235,7 @@ impl<'a, T: Layout + 'a> DisplayList<'a, T> {
 ///
 /// will be rendered as: `a,b,e,f,g,c,d`, so that the `c,d` sub-DOM is on top of the rest
 /// of the content. To support this, the content needs to be grouped: Whenever there is a
-/// `position:absolute` encountered, the children are grouped into a new `ContentGroup`:
+/// `position:absolute` or `position:fixed` encountered, the children are grouped into a new `ContentGroup`:
 ///
 /// ```no_run,ignore
 /// Group 1: [a, b, c, e, f, g]
@@ -251,7 +259,8 @@ struct RenderableNodeId {
     /// Whether the (hierarchical) children of this group need to be clipped (usually
     /// because the parent has an `overflow:hidden` property set).
     clip_children: bool,
-    /// Whether the children overflow the parent (see `O`)
+    /// Whether the children can be scrolled by the user, if they overflow the
+    /// parent (`overflow: scroll` or `overflow: auto` in either direction)
     scrolls_children: bool,
     /// The actual node ID of the content
     node_id: NodeId,
@@ -298,7 +307,7 @@ fn determine_rendering_order_inner<'a>(
         root: RenderableNodeId {
             node_id: root_id,
             clip_children: node_needs_to_clip_children(&rectangles[root_id].layout),
-            scrolls_children: false, // TODO
+            scrolls_children: node_needs_to_scroll_children(&rectangles[root_id].layout),
         },
         root_depth,
         node_ids: Vec::new(),
@@ -339,21 +348,19 @@ fn determine_rendering_order_inner<'a>(
                 NodeEdge::Start(node_id) => {
                     let rect_node = &rectangles[node_id];
                     let position = rect_node.layout.position.unwrap_or_default();
-                    if position == LayoutPosition::Absolute {
+                    if position.is_out_of_flow() {
                         // For now, ignore the node and put it aside for later
+                        // (`position: fixed` nodes end up outside of all scroll frames this way)
                         absolute_node_ids.push((depth, node_id));
                         // Skip this sub-tree and go straight to the next sibling
                         // Since the tree is positioned absolute, we'll worry about it later
                         current_node_edge = NodeEdge::End(node_id);
                         should_continue_loop = false;
                     } else {
-                        // TODO: Overflow hidden in horizontal / vertical direction
-                        let node_is_overflow_hidden = node_needs_to_clip_children(&rect_node.layout);
-                        let node_needs_to_scroll_children = false; // TODO
                         root_group.node_ids.push(RenderableNodeId {
                             node_id,
-                            clip_children: node_is_overflow_hidden,
-                            scrolls_children: node_needs_to_scroll_children,
+                            clip_children: node_needs_to_clip_children(&rect_node.layout),
+                            scrolls_children: node_needs_to_scroll_children(&rect_node.layout),
                         });
                     }
 
@@ -385,6 +392,13 @@ pub(crate)  struct ScrolledNodes {
     pub(crate) tags_to_node_ids: BTreeMap<ScrollTagId, NodeId>,
 }
 
+impl ScrolledNodes {
+    /// Returns whether any of the overflowing nodes shows a scrollbar
+    pub(crate) fn has_scrollbars(&self) -> bool {
+        self.overflowing_nodes.values().any(|node| node.horizontal_scrollbar.is_some() || node.vertical_scrollbar.is_some())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct OverflowingScrollNode {
     pub(crate) parent_rect: PositionedRectangle,
@@ -392,19 +406,41 @@ pub(crate) struct OverflowingScrollNode {
     pub(crate) parent_external_scroll_id: ExternalScrollId,
     pub(crate) parent_dom_hash: DomHash,
     pub(crate) scroll_tag_id: ScrollTagId,
+    /// Sum of the scroll amounts of all parent scroll frames, necessary to
+    /// translate the cursor position into the coordinate space of the scrollbars
+    pub(crate) parent_scroll_offset: LayoutVector2D,
+    /// Position of the horizontal scrollbar, `None` if the node shows no horizontal scrollbar
+    pub(crate) horizontal_scrollbar: Option<ScrollbarGeometry>,
+    /// Position of the vertical scrollbar, `None` if the node shows no vertical scrollbar
+    pub(crate) vertical_scrollbar: Option<ScrollbarGeometry>,
+}
+
+/// Solved position of a scrollbar, necessary for rendering and for dragging the thumb
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct ScrollbarGeometry {
+    /// The whole scrollbar, including the padding of the track
+    pub(crate) track: LayoutRect,
+    /// The thumb, positioned according to the current scroll amount
+    pub(crate) thumb: LayoutRect,
+    /// How many pixels the content is scrolled when the thumb is moved by one pixel
+    pub(crate) scroll_per_thumb_px: f32,
+    /// Visible size of the content along the scrollbar, clicking the track scrolls by one page
+    pub(crate) page_size: f32,
 }
 
+/// Minimum length of the scrollbar thumb in pixels, so that it can still be grabbed
+/// when the content is very large
+const MIN_SCROLLBAR_THUMB_LENGTH: f32 = 20.0;
+
 /// Returns all node IDs where the children overflow the parent, together with the
 /// `(parent_rect, child_rect)` - the child rect is the sum of the children.
+/// Nodes with `overflow: visible` in both directions are skipped, since they neither
+/// clip nor scroll their children.
 ///
 /// TODO: The performance of this function can be theoretically improved:
 ///
 /// - Unioning the rectangles is heavier than just looping through the children and
 /// summing up their width / height / padding + margin.
-/// - Scroll nodes only need to be inserted if the parent doesn't have `overflow: hidden`
-/// activated
-/// - Overflow for X and Y needs to be tracked seperately (for overflow-x / overflow-y separation),
-/// so there we'd need to track in which direction the inner_rect is overflowing.
 fn get_nodes_that_need_scroll_clip<'a, T: 'a + Layout>(
     node_hierarchy: &NodeHierarchy,
     display_list_rects: &NodeDataContainer<DisplayRectangle<'a>>,
@@ -419,6 +455,10 @@ fn get_nodes_that_need_scroll_clip<'a, T: 'a + Layout>(
 
     for (_, parent) in parents {
 
+        if !node_needs_to_clip_children(&display_list_rects[*parent].layout) {
+            continue;
+        }
+
         let mut children_sum_rect = None;
 
         for child in parent.children(&node_hierarchy) {
@@ -433,7 +473,7 @@ fn get_nodes_that_need_scroll_clip<'a, T: 'a + Layout>(
 
         let parent_rect = layouted_rects.get(*parent).unwrap();
 
-        if children_sum_rect.contains_rect(&parent_rect.bounds) {
+        if parent_rect.bounds.contains_rect(&children_sum_rect) {
             continue;
         }
 
@@ -456,6 +496,10 @@ fn get_nodes_that_need_scroll_clip<'a, T: 'a + Layout>(
             parent_external_scroll_id,
             parent_dom_hash,
             scroll_tag_id,
+            // Filled out by `push_child_clip` once the scroll amounts are known
+            parent_scroll_offset: LayoutVector2D::zero(),
+            horizontal_scrollbar: None,
+            vertical_scrollbar: None,
         });
     }
 
@@ -468,6 +512,12 @@ fn node_needs_to_clip_children(layout: &RectLayout) -> bool {
     !overflow.is_vertical_overflow_visible()
 }
 
+fn node_needs_to_scroll_children(layout: &RectLayout) -> bool {
+    let overflow = layout.overflow.unwrap_or_default();
+    overflow.allows_horizontal_scrolling() ||
+    overflow.allows_vertical_scrolling()
+}
+
 #[test]
 fn test_overflow_parsing() {
 
@@ -496,6 +546,18 @@ fn test_overflow_parsing() {
         .. Default::default()
     };
     assert_eq!(node_needs_to_clip_children(&layout3), true);
+    // overflow: hidden only clips, the user can't scroll the content
+    assert_eq!(node_needs_to_scroll_children(&layout3), false);
+
+    let layout4 = RectLayout {
+        overflow: Some(LayoutOverflow {
+            horizontal: Some(Overflow::Hidden),
+            vertical: Some(Overflow::Scroll),
+        }),
+        .. Default::default()
+    };
+    assert_eq!(node_needs_to_scroll_children(&layout4), true);
+    assert_eq!(node_needs_to_scroll_children(&layout1), true);
 }
 
 fn push_rectangles_into_displaylist<'a, 'b, 'c, 'd, 'e, 'f, T: Layout>(
@@ -507,82 +569,799 @@ fn push_rectangles_into_displaylist<'a, 'b, 'c, 'd, 'e, 'f, T: Layout>(
     referenced_content: &DisplayListParametersRef<'a,'b,'c,'d,'e, T>,
     referenced_mutable_content: &mut DisplayListParametersMut<'f, T>)
 {
-    let mut clip_stack = Vec::new();
+    // Stacking contexts and clips that are currently pushed, in the order they were pushed
+    let mut node_stack = Vec::new();
+    // Clips and scroll frames of the children of each node, see `push_ancestor_clips`
+    let mut child_clip_ids = BTreeMap::new();
+    // Nodes that are already drawn and the clip they are drawn in, see `push_backdrop_filter`
+    let mut drawn_nodes = Vec::new();
 
     for content_group in content_grouped_rectangles.groups {
-        let rectangle = DisplayListRectParams {
-            epoch,
-            rect_idx: content_group.root.node_id,
-            html_node: &referenced_content.node_data[content_group.root.node_id].node_type,
-            window_size,
-        };
 
-        // Push the root of the node
-        push_rectangles_into_displaylist_inner(
-            content_group.root,
-            scrollable_nodes,
-            &rectangle,
+        push_ancestor_clips(
+            content_group.root.node_id,
+            &child_clip_ids,
+            &mut node_stack,
             referenced_content,
-            referenced_mutable_content,
-            &mut clip_stack
+            referenced_mutable_content.builder,
         );
 
-        for item in content_group.node_ids {
+        for item in Some(content_group.root).into_iter().chain(content_group.node_ids.into_iter()) {
 
             let rectangle = DisplayListRectParams {
                 epoch,
                 rect_idx: item.node_id,
                 html_node: &referenced_content.node_data[item.node_id].node_type,
                 window_size,
+                is_backdrop_copy: false,
             };
 
             push_rectangles_into_displaylist_inner(
                 item,
                 scrollable_nodes,
+                scroll_states,
                 &rectangle,
                 referenced_content,
                 referenced_mutable_content,
-                &mut clip_stack
+                &mut node_stack,
+                &mut child_clip_ids,
+                &mut drawn_nodes,
+            );
+        }
+
+        // Content groups are drawn on top of each other, a
+        // filter or clip never spans multiple groups
+        pop_node_stack(
+            None,
+            &mut node_stack,
+            scrollable_nodes,
+            referenced_content,
+            referenced_mutable_content,
+        );
+    }
+}
+
+/// A stacking context or clip that was pushed for a node, which has
+/// to be popped again once all children of the node are drawn (or the
+/// outline of the node, which is drawn once all children are drawn)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum PushedNodeItem {
+    /// Stacking context with the `filter` of the node
+    Filter(NodeId),
+    /// Clip or scroll frame that clips the children of the node
+    ChildClip(NodeId),
+    /// Clip or scroll frame of the children of the node, pushed again for
+    /// an absolutely positioned descendant, see `push_ancestor_clips`
+    AncestorClip(NodeId),
+    /// Sticky frame of a `position: sticky` node
+    StickyFrame(NodeId, ClipId),
+    /// The `outline` of the node, which is drawn on top of its children
+    Outline(NodeId),
+}
+
+impl PushedNodeItem {
+    fn node_id(&self) -> NodeId {
+        match self {
+            PushedNodeItem::Filter(id) |
+            PushedNodeItem::ChildClip(id) |
+            PushedNodeItem::AncestorClip(id) |
+            PushedNodeItem::StickyFrame(id, _) |
+            PushedNodeItem::Outline(id) => *id,
+        }
+    }
+}
+
+/// If the node has `position: sticky`, pushes a sticky frame, so that the node and its children
+/// stay pinned to the `top` / `bottom` / `left` / `right` edge of the enclosing scroll frame
+/// while it scrolls. The node never moves out of the rect of its parent, so a list header
+/// gets pushed away by the next section.
+fn push_sticky_frame<'a,'b,'c,'d,'e, T: Layout>(
+    node_id: NodeId,
+    node_stack: &mut Vec<PushedNodeItem>,
+    scrollable_nodes: &ScrolledNodes,
+    referenced_content: &DisplayListParametersRef<'a,'b,'c,'d,'e, T>,
+    builder: &mut DisplayListBuilder,
+) {
+    use euclid::SideOffsets2D;
+    use webrender::api::StickyOffsetBounds;
+
+    let layout = &referenced_content.display_rectangle_arena[node_id].layout;
+    if layout.position != Some(LayoutPosition::Sticky) {
+        return;
+    }
+
+    let frame_rect = referenced_content.layout_result.rects[node_id].bounds;
+
+    // If the parent scrolls its children, the sticky node may move
+    // within the whole scrollable content of the parent
+    let containing_rect = match referenced_content.node_hierarchy[node_id].parent {
+        Some(parent_id) => {
+            let parent_bounds = referenced_content.layout_result.rects[parent_id].bounds;
+            match scrollable_nodes.overflowing_nodes.get(&parent_id) {
+                Some(scroll_node) => parent_bounds.union(&scroll_node.child_rect),
+                None => parent_bounds,
+            }
+        },
+        None => frame_rect,
+    };
+
+    let margins = SideOffsets2D::new(
+        layout.top.map(|top| top.0.to_pixels()),
+        layout.right.map(|right| right.0.to_pixels()),
+        layout.bottom.map(|bottom| bottom.0.to_pixels()),
+        layout.left.map(|left| left.0.to_pixels()),
+    );
+
+    let vertical_offset_bounds = StickyOffsetBounds::new(
+        containing_rect.min_y() - frame_rect.min_y(),
+        containing_rect.max_y() - frame_rect.max_y(),
+    );
+    let horizontal_offset_bounds = StickyOffsetBounds::new(
+        containing_rect.min_x() - frame_rect.min_x(),
+        containing_rect.max_x() - frame_rect.max_x(),
+    );
+
+    let sticky_frame_id = builder.define_sticky_frame(
+        frame_rect,
+        margins,
+        vertical_offset_bounds,
+        horizontal_offset_bounds,
+        LayoutVector2D::zero(),
+    );
+
+    builder.push_clip_id(sticky_frame_id);
+    node_stack.push(PushedNodeItem::StickyFrame(node_id, sticky_frame_id));
+}
+
+/// If the node has a `filter`, pushes a stacking context with the filters, so that
+/// the filters are applied to the node and all of its children
+fn push_filter_stacking_context<'a,'b,'c,'d,'e, T: Layout>(
+    node_id: NodeId,
+    node_stack: &mut Vec<PushedNodeItem>,
+    referenced_content: &DisplayListParametersRef<'a,'b,'c,'d,'e, T>,
+    builder: &mut DisplayListBuilder,
+) {
+    use webrender::api::{TransformStyle, MixBlendMode, GlyphRasterSpace};
+    use css::webrender_translate::wr_translate_filter_function;
+
+    let filter = match &referenced_content.display_rectangle_arena[node_id].style.filter {
+        Some(filter) if !filter.0.is_empty() => filter,
+        _ => return,
+    };
+
+    let filters = filter.0.iter().map(|f| wr_translate_filter_function(*f)).collect();
+    let bounds = referenced_content.layout_result.rects[node_id].bounds;
+
+    builder.push_stacking_context(
+        &LayoutPrimitiveInfo::new(get_stacking_context_rect(bounds)),
+        None,
+        TransformStyle::Flat,
+        MixBlendMode::Normal,
+        filters,
+        GlyphRasterSpace::Screen,
+    );
+
+    node_stack.push(PushedNodeItem::Filter(node_id));
+}
+
+/// All rectangles are positioned relative to the window, so a stacking
+/// context has to start at the origin, otherwise it would offset its children
+fn get_stacking_context_rect(bounds: LayoutRect) -> LayoutRect {
+    LayoutRect::new(
+        LayoutPoint::zero(),
+        LayoutSize::new(bounds.origin.x + bounds.size.width, bounds.origin.y + bounds.size.height),
+    )
+}
+
+/// If the node has a `backdrop-filter`, draws the nodes that are already drawn (the content
+/// behind the node) again in a stacking context with the backdrop filters, which is clipped
+/// to the border box of the node. WebRender can't read back what is already drawn,
+/// so the backdrop has to be drawn twice.
+///
+/// The copies are drawn in the same clips and scroll frames as the original nodes, so
+/// they scroll with them. They are not hit-testable, the filters, scrollbars and outlines of
+/// their ancestors are not repeated and OpenGL textures and iframes are left out, since
+/// drawing them again would call their callbacks again.
+fn push_backdrop_filter<'a,'b,'c,'d,'e,'f, T: Layout>(
+    node_id: NodeId,
+    drawn_nodes: &[(NodeId, ClipId)],
+    epoch: Epoch,
+    window_size: WindowSize,
+    scrollable_nodes: &mut ScrolledNodes,
+    referenced_content: &DisplayListParametersRef<'a,'b,'c,'d,'e, T>,
+    referenced_mutable_content: &mut DisplayListParametersMut<'f, T>,
+) {
+    use webrender::api::{TransformStyle, MixBlendMode, GlyphRasterSpace};
+    use css::webrender_translate::wr_translate_filter_function;
+
+    let rect = &referenced_content.display_rectangle_arena[node_id];
+    let backdrop_filter = match &rect.style.backdrop_filter {
+        Some(filter) if !filter.0.is_empty() => filter,
+        _ => return,
+    };
+
+    let filters = backdrop_filter.0.iter().map(|f| wr_translate_filter_function(*f)).collect();
+    let bounds = referenced_content.layout_result.rects[node_id].bounds;
+
+    // Clips the filtered backdrop (not the copies themselves, so that a blur
+    // at the edge of the node still picks up the content around the node)
+    let clip_id = referenced_mutable_content.builder.define_clip(bounds, get_clip_region(bounds, rect).into_iter().collect(), None);
+
+    referenced_mutable_content.builder.push_stacking_context(
+        &LayoutPrimitiveInfo::new(get_stacking_context_rect(bounds)),
+        Some(clip_id),
+        TransformStyle::Flat,
+        MixBlendMode::Normal,
+        filters,
+        GlyphRasterSpace::Screen,
+    );
+
+    for (drawn_node_id, drawn_clip_id) in drawn_nodes {
+
+        let html_node = &referenced_content.node_data[*drawn_node_id].node_type;
+        match html_node {
+            GlTexture(_) | IFrame(_) => continue,
+            _ => { },
+        }
+
+        let rectangle = DisplayListRectParams {
+            epoch,
+            rect_idx: *drawn_node_id,
+            html_node,
+            window_size,
+            is_backdrop_copy: true,
+        };
+
+        referenced_mutable_content.builder.push_clip_id(*drawn_clip_id);
+        displaylist_handle_rect(scrollable_nodes, &rectangle, referenced_content, referenced_mutable_content);
+        referenced_mutable_content.builder.pop_clip_id();
+    }
+
+    referenced_mutable_content.builder.pop_stacking_context();
+}
+
+/// Returns the clip that the next node is drawn in: the innermost clip, scroll frame
+/// or sticky frame on the node stack, or the root scroll frame if nothing is clipped
+fn get_current_clip_id(
+    node_stack: &[PushedNodeItem],
+    child_clip_ids: &BTreeMap<NodeId, ClipId>,
+    pipeline_id: PipelineId,
+) -> ClipId {
+    node_stack.iter().rev().filter_map(|pushed_item| match pushed_item {
+        PushedNodeItem::ChildClip(id) |
+        PushedNodeItem::AncestorClip(id) => child_clip_ids.get(id).cloned(),
+        PushedNodeItem::StickyFrame(_, clip_id) => Some(*clip_id),
+        PushedNodeItem::Filter(_) | PushedNodeItem::Outline(_) => None,
+    }).next().unwrap_or_else(|| ClipId::root_scroll_node(pipeline_id))
+}
+
+#[test]
+fn test_get_current_clip_id() {
+    let pipeline_id = PipelineId(0, 0);
+    let root = ClipId::root_scroll_node(pipeline_id);
+    let sticky = ClipId::root_reference_frame(pipeline_id);
+    let scroll_frame = ClipId::root_scroll_node(PipelineId(1, 0));
+
+    let mut child_clip_ids = BTreeMap::new();
+    child_clip_ids.insert(NodeId::new(1), scroll_frame);
+
+    let mut node_stack = vec![PushedNodeItem::Filter(NodeId::new(0))];
+    assert_eq!(get_current_clip_id(&node_stack, &child_clip_ids, pipeline_id), root);
+
+    node_stack.push(PushedNodeItem::ChildClip(NodeId::new(1)));
+    node_stack.push(PushedNodeItem::Outline(NodeId::new(2)));
+    assert_eq!(get_current_clip_id(&node_stack, &child_clip_ids, pipeline_id), scroll_frame);
+
+    node_stack.push(PushedNodeItem::StickyFrame(NodeId::new(3), sticky));
+    assert_eq!(get_current_clip_id(&node_stack, &child_clip_ids, pipeline_id), sticky);
+}
+
+/// Pops the stacking contexts and clips of all nodes that `next_node` is not a descendant
+/// of (or everything if `next_node` is `None`). The scrollbars of a node are drawn once its
+/// children are clipped, so that they don't scroll with the content, the outline of a node
+/// is drawn on top of its children.
+fn pop_node_stack<'a,'b,'c,'d,'e,'f, T: Layout>(
+    next_node: Option<NodeId>,
+    node_stack: &mut Vec<PushedNodeItem>,
+    scrollable_nodes: &ScrolledNodes,
+    referenced_content: &DisplayListParametersRef<'a,'b,'c,'d,'e, T>,
+    referenced_mutable_content: &mut DisplayListParametersMut<'f, T>,
+) {
+    while let Some(pushed_item) = node_stack.last().cloned() {
+        let pushed_node = pushed_item.node_id();
+        let is_descendant = next_node.map(|next| next.ancestors(referenced_content.node_hierarchy).any(|ancestor| ancestor == pushed_node));
+        if is_descendant == Some(true) {
+            break;
+        }
+        node_stack.pop();
+
+        match pushed_item {
+            PushedNodeItem::Filter(_) => {
+                referenced_mutable_content.builder.pop_stacking_context();
+            },
+            PushedNodeItem::ChildClip(_) => {
+                referenced_mutable_content.builder.pop_clip_id();
+                push_scrollbars(pushed_node, scrollable_nodes, referenced_content, referenced_mutable_content);
+            },
+            PushedNodeItem::AncestorClip(_) |
+            PushedNodeItem::StickyFrame(..) => {
+                referenced_mutable_content.builder.pop_clip_id();
+            },
+            PushedNodeItem::Outline(_) => {
+                let style = &referenced_content.display_rectangle_arena[pushed_node].style;
+                if let Some(ref outline) = style.outline {
+                    let bounds = referenced_content.layout_result.rects[pushed_node].bounds;
+                    push_outline(referenced_mutable_content.builder, outline, style, &bounds);
+                }
+            },
+        }
+    }
+}
+
+/// If the children of the node overflow and the node doesn't have `overflow: visible`,
+/// pushes a clip (for `overflow: hidden`) or a scroll frame (for `overflow: scroll | auto`)
+/// for the children. Also solves the position of the scrollbars, which depends on the
+/// current scroll amount.
+fn push_child_clip<'a,'b,'c,'d,'e,'f, T: Layout>(
+    item: RenderableNodeId,
+    node_stack: &mut Vec<PushedNodeItem>,
+    scrollable_nodes: &mut ScrolledNodes,
+    scroll_states: &mut ScrollStates,
+    referenced_content: &DisplayListParametersRef<'a,'b,'c,'d,'e, T>,
+    referenced_mutable_content: &mut DisplayListParametersMut<'f, T>,
+    child_clip_ids: &mut BTreeMap<NodeId, ClipId>,
+) {
+    use webrender::api::ScrollSensitivity;
+
+    if !item.clip_children {
+        return;
+    }
+
+    let node_id = item.node_id;
+
+    // Content of nested scroll frames is offset by the scroll amount of all parent scroll frames
+    let parent_scroll_offset = node_stack.iter().filter_map(|pushed_item| match pushed_item {
+        PushedNodeItem::ChildClip(parent) |
+        PushedNodeItem::AncestorClip(parent) => scrollable_nodes.overflowing_nodes.get(parent),
+        PushedNodeItem::Filter(_) | PushedNodeItem::StickyFrame(..) | PushedNodeItem::Outline(_) => None,
+    }).filter_map(|parent| scroll_states.get_scroll_amount(&parent.parent_external_scroll_id))
+    .fold(LayoutVector2D::zero(), |offset, (x, y)| offset + LayoutVector2D::new(x, y));
+
+    let scroll_node = match scrollable_nodes.overflowing_nodes.get_mut(&node_id) {
+        Some(s) => s,
+        None => return, // children don't overflow, no need to clip
+    };
+
+    let rect = &referenced_content.display_rectangle_arena[node_id];
+    let overflow = rect.layout.overflow.unwrap_or_default();
+    let bounds = scroll_node.parent_rect.bounds;
+    let content = scroll_node.child_rect;
+
+    let content_width = content.max_x() - bounds.origin.x;
+    let content_height = content.max_y() - bounds.origin.y;
+
+    let show_horizontal = overflow.needs_horizontal_scrollbar(content_width > bounds.size.width);
+    let show_vertical = overflow.needs_vertical_scrollbar(content_height > bounds.size.height);
+
+    let horizontal_style = rect.style.get_horizontal_scrollbar_style();
+    let vertical_style = rect.style.get_vertical_scrollbar_style();
+    let horizontal_thickness = if show_horizontal { horizontal_style.width.0.to_pixels() } else { 0.0 };
+    let vertical_thickness = if show_vertical { vertical_style.width.0.to_pixels() } else { 0.0 };
+
+    // The scrollbars cover the right / bottom edge of the node
+    let visible_width = (bounds.size.width - vertical_thickness).max(0.0);
+    let visible_height = (bounds.size.height - horizontal_thickness).max(0.0);
+
+    // `overflow: hidden` clips the content, but the user can't scroll it
+    let overflow_x = if overflow.allows_horizontal_scrolling() { (content_width - visible_width).max(0.0) } else { 0.0 };
+    let overflow_y = if overflow.allows_vertical_scrolling() { (content_height - visible_height).max(0.0) } else { 0.0 };
+
+    let scroll_id = scroll_node.parent_external_scroll_id;
+
+    let (scroll_x, scroll_y) = if item.scrolls_children {
+        scroll_states.ensure_initialized_scroll_state(scroll_id, overflow_x, overflow_y);
+        scroll_states.get_scroll_amount(&scroll_id).unwrap_or((0.0, 0.0))
+    } else {
+        (0.0, 0.0)
+    };
+
+    scroll_node.parent_scroll_offset = parent_scroll_offset;
+
+    scroll_node.horizontal_scrollbar = if show_horizontal {
+        let track = LayoutRect::new(
+            LayoutPoint::new(bounds.origin.x, bounds.max_y() - horizontal_thickness),
+            LayoutSize::new(visible_width, horizontal_thickness),
+        );
+        Some(get_scrollbar_geometry(track, &horizontal_style, ScrollbarAxis::Horizontal, visible_width, overflow_x, scroll_x))
+    } else {
+        None
+    };
+
+    scroll_node.vertical_scrollbar = if show_vertical {
+        let track = LayoutRect::new(
+            LayoutPoint::new(bounds.max_x() - vertical_thickness, bounds.origin.y),
+            LayoutSize::new(vertical_thickness, visible_height),
+        );
+        Some(get_scrollbar_geometry(track, &vertical_style, ScrollbarAxis::Vertical, visible_height, overflow_y, scroll_y))
+    } else {
+        None
+    };
+
+    // In the directions with `overflow: visible`, the children must not be clipped
+    let mut clip_rect = LayoutRect::new(bounds.origin, LayoutSize::new(visible_width, visible_height));
+    if overflow.is_horizontal_overflow_visible() {
+        clip_rect.origin.x = bounds.origin.x.min(content.origin.x);
+        clip_rect.size.width = bounds.max_x().max(content.max_x()) - clip_rect.origin.x;
+    }
+    if overflow.is_vertical_overflow_visible() {
+        clip_rect.origin.y = bounds.origin.y.min(content.origin.y);
+        clip_rect.size.height = bounds.max_y().max(content.max_y()) - clip_rect.origin.y;
+    }
+
+    // Children are also clipped to the rounded corners of the node
+    let complex_clips = get_clip_region(bounds, rect).into_iter().collect::<Vec<_>>();
+
+    let clip_id = if item.scrolls_children {
+        let content_rect = LayoutRect::new(
+            clip_rect.origin,
+            LayoutSize::new(clip_rect.size.width + overflow_x, clip_rect.size.height + overflow_y),
+        );
+        referenced_mutable_content.builder.define_scroll_frame(
+            Some(scroll_id),
+            content_rect,
+            clip_rect,
+            complex_clips,
+            None,
+            ScrollSensitivity::ScriptAndInputEvents,
+        )
+    } else {
+        referenced_mutable_content.builder.define_clip(clip_rect, complex_clips, None)
+    };
+
+    referenced_mutable_content.builder.push_clip_id(clip_id);
+    node_stack.push(PushedNodeItem::ChildClip(node_id));
+    child_clip_ids.insert(node_id, clip_id);
+}
+
+/// `position: absolute` nodes are drawn in their own content group after the other nodes, when
+/// the clips and scroll frames of their ancestors are already popped. Pushes the clips of the
+/// ancestors returned by `get_clipping_ancestors` again, so that the node is clipped by and
+/// scrolls with the scroll frames it is positioned in.
+fn push_ancestor_clips<'a,'b,'c,'d,'e, T: Layout>(
+    node_id: NodeId,
+    child_clip_ids: &BTreeMap<NodeId, ClipId>,
+    node_stack: &mut Vec<PushedNodeItem>,
+    referenced_content: &DisplayListParametersRef<'a,'b,'c,'d,'e, T>,
+    builder: &mut DisplayListBuilder,
+) {
+    let clipping_ancestors = get_clipping_ancestors(
+        node_id,
+        referenced_content.node_hierarchy,
+        referenced_content.display_rectangle_arena,
+    );
+
+    for ancestor in clipping_ancestors {
+        if let Some(clip_id) = child_clip_ids.get(&ancestor) {
+            builder.push_clip_id(*clip_id);
+            node_stack.push(PushedNodeItem::AncestorClip(ancestor));
+        }
+    }
+}
+
+/// Returns the ancestors whose clips and scroll frames apply to a `position: absolute` node,
+/// outermost first: its containing block (the nearest positioned ancestor or the root node)
+/// and the ancestors of the containing block. Like in browsers, ancestors between the node and
+/// its containing block don't clip it and `position: fixed` nodes escape all scroll frames.
+fn get_clipping_ancestors<'a>(
+    node_id: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    rectangles: &NodeDataContainer<DisplayRectangle<'a>>,
+) -> Vec<NodeId> {
+
+    let mut clipping_ancestors = Vec::new();
+
+    if rectangles[node_id].layout.position != Some(LayoutPosition::Absolute) {
+        return clipping_ancestors;
+    }
+
+    let mut reached_containing_block = false;
+    let mut root_id = node_id;
+
+    for ancestor in node_id.ancestors(node_hierarchy).skip(1) {
+        let position = rectangles[ancestor].layout.position.unwrap_or_default();
+        reached_containing_block = reached_containing_block || position != LayoutPosition::Static;
+        if reached_containing_block {
+            clipping_ancestors.push(ancestor);
+        }
+        // The ancestors of a `position: fixed` node don't clip its children
+        if position == LayoutPosition::Fixed {
+            break;
+        }
+        root_id = ancestor;
+    }
+
+    if !reached_containing_block && root_id != node_id {
+        clipping_ancestors.push(root_id);
+    }
+
+    clipping_ancestors.reverse();
+    clipping_ancestors
+}
+
+#[test]
+fn test_get_clipping_ancestors() {
+
+    use id_tree::Node;
+
+    // 0 -> 1 -> 2 -> 3 -> 4
+    let node_hierarchy = NodeHierarchy::new((0..5).map(|i| Node {
+        parent: if i == 0 { None } else { Some(NodeId::new(i - 1)) },
+        previous_sibling: None,
+        next_sibling: None,
+        first_child: if i == 4 { None } else { Some(NodeId::new(i + 1)) },
+        last_child: if i == 4 { None } else { Some(NodeId::new(i + 1)) },
+    }).collect());
+
+    let styled_node = StyledNode::default();
+    let get_rectangles = |positions: &[(usize, LayoutPosition)]| {
+        let mut rectangles = NodeDataContainer::new((0..5).map(|_| DisplayRectangle::new(None, &styled_node)).collect());
+        for (id, position) in positions {
+            rectangles[NodeId::new(*id)].layout.position = Some(*position);
+        }
+        rectangles
+    };
+    let ids = |ids: &[usize]| ids.iter().map(|id| NodeId::new(*id)).collect::<Vec<_>>();
+
+    // without a positioned ancestor, only the clip of the root node applies
+    let rectangles = get_rectangles(&[(4, LayoutPosition::Absolute)]);
+    assert_eq!(get_clipping_ancestors(NodeId::new(4), &node_hierarchy, &rectangles), ids(&[0]));
+
+    // the clip of node 3 doesn't apply, since node 3 isn't the containing block
+    let rectangles = get_rectangles(&[(2, LayoutPosition::Relative), (4, LayoutPosition::Absolute)]);
+    assert_eq!(get_clipping_ancestors(NodeId::new(4), &node_hierarchy, &rectangles), ids(&[0, 1, 2]));
+
+    // a fixed ancestor escapes the clips of its own ancestors
+    let rectangles = get_rectangles(&[(1, LayoutPosition::Relative), (2, LayoutPosition::Fixed), (4, LayoutPosition::Absolute)]);
+    assert_eq!(get_clipping_ancestors(NodeId::new(4), &node_hierarchy, &rectangles), ids(&[2]));
+
+    // fixed nodes aren't clipped at all
+    let rectangles = get_rectangles(&[(1, LayoutPosition::Relative), (4, LayoutPosition::Fixed)]);
+    assert_eq!(get_clipping_ancestors(NodeId::new(4), &node_hierarchy, &rectangles), ids(&[]));
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum ScrollbarAxis {
+    Horizontal,
+    Vertical,
+}
+
+/// Positions the thumb inside of the track. The padding of the `ScrollbarInfo` is specified
+/// for the vertical scrollbar (`left` / `right` make the thumb thinner, `top` / `bottom` shorten
+/// the movable area) and rotated for the horizontal scrollbar.
+fn get_scrollbar_geometry(
+    track: LayoutRect,
+    style: &ScrollbarInfo,
+    axis: ScrollbarAxis,
+    page_size: f32,
+    overflow: f32,
+    scroll_amount: f32,
+) -> ScrollbarGeometry {
+
+    let padding = |p: Option<azul_css::PixelValue>| p.map(|p| p.to_pixels()).unwrap_or(0.0);
+    let (thickness_before, thickness_after) = (padding(style.padding.left), padding(style.padding.right));
+    let (length_before, length_after) = (padding(style.padding.top), padding(style.padding.bottom));
+
+    let (track_start, track_length) = match axis {
+        ScrollbarAxis::Horizontal => (track.origin.x, track.size.width),
+        ScrollbarAxis::Vertical => (track.origin.y, track.size.height),
+    };
+
+    let movable_start = track_start + length_before;
+    let movable_length = (track_length - length_before - length_after).max(0.0);
+    let content_length = page_size + overflow;
+
+    let thumb_length = if content_length > 0.0 {
+        (movable_length * page_size / content_length).max(MIN_SCROLLBAR_THUMB_LENGTH).min(movable_length)
+    } else {
+        movable_length
+    };
+
+    let thumb_travel = movable_length - thumb_length;
+    let thumb_offset = if overflow > 0.0 { scroll_amount / overflow * thumb_travel } else { 0.0 };
+    let scroll_per_thumb_px = if thumb_travel > 0.0 { overflow / thumb_travel } else { 0.0 };
+
+    let thumb = match axis {
+        ScrollbarAxis::Horizontal => LayoutRect::new(
+            LayoutPoint::new(movable_start + thumb_offset, track.origin.y + thickness_before),
+            LayoutSize::new(thumb_length, (track.size.height - thickness_before - thickness_after).max(0.0)),
+        ),
+        ScrollbarAxis::Vertical => LayoutRect::new(
+            LayoutPoint::new(track.origin.x + thickness_before, movable_start + thumb_offset),
+            LayoutSize::new((track.size.width - thickness_before - thickness_after).max(0.0), thumb_length),
+        ),
+    };
+
+    ScrollbarGeometry { track, thumb, scroll_per_thumb_px, page_size }
+}
+
+#[test]
+fn test_scrollbar_geometry() {
+
+    let style = ScrollbarInfo::default(); // 17px wide, 2px padding left / right
+    let track = LayoutRect::new(LayoutPoint::new(83.0, 0.0), LayoutSize::new(17.0, 100.0));
+
+    // 100px visible, 300px content: the thumb takes up a third of the track
+    let geometry = get_scrollbar_geometry(track, &style, ScrollbarAxis::Vertical, 100.0, 200.0, 0.0);
+    assert_eq!(geometry.thumb, LayoutRect::new(LayoutPoint::new(85.0, 0.0), LayoutSize::new(13.0, 100.0 / 3.0)));
+    assert!((geometry.scroll_per_thumb_px - 3.0).abs() < 0.001);
+
+    // Scrolled to the end, the thumb is at the end of the track
+    let geometry = get_scrollbar_geometry(track, &style, ScrollbarAxis::Vertical, 100.0, 200.0, 200.0);
+    assert!((geometry.thumb.max_y() - 100.0).abs() < 0.001);
+
+    // The thumb doesn't get smaller than the minimum length
+    let geometry = get_scrollbar_geometry(track, &style, ScrollbarAxis::Vertical, 100.0, 100_000.0, 0.0);
+    assert_eq!(geometry.thumb.size.height, MIN_SCROLLBAR_THUMB_LENGTH);
+}
+
+/// Draws the scrollbars of a node, solved by `push_child_clip`
+fn push_scrollbars<'a,'b,'c,'d,'e,'f, T: Layout>(
+    node_id: NodeId,
+    scrollable_nodes: &ScrolledNodes,
+    referenced_content: &DisplayListParametersRef<'a,'b,'c,'d,'e, T>,
+    referenced_mutable_content: &mut DisplayListParametersMut<'f, T>,
+) {
+    let scroll_node = match scrollable_nodes.overflowing_nodes.get(&node_id) {
+        Some(s) => s,
+        None => return,
+    };
+
+    let style = &referenced_content.display_rectangle_arena[node_id].style;
+
+    if let Some(horizontal) = &scroll_node.horizontal_scrollbar {
+        let info = style.get_horizontal_scrollbar_style();
+        push_scrollbar_part(horizontal.track, &info.track, referenced_mutable_content);
+        push_scrollbar_part(horizontal.thumb, &info.thumb, referenced_mutable_content);
+    }
+
+    if let Some(vertical) = &scroll_node.vertical_scrollbar {
+        let info = style.get_vertical_scrollbar_style();
+        push_scrollbar_part(vertical.track, &info.track, referenced_mutable_content);
+        push_scrollbar_part(vertical.thumb, &info.thumb, referenced_mutable_content);
+    }
+
+    // TODO: -webkit-scrollbar-button and -webkit-resizer are parsed, but not drawn yet
+    if let (Some(horizontal), Some(vertical)) = (&scroll_node.horizontal_scrollbar, &scroll_node.vertical_scrollbar) {
+        let corner = LayoutRect::new(
+            LayoutPoint::new(vertical.track.origin.x, horizontal.track.origin.y),
+            LayoutSize::new(vertical.track.size.width, horizontal.track.size.height),
+        );
+        push_scrollbar_part(corner, &style.get_vertical_scrollbar_style().corner, referenced_mutable_content);
+    }
+}
+
+/// Draws the background, background layers and border of one part of a scrollbar
+fn push_scrollbar_part<'f, T: Layout>(
+    bounds: LayoutRect,
+    style: &RectStyle,
+    referenced_mutable_content: &mut DisplayListParametersMut<'f, T>,
+) {
+    use css::webrender_translate::wr_translate_border_radius;
+
+    if bounds.size.width <= 0.0 || bounds.size.height <= 0.0 {
+        return;
+    }
+
+    let info = LayoutPrimitiveInfo::new(bounds);
+
+    let clip_id = style.border_radius.map(|border_radius| {
+        let clip = ComplexClipRegion {
+            rect: bounds,
+            radii: wr_translate_border_radius(border_radius.0).into(),
+            mode: ClipMode::Clip,
+        };
+        referenced_mutable_content.builder.define_clip(bounds, vec![clip], None)
+    });
+
+    if let Some(id) = clip_id {
+        referenced_mutable_content.builder.push_clip_id(id);
+    }
+
+    if let Some(bg_col) = &style.background_color {
+        push_rect(&info, referenced_mutable_content.builder, bg_col);
+    }
+
+    if let Some(bg) = &style.background {
+        for (layer_index, background) in bg.0.iter().enumerate().rev() {
+            push_background(
+                &info,
+                referenced_mutable_content.builder,
+                background,
+                style.background_size.as_ref().and_then(|s| s.get(layer_index)),
+                style.background_repeat.as_ref().and_then(|r| r.get(layer_index)).cloned().unwrap_or_default(),
+                style.background_position.as_ref().and_then(|p| p.get(layer_index)).cloned().unwrap_or_default(),
+                referenced_mutable_content.app_resources,
             );
         }
     }
+
+    if let Some(border) = &style.border {
+        push_border(&info, referenced_mutable_content.builder, border, &style.border_radius);
+    }
+
+    if clip_id.is_some() {
+        referenced_mutable_content.builder.pop_clip_id();
+    }
 }
 
 fn push_rectangles_into_displaylist_inner<'a,'b,'c,'d,'e,'f, T: Layout>(
     item: RenderableNodeId,
     scrollable_nodes: &mut ScrolledNodes,
+    scroll_states: &mut ScrollStates,
     rectangle: &DisplayListRectParams<'a, T>,
     referenced_content: &DisplayListParametersRef<'a,'b,'c,'d,'e, T>,
     referenced_mutable_content: &mut DisplayListParametersMut<'f, T>,
-    clip_stack: &mut Vec<NodeId>,
+    node_stack: &mut Vec<PushedNodeItem>,
+    child_clip_ids: &mut BTreeMap<NodeId, ClipId>,
+    drawn_nodes: &mut Vec<(NodeId, ClipId)>,
 ) {
+    pop_node_stack(
+        Some(item.node_id),
+        node_stack,
+        scrollable_nodes,
+        referenced_content,
+        referenced_mutable_content,
+    );
+
+    push_sticky_frame(
+        item.node_id,
+        node_stack,
+        scrollable_nodes,
+        referenced_content,
+        referenced_mutable_content.builder,
+    );
+
+    push_backdrop_filter(
+        item.node_id,
+        drawn_nodes,
+        rectangle.epoch,
+        rectangle.window_size,
+        scrollable_nodes,
+        referenced_content,
+        referenced_mutable_content,
+    );
+
+    push_filter_stacking_context(
+        item.node_id,
+        node_stack,
+        referenced_content,
+        referenced_mutable_content.builder,
+    );
+
     displaylist_handle_rect(
         scrollable_nodes,
         rectangle,
         referenced_content,
         referenced_mutable_content
     );
-/*
-
-    // NOTE: table demo has problems with clipping
-
-    if item.clip_children {
-        if let Some(last_child) = referenced_content.node_hierarchy[rectangle.rect_idx].last_child {
-            let styled_node = &referenced_content.display_rectangle_arena[rectangle.rect_idx];
-            let solved_rect = &referenced_content.layout_result.rects[rectangle.rect_idx];
-            let clip = get_clip_region(solved_rect.bounds, &styled_node)
-                .unwrap_or(ComplexClipRegion::new(solved_rect.bounds, BorderRadius::zero(), ClipMode::Clip));
-            let clip_id = referenced_mutable_content.builder.define_clip(solved_rect.bounds, vec![clip], /* image_mask: */ None);
-            referenced_mutable_content.builder.push_clip_id(clip_id);
-            clip_stack.push(last_child);
-        }
-    }
 
-    if clip_stack.last().cloned() == Some(rectangle.rect_idx) {
-        referenced_mutable_content.builder.pop_clip_id();
-        clip_stack.pop();
+    drawn_nodes.push((item.node_id, get_current_clip_id(node_stack, child_clip_ids, referenced_content.pipeline_id)));
+
+    // The outline is drawn once all children are drawn and their clip is popped again,
+    // so that it is on top of the children, but not clipped by the node itself
+    if referenced_content.display_rectangle_arena[item.node_id].style.outline.is_some() {
+        node_stack.push(PushedNodeItem::Outline(item.node_id));
     }
-*/
+
+    push_child_clip(
+        item,
+        node_stack,
+        scrollable_nodes,
+        scroll_states,
+        referenced_content,
+        referenced_mutable_content,
+        child_clip_ids,
+    );
 }
 
 /// Parameters that apply to a single rectangle / div node
@@ -592,6 +1371,9 @@ pub(crate) struct DisplayListRectParams<'a, T: 'a + Layout> {
     pub rect_idx: NodeId,
     pub html_node: &'a NodeType<T>,
     window_size: WindowSize,
+    /// Whether the node is drawn again behind a `backdrop-filter` (see `push_backdrop_filter`),
+    /// copies are not hit-testable
+    is_backdrop_copy: bool,
 }
 
 fn get_clip_region<'a>(bounds: LayoutRect, rect: &DisplayRectangle<'a>) -> Option<ComplexClipRegion> {
@@ -620,7 +1402,7 @@ fn displaylist_handle_rect<'a,'b,'c,'d,'e,'f,'g, T: Layout>(
     } = referenced_content;
 
     let DisplayListRectParams {
-        epoch, rect_idx, html_node, window_size,
+        epoch, rect_idx, html_node, window_size, is_backdrop_copy,
     } = rectangle;
 
     let rect = &display_rectangle_arena[*rect_idx];
@@ -630,11 +1412,13 @@ fn displaylist_handle_rect<'a,'b,'c,'d,'e,'f,'g, T: Layout>(
         rect: bounds,
         clip_rect: bounds,
         is_backface_visible: false,
-        tag: rect.tag.map(|tag| (tag, 0)).or({
-            scrollable_nodes.overflowing_nodes
-            .get(&rect_idx)
-            .map(|scrolled| (scrolled.scroll_tag_id.0, 0))
-        }),
+        tag: if *is_backdrop_copy { None } else {
+            rect.tag.map(|tag| (tag, 0)).or({
+                scrollable_nodes.overflowing_nodes
+                .get(&rect_idx)
+                .map(|scrolled| (scrolled.scroll_tag_id.0, 0))
+            })
+        },
     };
 
     let clip_region_id = get_clip_region(bounds, &rect).map(|clip|
@@ -653,15 +1437,24 @@ fn displaylist_handle_rect<'a,'b,'c,'d,'e,'f,'g, T: Layout>(
         referenced_mutable_content.builder.push_clip_id(id);
     }
 
-    // If the rect is hit-testing relevant, we need to push a rect anyway.
-    // Otherwise the hit-testing gets confused
+    // The background color is clipped to the box of the last (bottom-most) background layer
+    let background_layer_count = rect.style.background.as_ref().map(|bg| bg.0.len()).unwrap_or(0).max(1);
+    let background_color_clip = rect.style.background_clip.as_ref()
+        .and_then(|clip| clip.get(background_layer_count - 1).cloned())
+        .unwrap_or_default();
+    let background_color_clip_rect = get_background_box(&bounds, rect, background_color_clip.into());
+
     if let Some(bg_col) = &rect.style.background_color {
         push_rect(
-            &info,
+            &LayoutPrimitiveInfo { clip_rect: background_color_clip_rect, .. info },
             referenced_mutable_content.builder,
             bg_col,
         );
-    } else if info.tag.is_some() {
+    }
+
+    // If the rect is hit-testing relevant, we need to push a rect anyway.
+    // Otherwise the hit-testing gets confused
+    if info.tag.is_some() && (rect.style.background_color.is_none() || background_color_clip_rect != bounds) {
         const TRANSPARENT_BG: StyleBackgroundColor = StyleBackgroundColor(StyleColorU { r: 0, g: 0, b: 0, a: 0 });
         push_rect(
             &info,
@@ -671,15 +1464,19 @@ fn displaylist_handle_rect<'a,'b,'c,'d,'e,'f,'g, T: Layout>(
     }
 
     if let Some(bg) = &rect.style.background {
-        push_background(
-            &info,
-            &bounds,
-            referenced_mutable_content.builder,
-            bg,
-            &rect.style.background_size,
-            &rect.style.background_repeat,
-            &referenced_mutable_content.app_resources,
-        );
+        // The first layer is on top, so the layers have to be drawn in reverse order
+        for (layer_index, background) in bg.0.iter().enumerate().rev() {
+            let layer_info = get_background_layer_info(&bounds, rect, layer_index, window_size);
+            push_background(
+                &layer_info,
+                referenced_mutable_content.builder,
+                background,
+                rect.style.background_size.as_ref().and_then(|s| s.get(layer_index)),
+                rect.style.background_repeat.as_ref().and_then(|r| r.get(layer_index)).cloned().unwrap_or_default(),
+                rect.style.background_position.as_ref().and_then(|p| p.get(layer_index)).cloned().unwrap_or_default(),
+                referenced_mutable_content.app_resources,
+            );
+        }
     }
 
     if let Some(ref border) = rect.style.border {
@@ -729,6 +1526,8 @@ fn displaylist_handle_rect<'a,'b,'c,'d,'e,'f,'g, T: Layout>(
     if clip_region_id.is_some() {
         referenced_mutable_content.builder.pop_clip_id();
     }
+
+    // The outline is pushed in `pop_node_stack`, after the children of the node
 }
 
 fn push_opengl_texture<'a,'b,'c,'d,'e,'f, T: Layout>(
@@ -842,6 +1641,8 @@ fn push_iframe<'a,'b,'c,'d,'e,'f, T: Layout>(
     let hovered_nodes = BTreeMap::new();
 
     let mut ui_state = new_dom.into_ui_state();
+    // The window-wide css variables also apply to the nodes of the iframe
+    ui_state.window_css_overrides = referenced_mutable_content.fake_window.css_variables.clone();
     let ui_description = UiDescription::<T>::match_css_to_dom(
         &mut ui_state,
         &referenced_content.css,
@@ -1066,7 +1867,7 @@ fn push_box_shadow(
 {
     fn push_box_shadow_inner(
         builder: &mut DisplayListBuilder,
-        pre_shadow: &Option<BoxShadowPreDisplayItem>,
+        pre_shadow: &BoxShadowPreDisplayItem,
         border_radius: StyleBorderRadius,
         bounds: &LayoutRect,
         clip_rect: LayoutRect,
@@ -1078,11 +1879,6 @@ fn push_box_shadow(
             wr_translate_box_shadow_clip_mode
         };
 
-        let pre_shadow = match pre_shadow {
-            None => return,
-            Some(ref s) => s,
-        };
-
         // The pre_shadow is missing the StyleBorderRadius & LayoutRect
         if pre_shadow.clip_mode != shadow_type {
             return;
@@ -1133,8 +1929,8 @@ fn push_box_shadow(
             let mut clip_rect = *bounds;
 
             let origin_displace = (pre_shadow.spread_radius.to_pixels() + pre_shadow.blur_radius.to_pixels()) * 2.0;
-            clip_rect.origin.x = clip_rect.origin.x - pre_shadow.offset[0].to_pixels() - origin_displace;
-            clip_rect.origin.y = clip_rect.origin.y - pre_shadow.offset[1].to_pixels() - origin_displace;
+            clip_rect.origin.x = clip_rect.origin.x + pre_shadow.offset[0].to_pixels() - origin_displace;
+            clip_rect.origin.y = clip_rect.origin.y + pre_shadow.offset[1].to_pixels() - origin_displace;
 
             clip_rect.size.height = clip_rect.size.height + (origin_displace * 2.0);
             clip_rect.size.width = clip_rect.size.width + (origin_displace * 2.0);
@@ -1142,72 +1938,58 @@ fn push_box_shadow(
         }
     }
 
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    enum BoxShadowSide {
+        Top,
+        Left,
+        Bottom,
+        Right,
+    }
+
     fn push_single_box_shadow_edge(
             builder: &mut DisplayListBuilder,
             current_shadow: &BoxShadowPreDisplayItem,
             bounds: &LayoutRect,
             border_radius: StyleBorderRadius,
             shadow_type: BoxShadowClipMode,
-            top: &Option<Option<BoxShadowPreDisplayItem>>,
-            bottom: &Option<Option<BoxShadowPreDisplayItem>>,
-            left: &Option<Option<BoxShadowPreDisplayItem>>,
-            right: &Option<Option<BoxShadowPreDisplayItem>>,
+            side: BoxShadowSide,
     ) {
+        use self::BoxShadowSide::*;
+
         let is_inset_shadow = current_shadow.clip_mode == BoxShadowClipMode::Inset;
         let origin_displace = (current_shadow.spread_radius.to_pixels() + current_shadow.blur_radius.to_pixels()) * 2.0;
 
         let mut shadow_bounds = *bounds;
         let mut clip_rect = *bounds;
 
-        if is_inset_shadow {
-            // If the shadow is inset, we adjust the clip rect to be
-            // exactly the amount of the shadow
-            if let Some(Some(top)) = top {
-                clip_rect.size.height = origin_displace;
-                shadow_bounds.size.width += origin_displace;
-                shadow_bounds.origin.x -= origin_displace / 2.0;
-            } else if let Some(Some(bottom)) = bottom {
-                clip_rect.size.height = origin_displace;
-                clip_rect.origin.y += bounds.size.height - origin_displace;
-                shadow_bounds.size.width += origin_displace;
-                shadow_bounds.origin.x -= origin_displace / 2.0;
-            } else if let Some(Some(left)) = left {
-                clip_rect.size.width = origin_displace;
-                shadow_bounds.size.height += origin_displace;
-                shadow_bounds.origin.y -= origin_displace / 2.0;
-            } else if let Some(Some(right)) = right {
-                clip_rect.size.width = origin_displace;
-                clip_rect.origin.x += bounds.size.width - origin_displace;
-                shadow_bounds.size.height += origin_displace;
-                shadow_bounds.origin.y -= origin_displace / 2.0;
-            }
-        } else {
-            if let Some(Some(top)) = top {
-                clip_rect.size.height = origin_displace;
-                clip_rect.origin.y -= origin_displace;
-                shadow_bounds.size.width += origin_displace;
-                shadow_bounds.origin.x -= origin_displace / 2.0;
-            } else if let Some(Some(bottom)) = bottom {
+        match side {
+            Top | Bottom => {
                 clip_rect.size.height = origin_displace;
-                clip_rect.origin.y += bounds.size.height;
                 shadow_bounds.size.width += origin_displace;
                 shadow_bounds.origin.x -= origin_displace / 2.0;
-            } else if let Some(Some(left)) = left {
-                clip_rect.size.width = origin_displace;
-                clip_rect.origin.x -= origin_displace;
-                shadow_bounds.size.height += origin_displace;
-                shadow_bounds.origin.y -= origin_displace / 2.0;
-            } else if let Some(Some(right)) = right {
+            },
+            Left | Right => {
                 clip_rect.size.width = origin_displace;
-                clip_rect.origin.x += bounds.size.width;
                 shadow_bounds.size.height += origin_displace;
                 shadow_bounds.origin.y -= origin_displace / 2.0;
-            }
+            },
+        }
+
+        // If the shadow is inset, the clip rect is exactly the amount of the
+        // shadow inside of the bounds, otherwise it lies just outside of the bounds
+        match (side, is_inset_shadow) {
+            (Top, true) | (Left, true) => { },
+            (Bottom, true) => { clip_rect.origin.y += bounds.size.height - origin_displace; },
+            (Right, true) => { clip_rect.origin.x += bounds.size.width - origin_displace; },
+            (Top, false) => { clip_rect.origin.y -= origin_displace; },
+            (Bottom, false) => { clip_rect.origin.y += bounds.size.height; },
+            (Left, false) => { clip_rect.origin.x -= origin_displace; },
+            (Right, false) => { clip_rect.origin.x += bounds.size.width; },
         }
 
         push_box_shadow_inner(
             builder,
-            &Some(*current_shadow),
+            current_shadow,
             border_radius,
             &shadow_bounds,
             clip_rect,
@@ -1215,7 +1997,7 @@ fn push_box_shadow(
         );
     }
 
-    // Box-shadow can be applied to each corner separately. This means, in practice
+    // Box-shadow can be applied to each side separately. This means, in practice
     // that we simply overlay multiple shadows with shifted clipping rectangles
     let StyleBoxShadow { top, left, bottom, right } = match &style.box_shadow {
         Some(s) => s,
@@ -1223,97 +2005,168 @@ fn push_box_shadow(
     };
     let border_radius = style.border_radius.unwrap_or(StyleBorderRadius::zero());
 
-    enum ShouldPushShadow {
-        PushOneShadow,
-        PushTwoShadows,
-        PushAllShadows,
+    // Shadows are drawn in reverse order, so that the first shadow ends up on top
+    if let (Some(t), Some(l), Some(b), Some(r)) = (top, left, bottom, right) {
+        if t == l && t == b && t == r {
+            // box-shadow: 0px 1px 3px black, 0px 1px 2px gray;
+            for shadow in t.0.iter().rev() {
+                push_box_shadow_inner(
+                    builder,
+                    shadow,
+                    border_radius,
+                    bounds,
+                    get_clip_rect(shadow, bounds),
+                    shadow_type
+                );
+            }
+            return;
+        }
     }
 
-    let what_shadow_to_push = match [top, left, bottom, right].iter().filter(|x| x.is_some()).count() {
-        1 => ShouldPushShadow::PushOneShadow,
-        2 => ShouldPushShadow::PushTwoShadows,
-        4 => ShouldPushShadow::PushAllShadows,
-        _ => return,
-    };
-
-    match what_shadow_to_push {
-        ShouldPushShadow::PushOneShadow => {
-            let current_shadow = match (top, left, bottom, right) {
-                 | (Some(Some(shadow)), None, None, None)
-                 | (None, Some(Some(shadow)), None, None)
-                 | (None, None, Some(Some(shadow)), None)
-                 | (None, None, None, Some(Some(shadow)))
-                 => shadow,
-                 _ => return, // reachable, but invalid box-shadow
-            };
-
+    // box-shadow-top: 0px 0px 5px red;
+    // box-shadow-bottom: 0px 0px 5px blue;
+    let sides = [
+        (top, BoxShadowSide::Top),
+        (left, BoxShadowSide::Left),
+        (bottom, BoxShadowSide::Bottom),
+        (right, BoxShadowSide::Right),
+    ];
+
+    for (shadows, side) in sides.iter() {
+        let shadows = match shadows {
+            Some(s) => s,
+            None => continue,
+        };
+        for shadow in shadows.0.iter().rev() {
             push_single_box_shadow_edge(
-                builder, current_shadow, bounds, border_radius, shadow_type,
-                top, bottom, left, right
-            );
-        },
-        // Two shadows in opposite directions:
-        //
-        // box-shadow-top: 0px 0px 5px red;
-        // box-shadow-bottom: 0px 0px 5px blue;
-        ShouldPushShadow::PushTwoShadows => {
-            match (top, left, bottom, right) {
-
-                // top + bottom box-shadow pair
-                (Some(Some(t)), None, Some(Some(b)), right) => {
-                    push_single_box_shadow_edge(
-                        builder, t, bounds, border_radius, shadow_type,
-                        top, &None, &None, &None
-                    );
-                    push_single_box_shadow_edge(
-                        builder, b, bounds, border_radius, shadow_type,
-                        &None, bottom, &None, &None
-                    );
-                },
-                // left + right box-shadow pair
-                (None, Some(Some(l)), None, Some(Some(r))) => {
-                    push_single_box_shadow_edge(
-                        builder, l, bounds, border_radius, shadow_type,
-                        &None, &None, left, &None
-                    );
-                    push_single_box_shadow_edge(
-                        builder, r, bounds, border_radius, shadow_type,
-                        &None, &None, &None, right
-                    );
-                }
-                _ => return, // reachable, but invalid
-            }
-        },
-        ShouldPushShadow::PushAllShadows => {
-
-            // Assumes that all box shadows are the same, so just use the top shadow
-            let top_shadow = top.unwrap();
-            let clip_rect = top_shadow
-                .as_ref()
-                .map(|top_shadow| get_clip_rect(top_shadow, bounds))
-                .unwrap_or(*bounds);
-
-            push_box_shadow_inner(
-                builder,
-                &top_shadow,
-                border_radius,
-                bounds,
-                clip_rect,
-                shadow_type
+                builder, shadow, bounds, border_radius, shadow_type, *side
             );
         }
     }
 }
 
+/// Box that a background layer is positioned in (`background-origin`) or clipped to (`background-clip`)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum BackgroundBox {
+    BorderBox,
+    PaddingBox,
+    ContentBox,
+}
+
+impl From<StyleBackgroundOrigin> for BackgroundBox {
+    fn from(origin: StyleBackgroundOrigin) -> Self {
+        match origin {
+            StyleBackgroundOrigin::BorderBox => BackgroundBox::BorderBox,
+            StyleBackgroundOrigin::PaddingBox => BackgroundBox::PaddingBox,
+            StyleBackgroundOrigin::ContentBox => BackgroundBox::ContentBox,
+        }
+    }
+}
+
+impl From<StyleBackgroundClip> for BackgroundBox {
+    fn from(clip: StyleBackgroundClip) -> Self {
+        match clip {
+            StyleBackgroundClip::BorderBox => BackgroundBox::BorderBox,
+            StyleBackgroundClip::PaddingBox => BackgroundBox::PaddingBox,
+            StyleBackgroundClip::ContentBox => BackgroundBox::ContentBox,
+        }
+    }
+}
+
+/// Returns the border box (= `bounds`), the padding box or the content box of the rectangle
+fn get_background_box(
+    bounds: &TypedRect<f32, LayoutPixel>,
+    rect: &DisplayRectangle,
+    background_box: BackgroundBox,
+) -> TypedRect<f32, LayoutPixel> {
+
+    if background_box == BackgroundBox::BorderBox {
+        return *bounds;
+    }
+
+    // Border widths are subtracted the same way as the padding
+    let padding_box = match &rect.style.border {
+        Some(border) => subtract_padding(bounds, &LayoutPadding {
+            top: border.top.map(|s| s.border_width),
+            bottom: border.bottom.map(|s| s.border_width),
+            left: border.left.map(|s| s.border_width),
+            right: border.right.map(|s| s.border_width),
+        }),
+        None => *bounds,
+    };
+
+    match (background_box, &rect.layout.padding) {
+        (BackgroundBox::ContentBox, Some(padding)) => subtract_padding(&padding_box, padding),
+        _ => padding_box,
+    }
+}
+
+/// Returns the positioning area (as `rect`) and the clip (as `clip_rect`) of a background layer
+fn get_background_layer_info(
+    bounds: &TypedRect<f32, LayoutPixel>,
+    rect: &DisplayRectangle,
+    layer_index: usize,
+    window_size: &WindowSize,
+) -> LayoutPrimitiveInfo {
+
+    let origin = rect.style.background_origin.as_ref().and_then(|o| o.get(layer_index).cloned()).unwrap_or_default();
+    let clip = rect.style.background_clip.as_ref().and_then(|c| c.get(layer_index).cloned()).unwrap_or_default();
+    let attachment = rect.style.background_attachment.as_ref().and_then(|a| a.get(layer_index).cloned()).unwrap_or_default();
+
+    let positioning_area = match attachment {
+        // Fixed backgrounds are positioned relative to the window
+        StyleBackgroundAttachment::Fixed => TypedRect::new(
+            TypedPoint2D::zero(),
+            TypedSize2D::new(window_size.dimensions.width as f32, window_size.dimensions.height as f32),
+        ),
+        StyleBackgroundAttachment::Scroll | StyleBackgroundAttachment::Local => get_background_box(bounds, rect, origin.into()),
+    };
+
+    LayoutPrimitiveInfo {
+        rect: positioning_area,
+        clip_rect: get_background_box(bounds, rect, clip.into()),
+        is_backface_visible: false,
+        tag: None,
+    }
+}
+
+/// Returns the rectangle of a single background tile, positioned in the positioning area (`info.rect`)
+fn get_background_tile_rect(
+    info: &LayoutPrimitiveInfo,
+    background_position: StyleBackgroundPosition,
+    tile_size: TypedSize2D<f32, LayoutPixel>,
+) -> TypedRect<f32, LayoutPixel> {
+
+    let area = info.rect;
+
+    let x = match background_position.horizontal {
+        BackgroundPositionHorizontal::Left => 0.0,
+        BackgroundPositionHorizontal::Center => (area.size.width - tile_size.width) / 2.0,
+        BackgroundPositionHorizontal::Right => area.size.width - tile_size.width,
+        BackgroundPositionHorizontal::Exact(e) => e.to_pixels(),
+    };
+
+    let y = match background_position.vertical {
+        BackgroundPositionVertical::Top => 0.0,
+        BackgroundPositionVertical::Center => (area.size.height - tile_size.height) / 2.0,
+        BackgroundPositionVertical::Bottom => area.size.height - tile_size.height,
+        BackgroundPositionVertical::Exact(e) => e.to_pixels(),
+    };
+
+    TypedRect::new(TypedPoint2D::new(area.origin.x + x, area.origin.y + y), tile_size)
+}
+
+/// Pushes a single background layer. `info.rect` is the positioning area of the
+/// layer (see `background-origin`), `info.clip_rect` the clip (see `background-clip`).
 #[inline]
 fn push_background(
     info: &PrimitiveInfo<LayoutPixel>,
-    bounds: &TypedRect<f32, LayoutPixel>,
     builder: &mut DisplayListBuilder,
     background: &StyleBackground,
-    background_size: &Option<StyleBackgroundSize>,
-    background_repeat: &Option<StyleBackgroundRepeat>,
-    app_resources: &AppResources)
+    background_size: Option<&StyleBackgroundSize>,
+    background_repeat: StyleBackgroundRepeat,
+    background_position: StyleBackgroundPosition,
+    app_resources: &mut AppResources)
 {
     use azul_css::{Shape, StyleBackground::*};
     use css::webrender_translate::{
@@ -1325,23 +2178,28 @@ fn push_background(
         RadialGradient(gradient) => {
             let stops: Vec<GradientStop> = gradient.stops.iter().map(|gradient_pre|
                 GradientStop {
-                    offset: gradient_pre.offset.unwrap().get(),
+                    offset: gradient_pre.offset.unwrap().get() / 100.0,
                     color: wr_translate_color_u(gradient_pre.color).into(),
                 }).collect();
 
-            let center = bounds.center();
+            // Gradients always cover the whole positioning area
+            let tile = get_background_tile_rect(info, background_position, info.rect.size);
+            let info = get_background_repeat_info(info, tile, background_repeat);
+
+            // Note: the gradient is relative to the tile, not to the positioning area
+            let center = TypedRect::new(TypedPoint2D::zero(), tile.size).center();
 
             // Note: division by 2.0 because it's the radius, not the diameter
             let radius = match gradient.shape {
-                Shape::Ellipse => TypedSize2D::new(bounds.size.width / 2.0, bounds.size.height / 2.0),
+                Shape::Ellipse => TypedSize2D::new(tile.size.width / 2.0, tile.size.height / 2.0),
                 Shape::Circle => {
-                    let largest_bound_size = bounds.size.width.max(bounds.size.height);
+                    let largest_bound_size = tile.size.width.max(tile.size.height);
                     TypedSize2D::new(largest_bound_size / 2.0, largest_bound_size / 2.0)
                 },
             };
 
             let gradient = builder.create_radial_gradient(center, radius, stops, wr_translate_extend_mode(gradient.extend_mode));
-            builder.push_radial_gradient(&info, gradient, bounds.size, LayoutSize::zero());
+            builder.push_radial_gradient(&info, gradient, tile.size, LayoutSize::zero());
         },
         LinearGradient(gradient) => {
 
@@ -1351,7 +2209,11 @@ fn push_background(
                     color: wr_translate_color_u(gradient_pre.color).into(),
                 }).collect();
 
-            let (begin_pt, end_pt) = gradient.direction.to_points(&wr_translate_layout_rect(*bounds));
+            let tile = get_background_tile_rect(info, background_position, info.rect.size);
+            let info = get_background_repeat_info(info, tile, background_repeat);
+
+            let tile_bounds = TypedRect::new(TypedPoint2D::zero(), tile.size);
+            let (begin_pt, end_pt) = gradient.direction.to_points(&wr_translate_layout_rect(tile_bounds));
             let gradient = builder.create_gradient(
                 wr_translate_layout_point(begin_pt),
                 wr_translate_layout_point(end_pt),
@@ -1359,10 +2221,44 @@ fn push_background(
                 wr_translate_extend_mode(gradient.extend_mode),
             );
 
-            builder.push_gradient(&info, gradient, bounds.size, LayoutSize::zero());
+            builder.push_gradient(&info, gradient, tile.size, LayoutSize::zero());
+        },
+        ConicGradient(gradient) => {
+            let tile = get_background_tile_rect(info, background_position, info.rect.size);
+            let info = get_background_repeat_info(info, tile, background_repeat);
+
+            // WebRender has no conic gradients, so the gradient is rasterized
+            // into an image (cached in the AppResources as long as it is in use)
+            let width = (tile.size.width.round() as usize).min(MAX_CONIC_GRADIENT_SIZE);
+            let height = (tile.size.height.round() as usize).min(MAX_CONIC_GRADIENT_SIZE);
+            if width == 0 || height == 0 {
+                return;
+            }
+
+            // The image gets stretched to the tile size, so exact center
+            // positions have to be scaled to the size of the image
+            let scaled_gradient = scale_conic_gradient_center(
+                gradient,
+                width as f32 / tile.size.width,
+                height as f32 / tile.size.height,
+            );
+            let gradient = &*scaled_gradient;
+
+            let image_key = app_resources.get_or_add_conic_gradient_image(gradient, width, height, || {
+                rasterize_conic_gradient(gradient, width, height)
+            });
+
+            builder.push_image(
+                &info,
+                tile.size,
+                LayoutSize::zero(),
+                ImageRendering::Auto,
+                AlphaType::PremultipliedAlpha,
+                image_key,
+                ColorF::WHITE,
+            );
         },
         Image(style_image_id) => {
-            // TODO: background-origin, background-position, background-repeat
             if let Some(image_id) = app_resources.get_css_image_id(&style_image_id.0) {
 
                 let bounds = info.rect;
@@ -1375,48 +2271,225 @@ fn push_background(
                     None => TypedSize2D::new(image_dimensions.0 as f32, image_dimensions.1 as f32),
                 };
 
-                let background_repeat = background_repeat.unwrap_or_default();
-                let background_repeat_info = get_background_repeat_info(&info, background_repeat, size);
+                let tile = get_background_tile_rect(info, background_position, size);
+                let background_repeat_info = get_background_repeat_info(info, tile, background_repeat);
 
                 push_image(&background_repeat_info, builder, app_resources, image_id, size);
             }
         },
         Color(c) => {
-            push_rect(&info, builder, c);
+            push_rect(&LayoutPrimitiveInfo { rect: info.clip_rect, .. *info }, builder, c);
         },
         NoBackground => { },
     }
 }
 
+/// Conic gradient images larger than this are stretched to save memory
+const MAX_CONIC_GRADIENT_SIZE: usize = 2048;
+
+/// Returns the gradient with its exact (pixel) center position multiplied by the scale factors
+fn scale_conic_gradient_center(gradient: &StyleConicGradient, scale_x: f32, scale_y: f32) -> Cow<StyleConicGradient> {
+
+    use azul_css::PixelValue;
+
+    let mut center = gradient.center;
+
+    if let BackgroundPositionHorizontal::Exact(x) = center.horizontal {
+        center.horizontal = BackgroundPositionHorizontal::Exact(PixelValue::px(x.to_pixels() * scale_x));
+    }
+
+    if let BackgroundPositionVertical::Exact(y) = center.vertical {
+        center.vertical = BackgroundPositionVertical::Exact(PixelValue::px(y.to_pixels() * scale_y));
+    }
+
+    if center == gradient.center {
+        Cow::Borrowed(gradient)
+    } else {
+        Cow::Owned(StyleConicGradient { center, .. gradient.clone() })
+    }
+}
+
+#[test]
+fn test_scale_conic_gradient_center() {
+    use azul_css::{PixelValue, FloatValue};
+
+    let gradient = |horizontal, vertical| StyleConicGradient {
+        angle: FloatValue::new(0.0),
+        center: StyleBackgroundPosition { horizontal, vertical },
+        extend_mode: ExtendMode::Clamp,
+        stops: Vec::new(),
+    };
+
+    // `at 3000px 100px` on a 4096px wide tile, rasterized into a 2048px wide image
+    let exact = gradient(
+        BackgroundPositionHorizontal::Exact(PixelValue::px(3000.0)),
+        BackgroundPositionVertical::Exact(PixelValue::px(100.0)),
+    );
+    assert_eq!(*scale_conic_gradient_center(&exact, 0.5, 1.0), gradient(
+        BackgroundPositionHorizontal::Exact(PixelValue::px(1500.0)),
+        BackgroundPositionVertical::Exact(PixelValue::px(100.0)),
+    ));
+
+    // keywords are already relative to the size of the image
+    let keywords = gradient(BackgroundPositionHorizontal::Right, BackgroundPositionVertical::Center);
+    assert_eq!(*scale_conic_gradient_center(&keywords, 0.5, 0.5), keywords);
+}
+
+/// Rasterizes a conic gradient into premultiplied BGRA8 pixels
+pub(crate) fn rasterize_conic_gradient(gradient: &StyleConicGradient, width: usize, height: usize) -> Vec<u8> {
+
+    use std::f32::consts::PI;
+
+    let center_x = match gradient.center.horizontal {
+        BackgroundPositionHorizontal::Left => 0.0,
+        BackgroundPositionHorizontal::Center => width as f32 / 2.0,
+        BackgroundPositionHorizontal::Right => width as f32,
+        BackgroundPositionHorizontal::Exact(e) => e.to_pixels(),
+    };
+
+    let center_y = match gradient.center.vertical {
+        BackgroundPositionVertical::Top => 0.0,
+        BackgroundPositionVertical::Center => height as f32 / 2.0,
+        BackgroundPositionVertical::Bottom => height as f32,
+        BackgroundPositionVertical::Exact(e) => e.to_pixels(),
+    };
+
+    // offsets as fractions of a full turn
+    let stops = gradient.stops.iter()
+        .map(|stop| (stop.offset.map(|o| o.get() / 100.0).unwrap_or(0.0), stop.color))
+        .collect::<Vec<_>>();
+
+    let (first_offset, last_offset) = match (stops.first(), stops.last()) {
+        (Some(first), Some(last)) => (first.0, last.0),
+        _ => return vec![0; width * height * 4],
+    };
+
+    let start_angle = gradient.angle.get() / 360.0;
+    let mut pixels = Vec::with_capacity(width * height * 4);
+
+    for y in 0..height {
+        for x in 0..width {
+            let dx = x as f32 + 0.5 - center_x;
+            let dy = y as f32 + 0.5 - center_y;
+
+            // 0 = top, clockwise
+            let mut t = dx.atan2(-dy) / (2.0 * PI) - start_angle;
+            t -= t.floor();
+
+            if gradient.extend_mode == ExtendMode::Repeat && last_offset > first_offset {
+                let range = last_offset - first_offset;
+                let repeated = (t - first_offset) / range;
+                t = first_offset + (repeated - repeated.floor()) * range;
+            }
+
+            let color = interpolate_gradient_stops(&stops, t);
+            let alpha = color.a as f32 / 255.0;
+            pixels.push((color.b as f32 * alpha).round() as u8);
+            pixels.push((color.g as f32 * alpha).round() as u8);
+            pixels.push((color.r as f32 * alpha).round() as u8);
+            pixels.push(color.a);
+        }
+    }
+
+    pixels
+}
+
+#[test]
+fn test_rasterize_conic_gradient() {
+    use azul_css::{GradientStopPre, PercentageValue, FloatValue};
+
+    let red = StyleColorU { r: 255, g: 0, b: 0, a: 255 };
+    let blue = StyleColorU { r: 0, g: 0, b: 255, a: 255 };
+    let stop = |offset, color| GradientStopPre { offset: Some(PercentageValue::new(offset)), color };
+
+    // right half red, left half blue
+    let gradient = StyleConicGradient {
+        angle: FloatValue::new(0.0),
+        center: StyleBackgroundPosition {
+            horizontal: BackgroundPositionHorizontal::Center,
+            vertical: BackgroundPositionVertical::Center,
+        },
+        extend_mode: ExtendMode::Clamp,
+        stops: vec![stop(0.0, red), stop(50.0, red), stop(50.0, blue), stop(100.0, blue)],
+    };
+
+    let pixels = rasterize_conic_gradient(&gradient, 10, 10);
+    assert_eq!(pixels.len(), 10 * 10 * 4);
+    // BGRA: top left pixel is blue, top right pixel is red
+    assert_eq!(&pixels[0..4], &[255, 0, 0, 255]);
+    assert_eq!(&pixels[(9 * 4)..(10 * 4)], &[0, 0, 255, 255]);
+}
+
+/// Returns the color at `t` (between 0 and 1) of a gradient with sorted stops
+fn interpolate_gradient_stops(stops: &[(f32, StyleColorU)], t: f32) -> StyleColorU {
+
+    let next_stop = match stops.iter().position(|(offset, _)| *offset > t) {
+        Some(0) => return stops[0].1,
+        Some(s) => s,
+        None => return stops[stops.len() - 1].1,
+    };
+
+    let (start_offset, start) = stops[next_stop - 1];
+    let (end_offset, end) = stops[next_stop];
+    let factor = (t - start_offset) / (end_offset - start_offset);
+    let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * factor).round() as u8;
+
+    StyleColorU {
+        r: mix(start.r, end.r),
+        g: mix(start.g, end.g),
+        b: mix(start.b, end.b),
+        a: mix(start.a, end.a),
+    }
+}
+
+/// Returns the area that the tiles of a background layer have to be repeated in.
+///
+/// The returned `rect` starts at a multiple of the tile size from the positioned `tile`,
+/// so that one of the repeated tiles is exactly at the `background-position`.
 fn get_background_repeat_info(
     info: &LayoutPrimitiveInfo,
+    tile: TypedRect<f32, LayoutPixel>,
     background_repeat: StyleBackgroundRepeat,
-    background_size: TypedSize2D<f32, LayoutPixel>,
 ) -> LayoutPrimitiveInfo {
     use azul_css::StyleBackgroundRepeat::*;
-    match background_repeat {
-        NoRepeat => LayoutPrimitiveInfo::with_clip_rect(
-            info.rect,
-            TypedRect::new(
-                info.rect.origin,
-                TypedSize2D::new(background_size.width, background_size.height),
-            ),
-        ),
-        Repeat => *info,
-        RepeatX => LayoutPrimitiveInfo::with_clip_rect(
-            info.rect,
-            TypedRect::new(
-                info.rect.origin,
-                TypedSize2D::new(info.rect.size.width, background_size.height),
-            ),
-        ),
-        RepeatY => LayoutPrimitiveInfo::with_clip_rect(
-            info.rect,
-            TypedRect::new(
-                info.rect.origin,
-                TypedSize2D::new(background_size.width, info.rect.size.height),
-            ),
-        ),
+
+    let clip = info.clip_rect;
+
+    // Extends the tile to cover the range from `start` to `end` on one axis
+    let repeat_axis = |tile_origin: f32, tile_size: f32, start: f32, end: f32| -> (f32, f32) {
+        if tile_size <= 0.0 {
+            return (tile_origin, tile_size);
+        }
+        let tiles_before = ((tile_origin - start) / tile_size).ceil().max(0.0);
+        let origin = tile_origin - tiles_before * tile_size;
+        let tiles = ((end - origin) / tile_size).ceil().max(1.0);
+        (origin, tiles * tile_size)
+    };
+
+    let (repeat_x, repeat_y) = match background_repeat {
+        NoRepeat => (false, false),
+        Repeat => (true, true),
+        RepeatX => (true, false),
+        RepeatY => (false, true),
+    };
+
+    let (x, width) = if repeat_x {
+        repeat_axis(tile.origin.x, tile.size.width, clip.origin.x, clip.origin.x + clip.size.width)
+    } else {
+        (tile.origin.x, tile.size.width)
+    };
+
+    let (y, height) = if repeat_y {
+        repeat_axis(tile.origin.y, tile.size.height, clip.origin.y, clip.origin.y + clip.size.height)
+    } else {
+        (tile.origin.y, tile.size.height)
+    };
+
+    LayoutPrimitiveInfo {
+        rect: TypedRect::new(TypedPoint2D::new(x, y), TypedSize2D::new(width, height)),
+        clip_rect: clip,
+        is_backface_visible: info.is_backface_visible,
+        tag: info.tag,
     }
 }
 
@@ -1484,6 +2557,50 @@ fn push_border(
     }
 }
 
+/// Pushes an `outline` around the border box, the outline doesn't take up any space in the layout.
+///
+/// The outline follows the `border-radius` of the node, its radii grow by the width and offset of the outline.
+fn push_outline(
+    builder: &mut DisplayListBuilder,
+    outline: &StyleOutline,
+    style: &RectStyle,
+    bounds: &LayoutRect)
+{
+    use azul_css::{PixelValue, PixelSize};
+
+    let text_color = style.font_color.unwrap_or(DEFAULT_FONT_COLOR).0;
+    let border_side = match outline.get_border_side(text_color) {
+        Some(s) => s,
+        None => return,
+    };
+
+    let outset = outline.get_outset();
+    let outline_bounds = bounds.inflate(outset, outset);
+    if outline_bounds.size.width <= 0.0 || outline_bounds.size.height <= 0.0 {
+        return;
+    }
+
+    let outline_radius = style.border_radius.map(|radius| {
+        let grow = |size: PixelSize| -> PixelSize {
+            let grow_value = |v: PixelValue| if v.to_pixels() > 0.0 {
+                PixelValue::px((v.to_pixels() + outset).max(0.0))
+            } else {
+                v
+            };
+            PixelSize::new(grow_value(size.width), grow_value(size.height))
+        };
+        let mut radius = radius;
+        radius.0.top_left = grow(radius.0.top_left);
+        radius.0.top_right = grow(radius.0.top_right);
+        radius.0.bottom_left = grow(radius.0.bottom_left);
+        radius.0.bottom_right = grow(radius.0.bottom_right);
+        radius
+    });
+
+    let info = LayoutPrimitiveInfo::new(outline_bounds);
+    push_border(&info, builder, &StyleBorder::all(border_side), &outline_radius);
+}
+
 /// Subtracts the padding from the bounds, returning the new bounds
 ///
 /// Warning: The resulting rectangle may have negative width or height
@@ -1509,37 +2626,293 @@ fn subtract_padding(bounds: &TypedRect<f32, LayoutPixel>, padding: &LayoutPaddin
 fn populate_css_properties(
     rect: &mut DisplayRectangle,
     node_id: NodeId,
-    css_overrides: &BTreeMap<NodeId, FastHashMap<DomString, CssProperty>>
+    css_overrides: &BTreeMap<NodeId, FastHashMap<DomString, CssProperty>>,
+    window_css_overrides: &FastHashMap<DomString, CssProperty>,
 ) {
+    let styled_node = rect.styled_node;
+
+    for property in resolve_css_declarations(&styled_node.css_constraints, node_id, css_overrides, window_css_overrides) {
+        apply_style_property(&mut rect.style, &mut rect.layout, &property);
+    }
+
+    if !styled_node.scrollbar_constraints.is_empty() {
+        let scrollbar_info = get_scrollbar_info(&styled_node.scrollbar_constraints, node_id, css_overrides, window_css_overrides);
+        rect.style.scrollbar = Some(Box::new(scrollbar_info));
+    }
+}
+
+/// Converts the `em` values of all nodes into pixels, starting at the root: The `font-size` of
+/// a node is relative to the computed font size of its parent, all other values are relative to
+/// the computed font size of the node itself. Nodes without a `font-size` inherit the computed
+/// font size of their parent, so that nested `font-size: 1.2em` declarations compound.
+fn resolve_em_values(node_hierarchy: &NodeHierarchy, rectangles: &mut NodeDataContainer<DisplayRectangle>) {
+
+    let mut font_sizes = vec![None; rectangles.len()];
+    let root_id = NodeId::new(0);
+    font_sizes[root_id.index()] = resolve_em_values_of_node(&mut rectangles[root_id], None);
+
+    // Parents are sorted by depth, so the font size of a parent is always computed before its children
+    for (_depth, parent_id) in node_hierarchy.get_parents_sorted_by_depth() {
+        let parent_font_size = font_sizes[parent_id.index()];
+        for child_id in parent_id.children(node_hierarchy) {
+            font_sizes[child_id.index()] = resolve_em_values_of_node(&mut rectangles[child_id], parent_font_size);
+        }
+    }
+}
+
+/// Resolves the `em` values of a single node, returns the computed font size in pixels
+/// (`None` if neither the node nor any of its parents has a `font-size`)
+fn resolve_em_values_of_node(rect: &mut DisplayRectangle, parent_font_size_px: Option<f32>) -> Option<f32> {
+
+    let font_size_px = match rect.style.font_size {
+        Some(font_size) => Some(font_size.0.resolve_em(parent_font_size_px.unwrap_or(EM_HEIGHT)).to_pixels()),
+        None => parent_font_size_px,
+    };
+
+    rect.style.font_size = font_size_px.map(StyleFontSize::px);
+    rect.style.resolve_em_values(font_size_px.unwrap_or(EM_HEIGHT));
+    rect.layout.resolve_em_values(font_size_px.unwrap_or(EM_HEIGHT));
+
+    font_size_px
+}
+
+#[test]
+fn test_resolve_em_values() {
+
+    use azul_css::{LayoutPadding, PixelValue};
+    use id_tree::Node;
+
+    // 0 -> 1 -> 2
+    let node_hierarchy = NodeHierarchy::new(vec![
+        Node { parent: None, previous_sibling: None, next_sibling: None, first_child: Some(NodeId::new(1)), last_child: Some(NodeId::new(1)) },
+        Node { parent: Some(NodeId::new(0)), previous_sibling: None, next_sibling: None, first_child: Some(NodeId::new(2)), last_child: Some(NodeId::new(2)) },
+        Node { parent: Some(NodeId::new(1)), previous_sibling: None, next_sibling: None, first_child: None, last_child: None },
+    ]);
+
+    let styled_node = StyledNode::default();
+    let get_rectangles = || NodeDataContainer::new((0..3).map(|_| DisplayRectangle::new(None, &styled_node)).collect());
+    let mut rectangles = get_rectangles();
+    rectangles[NodeId::new(0)].style.font_size = Some(StyleFontSize::px(10.0));
+    rectangles[NodeId::new(1)].style.font_size = Some(StyleFontSize::em(1.5));
+    rectangles[NodeId::new(2)].style.font_size = Some(StyleFontSize::em(2.0));
+    rectangles[NodeId::new(2)].layout.padding = Some(LayoutPadding::all(PixelValue::em(0.5)));
+
+    resolve_em_values(&node_hierarchy, &mut rectangles);
+
+    // nested `em` font sizes compound, the padding is relative to the font size of the node
+    assert_eq!(rectangles[NodeId::new(1)].style.font_size, Some(StyleFontSize::px(15.0)));
+    assert_eq!(rectangles[NodeId::new(2)].style.font_size, Some(StyleFontSize::px(30.0)));
+    assert_eq!(rectangles[NodeId::new(2)].layout.padding, Some(LayoutPadding::all(PixelValue::px(15.0))));
+
+    // without any font-size, `em` is relative to the default font size
+    let mut rectangles = get_rectangles();
+    rectangles[NodeId::new(2)].layout.padding = Some(LayoutPadding::all(PixelValue::em(1.0)));
+    resolve_em_values(&node_hierarchy, &mut rectangles);
+    assert_eq!(rectangles[NodeId::new(2)].style.font_size, None);
+    assert_eq!(rectangles[NodeId::new(2)].layout.padding, Some(LayoutPadding::all(PixelValue::px(EM_HEIGHT))));
+
+    // `font-size: initial` (resolved to the default font size) ignores the 30px of the parent,
+    // `font-size: inherit` (no font size) takes it
+    let mut rectangles = get_rectangles();
+    rectangles[NodeId::new(1)].style.font_size = Some(StyleFontSize::px(30.0));
+    rectangles[NodeId::new(2)].style.font_size = Some(StyleFontSize::px(EM_HEIGHT));
+    rectangles[NodeId::new(2)].layout.padding = Some(LayoutPadding::all(PixelValue::em(1.0)));
+    resolve_em_values(&node_hierarchy, &mut rectangles);
+    assert_eq!(rectangles[NodeId::new(2)].layout.padding, Some(LayoutPadding::all(PixelValue::px(EM_HEIGHT))));
+
+    rectangles[NodeId::new(2)].style.font_size = None;
+    rectangles[NodeId::new(2)].layout.padding = Some(LayoutPadding::all(PixelValue::em(1.0)));
+    resolve_em_values(&node_hierarchy, &mut rectangles);
+    assert_eq!(rectangles[NodeId::new(2)].style.font_size, Some(StyleFontSize::px(30.0)));
+    assert_eq!(rectangles[NodeId::new(2)].layout.padding, Some(LayoutPadding::all(PixelValue::px(30.0))));
+}
+
+/// Resolves the declarations of a node to the properties that apply in this frame (see
+/// `resolve_css_declaration`) and replaces the placeholder color of `currentColor` declarations
+/// with the text color of the node, which is the last `color` or the default text color.
+///
+/// `currentColor` is resolved here instead of during the cascade, so that it follows
+/// a `color` that is overridden dynamically or for the whole window.
+fn resolve_css_declarations<'a>(
+    declarations: &'a [CssDeclaration],
+    node_id: NodeId,
+    css_overrides: &'a BTreeMap<NodeId, FastHashMap<DomString, CssProperty>>,
+    window_css_overrides: &'a FastHashMap<DomString, CssProperty>,
+) -> Vec<Cow<'a, CssProperty>> {
+
+    let text_color = declarations.iter().rev()
+        .filter_map(|d| resolve_css_declaration(d, node_id, css_overrides, window_css_overrides))
+        .filter_map(|p| match p {
+            CssProperty::TextColor(color) => Some(*color),
+            _ => None,
+        })
+        .next().unwrap_or(DEFAULT_FONT_COLOR).0;
+
+    declarations.iter().filter_map(|declaration| match declaration.without_importance() {
+        CssDeclaration::CurrentColor(property) => property.with_color(text_color).map(Cow::Owned),
+        _ => resolve_css_declaration(declaration, node_id, css_overrides, window_css_overrides).map(Cow::Borrowed),
+    }).collect()
+}
+
+#[test]
+fn test_resolve_css_declarations_current_color() {
+    use azul_css::{DynamicCssProperty, CssPropertyType};
+
+    let red = StyleColorU { r: 255, g: 0, b: 0, a: 255 };
+    let blue = StyleColorU { r: 0, g: 0, b: 255, a: 255 };
+    let placeholder = StyleColorU { r: 0, g: 0, b: 0, a: 255 };
+    let background = |color| CssProperty::BackgroundColor(StyleBackgroundColor(color));
+    let text_color = |color| CssProperty::TextColor(StyleTextColor(color));
+
+    fn resolve(declarations: &[CssDeclaration], node_overrides: &BTreeMap<NodeId, FastHashMap<DomString, CssProperty>>) -> Vec<CssProperty> {
+        resolve_css_declarations(declarations, NodeId::new(0), node_overrides, &FastHashMap::default())
+        .into_iter().map(Cow::into_owned).collect()
+    }
+
+    let mut node_overrides = BTreeMap::new();
+
+    // `currentColor` uses the text color, even if the `color` is declared afterwards
+    let declarations = vec![
+        CssDeclaration::CurrentColor(background(placeholder)),
+        CssDeclaration::Static(text_color(red)),
+    ];
+    assert_eq!(resolve(&declarations, &node_overrides), vec![background(red), text_color(red)]);
+
+    // without any `color`, the default text color is used
+    assert_eq!(resolve(&[CssDeclaration::CurrentColor(background(red))], &node_overrides), vec![background(DEFAULT_FONT_COLOR.0)]);
+
+    // `currentColor` follows a dynamically overridden `color`
+    let declarations = vec![
+        CssDeclaration::Dynamic(DynamicCssProperty {
+            property_type: CssPropertyType::TextColor,
+            dynamic_id: "text_color".into(),
+            default: DynamicCssPropertyDefault::Exact(text_color(red)),
+        }),
+        CssDeclaration::CurrentColor(background(placeholder)),
+    ];
+    assert_eq!(resolve(&declarations, &node_overrides), vec![text_color(red), background(red)]);
+
+    let mut overrides_of_node = FastHashMap::default();
+    overrides_of_node.insert(DomString::Static("text_color"), text_color(blue));
+    node_overrides.insert(NodeId::new(0), overrides_of_node);
+    assert_eq!(resolve(&declarations, &node_overrides), vec![text_color(blue), background(blue)]);
+}
+
+/// Returns the property that a declaration resolves to for this frame: either the static
+/// property, the dynamic property overridden on the node, the dynamic property overridden
+/// on the whole window or the default of the dynamic property
+fn resolve_css_declaration<'a>(
+    declaration: &'a CssDeclaration,
+    node_id: NodeId,
+    css_overrides: &'a BTreeMap<NodeId, FastHashMap<DomString, CssProperty>>,
+    window_css_overrides: &'a FastHashMap<DomString, CssProperty>,
+) -> Option<&'a CssProperty> {
     use azul_css::CssDeclaration::*;
 
-    for constraint in &rect.styled_node.css_constraints {
-        match constraint {
-            Static(static_property) => apply_style_property(rect, static_property),
-            Dynamic(dynamic_property) => {
-                let is_dynamic_prop = css_overrides.get(&node_id).and_then(|overrides| {
-                    overrides.get(&DomString::Heap(dynamic_property.dynamic_id.clone()))
-                });
-
-                if let Some(overridden_property) = is_dynamic_prop {
-                    // Only apply the dynamic style property default, if it isn't set to auto
-                    if property_type_matches(overridden_property, &dynamic_property.default) {
-                        apply_style_property(rect, overridden_property);
-                    } else {
-                        #[cfg(feature = "logging")] {
-                            error!(
-                                "Dynamic style property on rect {:?} don't have the same discriminant type,\r\n
-                                cannot override {:?} with {:?} - enum discriminant mismatch",
-                                rect, dynamic_property.default, overridden_property
-                            )
-                        }
+    match declaration.without_importance() {
+        Static(static_property) => Some(static_property),
+        Dynamic(dynamic_property) => {
+            let dynamic_id = DomString::Heap(dynamic_property.dynamic_id.clone());
+            let is_dynamic_prop = css_overrides.get(&node_id)
+                .and_then(|overrides| overrides.get(&dynamic_id))
+                .or_else(|| window_css_overrides.get(&dynamic_id));
+
+            if let Some(overridden_property) = is_dynamic_prop {
+                // Only apply the dynamic style property default, if it isn't set to auto
+                if property_type_matches(overridden_property, &dynamic_property.default) {
+                    Some(overridden_property)
+                } else {
+                    #[cfg(feature = "logging")] {
+                        error!(
+                            "Dynamic style property on node {:?} don't have the same discriminant type,\r\n
+                            cannot override {:?} with {:?} - enum discriminant mismatch",
+                            node_id, dynamic_property.default, overridden_property
+                        )
                     }
-                } else if let DynamicCssPropertyDefault::Exact(default) = &dynamic_property.default {
-                    apply_style_property(rect, default);
+                    None
                 }
+            } else if let DynamicCssPropertyDefault::Exact(default) = &dynamic_property.default {
+                Some(default)
+            } else {
+                None
             }
+        },
+        Important(_) => None, /* unreachable, the importance has been removed above */
+        Keyword(..) => None, /* keywords are resolved during the cascade */
+        CurrentColor(_) => None, /* currentColor is resolved in resolve_css_declarations */
+    }
+}
+
+#[test]
+fn test_resolve_css_declaration_window_overrides() {
+    use azul_css::{DynamicCssProperty, CssPropertyType, LayoutWidth};
+
+    let width = |px| CssProperty::Width(LayoutWidth::px(px));
+    let declaration = CssDeclaration::Dynamic(DynamicCssProperty {
+        property_type: CssPropertyType::Width,
+        dynamic_id: "zoom".into(),
+        default: DynamicCssPropertyDefault::Exact(width(1.0)),
+    });
+
+    let mut node_overrides = BTreeMap::new();
+    let mut window_overrides = FastHashMap::default();
+    let node = NodeId::new(0);
+    let other_node = NodeId::new(1);
+
+    assert_eq!(resolve_css_declaration(&declaration, node, &node_overrides, &window_overrides), Some(&width(1.0)));
+
+    // the window-wide value applies to all nodes ...
+    window_overrides.insert(DomString::Static("zoom"), width(2.0));
+    assert_eq!(resolve_css_declaration(&declaration, node, &node_overrides, &window_overrides), Some(&width(2.0)));
+
+    // ... unless the node overrides the property itself
+    let mut overrides_of_node = FastHashMap::default();
+    overrides_of_node.insert(DomString::Static("zoom"), width(3.0));
+    node_overrides.insert(node, overrides_of_node);
+    assert_eq!(resolve_css_declaration(&declaration, node, &node_overrides, &window_overrides), Some(&width(3.0)));
+    assert_eq!(resolve_css_declaration(&declaration, other_node, &node_overrides, &window_overrides), Some(&width(2.0)));
+}
+
+/// Builds the scrollbar style from the declarations of the `::-webkit-scrollbar*` rules
+/// of a node, starting from the default scrollbar style.
+///
+/// The `width` and `padding` of `::-webkit-scrollbar` set the size of the scrollbar,
+/// all other layout properties are ignored. `-webkit-scrollbar`, `-webkit-scrollbar-track`
+/// and `-webkit-scrollbar-track-piece` all style the track.
+fn get_scrollbar_info(
+    scrollbar_constraints: &[(CssScrollbarPseudoElement, CssDeclaration)],
+    node_id: NodeId,
+    css_overrides: &BTreeMap<NodeId, FastHashMap<DomString, CssProperty>>,
+    window_css_overrides: &FastHashMap<DomString, CssProperty>,
+) -> ScrollbarInfo {
+    use azul_css::CssScrollbarPseudoElement::*;
+
+    let mut info = ScrollbarInfo::default();
+    let mut scrollbar_layout = RectLayout {
+        width: Some(info.width),
+        padding: Some(info.padding),
+        .. Default::default()
+    };
+    let mut ignored_layout = RectLayout::default();
+
+    for (part, declaration) in scrollbar_constraints {
+        let property = match resolve_css_declaration(declaration, node_id, css_overrides, window_css_overrides) {
+            Some(p) => p,
+            None => continue,
+        };
+        match part {
+            Scrollbar => apply_style_property(&mut info.track, &mut scrollbar_layout, property),
+            Track | TrackPiece => apply_style_property(&mut info.track, &mut ignored_layout, property),
+            Thumb => apply_style_property(&mut info.thumb, &mut ignored_layout, property),
+            Button => apply_style_property(&mut info.button, &mut ignored_layout, property),
+            Corner => apply_style_property(&mut info.corner, &mut ignored_layout, property),
+            Resizer => apply_style_property(&mut info.resizer, &mut ignored_layout, property),
         }
     }
+
+    info.width = scrollbar_layout.width.unwrap_or(info.width);
+    info.padding = scrollbar_layout.padding.unwrap_or(info.padding);
+    info
 }
 
 // Assert that the types of two properties matches
@@ -1552,51 +2925,59 @@ fn property_type_matches(a: &CssProperty, b: &DynamicCssPropertyDefault) -> bool
     }
 }
 
-fn apply_style_property(rect: &mut DisplayRectangle, property: &CssProperty) {
+fn apply_style_property(style: &mut RectStyle, layout: &mut RectLayout, property: &CssProperty) {
 
     use azul_css::CssProperty::*;
 
     match property {
-        BorderRadius(b)     => { rect.style.border_radius = Some(*b);                   },
-        BackgroundColor(c)  => { rect.style.background_color = Some(*c);                },
-        BackgroundSize(s)   => { rect.style.background_size = Some(*s);                 },
-        BackgroundRepeat(r) => { rect.style.background_repeat = Some(*r);               },
-        TextColor(t)        => { rect.style.font_color = Some(*t);                      },
-        Border(b)           => { StyleBorder::merge(&mut rect.style.border, &b);        },
-        Background(b)       => { rect.style.background = Some(b.clone());               },
-        FontSize(f)         => { rect.style.font_size = Some(*f);                       },
-        FontFamily(f)       => { rect.style.font_family = Some(f.clone());              },
-        LetterSpacing(l)    => { rect.style.letter_spacing = Some(*l);                  },
-        TextAlign(ta)       => { rect.style.text_align = Some(*ta);                     },
-        BoxShadow(b)        => { StyleBoxShadow::merge(&mut rect.style.box_shadow, b);  },
-        LineHeight(lh)      => { rect.style.line_height = Some(*lh);                    },
-
-        Width(w)            => { rect.layout.width = Some(*w);                          },
-        Height(h)           => { rect.layout.height = Some(*h);                         },
-        MinWidth(mw)        => { rect.layout.min_width = Some(*mw);                     },
-        MinHeight(mh)       => { rect.layout.min_height = Some(*mh);                    },
-        MaxWidth(mw)        => { rect.layout.max_width = Some(*mw);                     },
-        MaxHeight(mh)       => { rect.layout.max_height = Some(*mh);                    },
-
-        Position(p)         => { rect.layout.position = Some(*p);                       },
-        Top(t)              => { rect.layout.top = Some(*t);                            },
-        Bottom(b)           => { rect.layout.bottom = Some(*b);                         },
-        Right(r)            => { rect.layout.right = Some(*r);                          },
-        Left(l)             => { rect.layout.left = Some(*l);                           },
-
-        Padding(p)          => { LayoutPadding::merge(&mut rect.layout.padding, &p);    },
-        Margin(m)           => { LayoutMargin::merge(&mut rect.layout.margin, &m);      },
-        Overflow(o)         => { LayoutOverflow::merge(&mut rect.layout.overflow, &o);  },
-        WordSpacing(ws)     => { rect.style.word_spacing = Some(*ws);                   },
-        TabWidth(tw)        => { rect.style.tab_width = Some(*tw);                      },
-
-        FlexGrow(g)         => { rect.layout.flex_grow = Some(*g)                       },
-        FlexShrink(s)       => { rect.layout.flex_shrink = Some(*s)                     },
-        FlexWrap(w)         => { rect.layout.wrap = Some(*w);                           },
-        FlexDirection(d)    => { rect.layout.direction = Some(*d);                      },
-        JustifyContent(j)   => { rect.layout.justify_content = Some(*j);                },
-        AlignItems(a)       => { rect.layout.align_items = Some(*a);                    },
-        AlignContent(a)     => { rect.layout.align_content = Some(*a);                  },
-        Cursor(_)           => { /* cursor neither affects layout nor styling */        },
+        BorderRadius(b)     => { style.border_radius = Some(*b);                   },
+        BackgroundColor(c)  => { style.background_color = Some(*c);                },
+        BackgroundSize(s)   => { style.background_size = Some(s.clone());          },
+        BackgroundRepeat(r) => { style.background_repeat = Some(r.clone());        },
+        BackgroundPosition(p) => { style.background_position = Some(p.clone());    },
+        BackgroundOrigin(o) => { style.background_origin = Some(o.clone());        },
+        BackgroundClip(c)   => { style.background_clip = Some(c.clone());          },
+        BackgroundAttachment(a) => { style.background_attachment = Some(a.clone()); },
+        TextColor(t)        => { style.font_color = Some(*t);                      },
+        Border(b)           => { StyleBorder::merge(&mut style.border, &b);        },
+        Background(b)       => { style.background = Some(b.clone());               },
+        FontSize(f)         => { style.font_size = Some(*f);                       },
+        FontFamily(f)       => { style.font_family = Some(f.clone());              },
+        LetterSpacing(l)    => { style.letter_spacing = Some(*l);                  },
+        TextAlign(ta)       => { style.text_align = Some(*ta);                     },
+        BoxShadow(b)        => { StyleBoxShadow::merge(&mut style.box_shadow, b);  },
+        Outline(o)          => { StyleOutline::merge(&mut style.outline, o);       },
+        Filter(f)           => { style.filter = Some(f.clone());                   },
+        BackdropFilter(f)   => { style.backdrop_filter = Some(f.clone());          },
+        LineHeight(lh)      => { style.line_height = Some(*lh);                    },
+
+        Width(w)            => { layout.width = Some(*w);                          },
+        Height(h)           => { layout.height = Some(*h);                         },
+        MinWidth(mw)        => { layout.min_width = Some(*mw);                     },
+        MinHeight(mh)       => { layout.min_height = Some(*mh);                    },
+        MaxWidth(mw)        => { layout.max_width = Some(*mw);                     },
+        MaxHeight(mh)       => { layout.max_height = Some(*mh);                    },
+        AspectRatio(ar)     => { layout.aspect_ratio = Some(*ar);                  },
+
+        Position(p)         => { layout.position = Some(*p);                       },
+        Top(t)              => { layout.top = Some(*t);                            },
+        Bottom(b)           => { layout.bottom = Some(*b);                         },
+        Right(r)            => { layout.right = Some(*r);                          },
+        Left(l)             => { layout.left = Some(*l);                           },
+
+        Padding(p)          => { LayoutPadding::merge(&mut layout.padding, &p);    },
+        Margin(m)           => { LayoutMargin::merge(&mut layout.margin, &m);      },
+        Overflow(o)         => { LayoutOverflow::merge(&mut layout.overflow, &o);  },
+        WordSpacing(ws)     => { style.word_spacing = Some(*ws);                   },
+        TabWidth(tw)        => { style.tab_width = Some(*tw);                      },
+
+        FlexGrow(g)         => { layout.flex_grow = Some(*g)                       },
+        FlexShrink(s)       => { layout.flex_shrink = Some(*s)                     },
+        FlexWrap(w)         => { layout.wrap = Some(*w);                           },
+        FlexDirection(d)    => { layout.direction = Some(*d);                      },
+        JustifyContent(j)   => { layout.justify_content = Some(*j);                },
+        AlignItems(a)       => { layout.align_items = Some(*a);                    },
+        AlignContent(a)     => { layout.align_content = Some(*a);                  },
+        Cursor(_)           => { /* cursor neither affects layout nor styling */   },
     }
 }
@@ -9,6 +9,7 @@ use std::{
 };
 use azul_css::{ NodeTypePath, CssProperty };
 use {
+    FastHashMap,
     ui_state::UiState,
     callbacks::{
         DefaultCallbackId, StackCheckedPointer,
@@ -515,6 +516,17 @@ pub struct NodeData<T: Layout> {
     /// }
     /// ```
     pub dynamic_css_overrides: Vec<(DomString, CssProperty)>,
+    /// Static properties that are applied to this node only, similar to the `style`
+    /// attribute in HTML. They override all (non-`!important`) declarations of the stylesheet
+    /// and don't need an entry in the stylesheet:
+    ///
+    /// ```rust,ignore
+    /// let node = NodeData {
+    ///     inline_styles: vec![CssProperty::BackgroundColor(tag_color)],
+    ///     .. Default::default()
+    /// };
+    /// ```
+    pub inline_styles: Vec<CssProperty>,
     /// Whether this div can be dragged or not, similar to `draggable = "true"` in HTML, .
     ///
     /// **TODO**: Currently doesn't do anything, since the drag & drop implementation is missing, API stub.
@@ -571,6 +583,7 @@ impl<T: Layout> PartialEq for NodeData<T> {
         self.callbacks == other.callbacks &&
         self.default_callback_ids == other.default_callback_ids &&
         self.dynamic_css_overrides == other.dynamic_css_overrides &&
+        self.inline_styles == other.inline_styles &&
         self.is_draggable == other.is_draggable &&
         self.tab_index == other.tab_index
     }
@@ -587,6 +600,7 @@ impl<T: Layout> Default for NodeData<T> {
             callbacks: Vec::new(),
             default_callback_ids: Vec::new(),
             dynamic_css_overrides: Vec::new(),
+            inline_styles: Vec::new(),
             is_draggable: false,
             tab_index: None,
         }
@@ -611,6 +625,9 @@ impl<T: Layout> Hash for NodeData<T> {
         for dynamic_css_override in &self.dynamic_css_overrides {
             dynamic_css_override.hash(state);
         }
+        for inline_style in &self.inline_styles {
+            inline_style.hash(state);
+        }
         self.is_draggable.hash(state);
         self.tab_index.hash(state);
     }
@@ -625,6 +642,7 @@ impl<T: Layout> Clone for NodeData<T> {
             callbacks: self.callbacks.clone(),
             default_callback_ids: self.default_callback_ids.clone(),
             dynamic_css_overrides: self.dynamic_css_overrides.clone(),
+            inline_styles: self.inline_styles.clone(),
             is_draggable: self.is_draggable.clone(),
             tab_index: self.tab_index.clone(),
         }
@@ -662,6 +680,7 @@ impl<T: Layout> fmt::Debug for NodeData<T> {
                 \tcallbacks: {:?}, \
                 \tdefault_callback_ids: {:?}, \
                 \tdynamic_css_overrides: {:?}, \
+                \tinline_styles: {:?}, \
                 \tis_draggable: {:?}, \
                 \ttab_index: {:?}, \
             }}",
@@ -671,6 +690,7 @@ impl<T: Layout> fmt::Debug for NodeData<T> {
             self.callbacks,
             self.default_callback_ids,
             self.dynamic_css_overrides,
+            self.inline_styles,
             self.is_draggable,
             self.tab_index,
         )
@@ -1075,6 +1095,13 @@ impl<T: Layout> Dom<T> {
         self
     }
 
+    /// Same as `add_style`, but easier to use for method chaining in a builder-style pattern
+    #[inline]
+    pub fn with_style(mut self, property: CssProperty) -> Self {
+        self.add_style(property);
+        self
+    }
+
     #[inline]
     pub fn with_tab_index(mut self, tab_index: TabIndex) -> Self {
         self.set_tab_index(tab_index);
@@ -1112,6 +1139,12 @@ impl<T: Layout> Dom<T> {
         self.arena.node_data[self.head].dynamic_css_overrides.push((override_id.into(), property));
     }
 
+    /// Adds an inline style to the node, see `NodeData::inline_styles`
+    #[inline]
+    pub fn add_style(&mut self, property: CssProperty) {
+        self.arena.node_data[self.head].inline_styles.push(property);
+    }
+
     #[inline]
     pub fn set_tab_index(&mut self, tab_index: TabIndex) {
         self.arena.node_data[self.head].tab_index = Some(tab_index);
@@ -1336,6 +1369,7 @@ impl<T: Layout> Dom<T> {
 
             dom: self,
             dynamic_css_overrides,
+            window_css_overrides: FastHashMap::default(),
             tag_ids_to_hover_active_states: BTreeMap::new(),
 
             tab_index_tags,
@@ -60,10 +60,7 @@
 //!
 //! There are a few limitations that should be noted:
 //!
-//! - There are no scrollbars yet. Creating scrollable frames can be done by
-//!   [creating an `IFrameCallback`].
-//! - Similarly, there is no clipping of overflowing content yet - clipping only
-//!   works for `IFrameCallback`s.
+//! - Scrollbar buttons (`::-webkit-scrollbar-button`) and resizers are not drawn yet
 //! - There is no support for CSS animations of any kind yet
 //! - Changing dynamic variables will trigger an entire UI relayout and restyling
 //!
@@ -115,7 +112,6 @@
 //! [`Redraw`]: ../azul/callbacks/constant.Redraw.html
 //! [`DontRedraw`]: ../azul/callbacks/constant.DontRedraw.html
 //! [`GlTextureCallback`]: ../azul/callbacks/struct.GlTextureCallback.html
-//! [creating an `IFrameCallback`]: ../azul/dom/struct.Dom.html#method.iframe
 //! [from a file]: ../azul/dom/struct.Dom.html#method.from_file
 //! [CSS]: ../azul/css/index.html
 //! [load and parse stylesheets]: ../azul/css/fn.from_str.html
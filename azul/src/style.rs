@@ -2,8 +2,10 @@
 
 use std::{fmt, collections::BTreeMap};
 use azul_css::{
-    Css, CssContentGroup, CssDeclaration, CssPath,
+    Css, CssContentGroup, CssDeclaration, CssPath, CssRuleBlock,
     CssPathSelector, CssPathPseudoSelector, CssNthChildSelector::*,
+    CssScrollbarPseudoElement, CssWideKeyword, CssPropertyType, CssProperty,
+    StyleFontSize, EM_HEIGHT,
 };
 use webrender::api::HitTestItem;
 use {
@@ -469,6 +471,11 @@ fn selector_group_matches<'a, T: Layout>(selectors: &[&CssPathSelector], html_no
             PseudoSelector(CssPathPseudoSelector::Focus) => {
                 if !html_node.is_focused { return false; }
             },
+            PseudoSelector(CssPathPseudoSelector::Scrollbar(_)) => {
+                // Scrollbar rules style the scrollbar, not the node itself,
+                // see `collect_scrollbar_declarations`
+                return false;
+            },
             DirectChildren | Children => {
                 panic!("Unreachable: DirectChildren or Children in CSS path!");
             },
@@ -515,17 +522,29 @@ pub(crate) fn match_dom_selectors<T: Layout>(
     for (_depth, parent_id) in non_leaf_nodes {
 
         let mut parent_rules = styled_nodes.get(&parent_id).cloned().unwrap_or_default();
+        parent_rules.scrollbar_constraints = collect_scrollbar_declarations(css, parent_id, &ui_state.dom.arena.node_layout, &html_tree);
 
         // Iterate through all CSS rules, test if they match
         // This is technically O(n ^ 2), however, there are usually not that many CSS blocks,
         // so the cost of this should be insignificant.
-        for applying_rule in css.rules().filter(|rule| {
+        push_cascaded_declarations(&mut parent_rules.css_constraints, css.rules().filter(|rule| {
             matches_html_element(&rule.path, parent_id, &ui_state.dom.arena.node_layout, &html_tree)
-        }) {
-            parent_rules.css_constraints.extend(applying_rule.declarations.clone());
-        }
+        }), &ui_state.dom.arena.node_data[parent_id].inline_styles);
+
+        // The parent of this node has already been styled, since the nodes are sorted by depth
+        parent_rules.css_constraints = {
+            let grandparent_rules = ui_state.dom.arena.node_layout[parent_id].parent
+                .and_then(|grandparent_id| styled_nodes.get(&grandparent_id))
+                .map(|grandparent| &grandparent.css_constraints[..])
+                .unwrap_or(&[]);
+            resolve_css_wide_keywords(parent_rules.css_constraints, grandparent_rules)
+        };
 
-        let inheritable_rules: Vec<CssDeclaration> = parent_rules.css_constraints.iter().filter(|prop| prop.is_inheritable()).cloned().collect();
+        // The font size is inherited as a computed pixel value when building the display list
+        // instead, otherwise the `em` font sizes of the parents would be applied twice
+        let inheritable_rules: Vec<CssDeclaration> = parent_rules.css_constraints.iter()
+            .filter(|prop| prop.is_inheritable() && prop.get_type() != CssPropertyType::FontSize)
+            .cloned().collect();
 
         // For children: inherit from parents - filter children that themselves are not parents!
         for child_id in parent_id.children(&ui_state.dom.arena.node_layout) {
@@ -539,17 +558,19 @@ pub(crate) fn match_dom_selectors<T: Layout>(
                     // Iterate through all style rules, test if they match
                     // This is technically O(n ^ 2), however, there are usually not that many style blocks,
                     // so the cost of this should be insignificant.
-                    for applying_rule in css.rules().filter(|rule| {
+                    push_cascaded_declarations(&mut child_rules, css.rules().filter(|rule| {
                         matches_html_element(&rule.path, child_id, &ui_state.dom.arena.node_layout, &html_tree)
-                    }) {
-                        child_rules.extend(applying_rule.declarations.clone());
-                    }
+                    }), &ui_state.dom.arena.node_data[child_id].inline_styles);
+
+                    let child_rules = resolve_css_wide_keywords(child_rules, &parent_rules.css_constraints);
 
-                    styled_nodes.insert(child_id, StyledNode { css_constraints: child_rules });
+                    let scrollbar_rules = collect_scrollbar_declarations(css, child_id, &ui_state.dom.arena.node_layout, &html_tree);
+
+                    styled_nodes.insert(child_id, StyledNode { css_constraints: child_rules, scrollbar_constraints: scrollbar_rules });
                 },
                 Some(_) => {
                     // For all children that themselves are parents, simply copy the inheritable rules
-                    styled_nodes.insert(child_id, StyledNode { css_constraints: inheritable_rules.clone() });
+                    styled_nodes.insert(child_id, StyledNode { css_constraints: inheritable_rules.clone(), scrollbar_constraints: Vec::new() });
                 },
             }
         }
@@ -582,10 +603,189 @@ pub(crate) fn match_dom_selectors<T: Layout>(
         styled_nodes: styled_nodes,
         default_style_of_node: StyledNode::default(),
         dynamic_css_overrides: ui_state.dynamic_css_overrides.clone(),
+        window_css_overrides: ui_state.window_css_overrides.clone(),
         selected_hover_nodes,
     }
 }
 
+/// Appends the declarations of the matching rules (which are sorted by specificity)
+/// and the inline styles of the node in cascade order: Declarations that are applied later win,
+/// so the normal declarations are pushed first, followed by the inline styles (like the
+/// `style` attribute in HTML) and then all `!important` declarations.
+///
+/// The `!important` flag is removed afterwards, since it doesn't have any effect on
+/// inherited properties - the properties of the node itself always win over them.
+fn push_cascaded_declarations<'a, I: Iterator<Item=&'a CssRuleBlock>>(
+    target: &mut Vec<CssDeclaration>,
+    matching_rules: I,
+    inline_styles: &[CssProperty],
+) {
+    let matching_rules = matching_rules.collect::<Vec<_>>();
+    let declarations = || matching_rules.iter().flat_map(|rule| rule.declarations.iter());
+
+    target.extend(declarations().filter(|d| !d.is_important()).cloned());
+    target.extend(inline_styles.iter().cloned().map(CssDeclaration::Static));
+    target.extend(declarations().filter(|d| d.is_important()).map(|d| d.without_importance().clone()));
+}
+
+/// Resolves the `inherit`, `initial` and `unset` keywords in the cascaded declarations of a node:
+/// `initial` removes all earlier declarations of the property, which resets it to its default.
+/// `inherit` replaces them with the declarations of the parent node (except for dynamic
+/// declarations, which are never inherited) and `unset` acts like `inherit` for inheritable
+/// properties and like `initial` for all other properties.
+///
+/// Keywords on a side of a merged property (i.e. `padding-top: initial`) only apply to that
+/// side, the other sides of earlier `padding` declarations are kept.
+///
+/// Since the `font-size` is inherited as a computed value when building the display list,
+/// `inherit` and `unset` only remove the earlier `font-size` declarations of the node,
+/// `initial` replaces them with the default font size (`medium`).
+fn resolve_css_wide_keywords(declarations: Vec<CssDeclaration>, parent_declarations: &[CssDeclaration]) -> Vec<CssDeclaration> {
+
+    let mut resolved = Vec::<CssDeclaration>::with_capacity(declarations.len());
+
+    for declaration in declarations {
+
+        let (property_type, keyword) = match declaration.without_importance() {
+            CssDeclaration::Keyword(property_type, keyword) => (*property_type, *keyword),
+            _ => {
+                resolved.push(declaration);
+                continue;
+            },
+        };
+
+        // `padding: initial` applies to all sides, `padding-top: initial` only to `PaddingTop`
+        let applies_to = |part: CssPropertyType| part == property_type || part.get_merged_type() == property_type;
+
+        resolved = resolved.iter().filter_map(|d| filter_declaration_parts(d, &|part: CssPropertyType| !applies_to(part))).collect();
+
+        if property_type == CssPropertyType::FontSize {
+            if keyword == CssWideKeyword::Initial {
+                resolved.push(CssDeclaration::Static(CssProperty::FontSize(StyleFontSize::px(EM_HEIGHT))));
+            }
+            continue;
+        }
+
+        let inherits = match keyword {
+            CssWideKeyword::Inherit => true,
+            CssWideKeyword::Initial => false,
+            CssWideKeyword::Unset => property_type.is_inheritable(),
+        };
+
+        if inherits {
+            resolved.extend(parent_declarations.iter().filter_map(|d| match d {
+                CssDeclaration::Dynamic(_) => None,
+                _ => filter_declaration_parts(d, &applies_to),
+            }));
+        }
+    }
+
+    resolved
+}
+
+/// Returns the declaration with only the parts for which `keep` returns `true`,
+/// see `CssProperty::filter_parts`. Dynamic declarations can't be split into parts.
+fn filter_declaration_parts<F: Fn(CssPropertyType) -> bool>(declaration: &CssDeclaration, keep: &F) -> Option<CssDeclaration> {
+    use azul_css::CssDeclaration::*;
+    match declaration {
+        Static(p) => p.filter_parts(keep).map(Static),
+        CurrentColor(p) => p.filter_parts(keep).map(CurrentColor),
+        Important(i) => filter_declaration_parts(i, keep).map(CssDeclaration::into_important),
+        Dynamic(_) | Keyword(..) => if keep(declaration.get_type()) { Some(declaration.clone()) } else { None },
+    }
+}
+
+#[test]
+fn test_push_cascaded_declarations_inline_styles() {
+    use azul_css::*;
+
+    let color = |r| CssProperty::TextColor(StyleTextColor(ColorU { r, g: 0, b: 0, a: 255 }));
+    let path = CssPath { selectors: vec![CssPathSelector::Global] };
+    let rules = vec![
+        CssRuleBlock::new(path.clone(), vec![CssDeclaration::Static(color(1))]),
+        CssRuleBlock::new(path.clone(), vec![CssDeclaration::Static(color(2)).into_important()]),
+    ];
+
+    // inline styles override normal declarations, but not `!important` ones
+    let mut declarations = Vec::new();
+    push_cascaded_declarations(&mut declarations, rules.iter(), &[color(3)]);
+    assert_eq!(declarations, vec![
+        CssDeclaration::Static(color(1)),
+        CssDeclaration::Static(color(3)),
+        CssDeclaration::Static(color(2)),
+    ]);
+}
+
+#[test]
+fn test_resolve_css_wide_keywords() {
+    use azul_css::*;
+    use azul_css::CssWideKeyword::*;
+
+    let red = CssDeclaration::Static(CssProperty::TextColor(StyleTextColor(ColorU { r: 255, g: 0, b: 0, a: 255 })));
+    let blue = CssDeclaration::Static(CssProperty::TextColor(StyleTextColor(ColorU { r: 0, g: 0, b: 255, a: 255 })));
+    let width = CssDeclaration::Static(CssProperty::Width(LayoutWidth::px(5.0)));
+    let padding_top = CssDeclaration::Static(CssProperty::Padding(LayoutPadding { top: Some(PixelValue::px(5.0)), .. Default::default() }));
+
+    let keyword = |property_type, keyword| CssDeclaration::Keyword(property_type, keyword);
+    let parent = vec![red.clone(), width.clone()];
+
+    // inherited `red`, overridden by `blue`, reset by `initial`
+    assert_eq!(resolve_css_wide_keywords(vec![red.clone(), blue.clone(), keyword(CssPropertyType::TextColor, Initial)], &parent), vec![]);
+    assert_eq!(resolve_css_wide_keywords(vec![red.clone(), blue.clone(), keyword(CssPropertyType::TextColor, Inherit)], &parent), vec![red.clone()]);
+    assert_eq!(resolve_css_wide_keywords(vec![blue.clone(), keyword(CssPropertyType::TextColor, Unset)], &parent), vec![red.clone()]);
+
+    // `width` isn't inheritable, so `unset` resets it
+    assert_eq!(resolve_css_wide_keywords(vec![width.clone(), keyword(CssPropertyType::Width, Unset)], &parent), vec![]);
+    assert_eq!(resolve_css_wide_keywords(vec![keyword(CssPropertyType::Width, Inherit)], &parent), vec![width.clone()]);
+
+    // keywords on a side only reset that side of the merged property
+    let padding = |top: Option<f32>, left: Option<f32>| CssDeclaration::Static(CssProperty::Padding(LayoutPadding {
+        top: top.map(PixelValue::px),
+        left: left.map(PixelValue::px),
+        .. Default::default()
+    }));
+    assert_eq!(resolve_css_wide_keywords(vec![padding_top.clone(), keyword(CssPropertyType::PaddingLeft, Initial), blue.clone()], &parent), vec![padding_top.clone(), blue.clone()]);
+    assert_eq!(resolve_css_wide_keywords(vec![padding(Some(5.0), Some(3.0)), keyword(CssPropertyType::PaddingTop, Initial)], &parent), vec![padding(None, Some(3.0))]);
+    assert_eq!(resolve_css_wide_keywords(vec![padding(Some(5.0), Some(3.0)), keyword(CssPropertyType::Padding, Initial)], &parent), vec![]);
+
+    // `inherit` on a side only takes that side from the parent
+    let parent = vec![padding(Some(1.0), Some(2.0))];
+    assert_eq!(resolve_css_wide_keywords(vec![padding(Some(5.0), Some(3.0)), keyword(CssPropertyType::PaddingTop, Inherit)], &parent), vec![padding(None, Some(3.0)), padding(Some(1.0), None)]);
+
+    // `font-size: initial` is the default font size, not the font size of the parent,
+    // `inherit` and `unset` leave it to the computed font size of the parent
+    let font_size = |px| CssDeclaration::Static(CssProperty::FontSize(StyleFontSize::px(px)));
+    let parent = vec![font_size(30.0)];
+    assert_eq!(resolve_css_wide_keywords(vec![font_size(20.0), keyword(CssPropertyType::FontSize, Initial)], &parent), vec![font_size(EM_HEIGHT)]);
+    assert_eq!(resolve_css_wide_keywords(vec![font_size(20.0), keyword(CssPropertyType::FontSize, Inherit)], &parent), vec![]);
+    assert_eq!(resolve_css_wide_keywords(vec![font_size(20.0), keyword(CssPropertyType::FontSize, Unset)], &parent), vec![]);
+}
+
+/// Returns the declarations of all `::-webkit-scrollbar*` rules whose path (without the
+/// pseudo-element) matches the node, in the same cascade order as `push_cascaded_declarations`.
+fn collect_scrollbar_declarations<'a, T: Layout>(
+    css: &Css,
+    node_id: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    html_node_tree: &NodeDataContainer<HtmlCascadeInfo<'a, T>>,
+) -> Vec<(CssScrollbarPseudoElement, CssDeclaration)>
+{
+    let matching_rules = css.rules().filter_map(|rule| {
+        let (part, path) = rule.path.split_scrollbar_pseudo_element()?;
+        if matches_html_element(&path, node_id, node_hierarchy, html_node_tree) {
+            Some((part, rule))
+        } else {
+            None
+        }
+    }).collect::<Vec<_>>();
+
+    let declarations = || matching_rules.iter().flat_map(|(part, rule)| rule.declarations.iter().map(move |d| (*part, d)));
+
+    declarations().filter(|(_, d)| !d.is_important()).map(|(part, d)| (part, d.clone()))
+    .chain(declarations().filter(|(_, d)| d.is_important()).map(|(part, d)| (part, d.without_importance().clone())))
+    .collect()
+}
+
 /// Update the WindowStates focus node in case the previous
 /// frames callbacks set the focus to a specific node
 ///
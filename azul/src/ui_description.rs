@@ -2,7 +2,7 @@ use std::{
     fmt,
     collections::BTreeMap,
 };
-use azul_css::{ Css, CssDeclaration, CssProperty };
+use azul_css::{ Css, CssDeclaration, CssProperty, CssScrollbarPseudoElement };
 use webrender::api::HitTestItem;
 use {
     FastHashMap,
@@ -28,6 +28,9 @@ pub struct UiDescription<T: Layout> {
     pub(crate) default_style_of_node: StyledNode,
     /// The style properties that should be overridden for this frame, cloned from the `Css`
     pub(crate) dynamic_css_overrides: BTreeMap<NodeId, FastHashMap<DomString, CssProperty>>,
+    /// The style properties that should be overridden on all nodes for this frame,
+    /// used if the node doesn't override the property itself
+    pub(crate) window_css_overrides: FastHashMap<DomString, CssProperty>,
     /// In order to hit-test :hover and :active selectors, need to insert tags for all rectangles
     /// that have a non-:hover path, for example if we have `#thing:hover`, then all nodes selected by `#thing`
     /// need to get a TagId, otherwise, they can't be hit-tested.
@@ -42,6 +45,7 @@ impl<T: Layout> fmt::Debug for UiDescription<T> {
             styled_nodes: {:?},
             default_style_of_node: {:?},
             dynamic_css_overrides: {:?},
+            window_css_overrides: {:?},
             selected_hover_nodes: {:?},
         }}",
             self.ui_descr_arena,
@@ -49,6 +53,7 @@ impl<T: Layout> fmt::Debug for UiDescription<T> {
             self.styled_nodes,
             self.default_style_of_node,
             self.dynamic_css_overrides,
+            self.window_css_overrides,
             self.selected_hover_nodes,
         )
     }
@@ -62,6 +67,7 @@ impl<T: Layout> Clone for UiDescription<T> {
             styled_nodes: self.styled_nodes.clone(),
             default_style_of_node: self.default_style_of_node.clone(),
             dynamic_css_overrides: self.dynamic_css_overrides.clone(),
+            window_css_overrides: self.window_css_overrides.clone(),
             selected_hover_nodes: self.selected_hover_nodes.clone(),
         }
     }
@@ -118,4 +124,7 @@ impl<T: Layout> UiDescription<T> {
 pub(crate) struct StyledNode {
     /// The CSS constraints, after the cascading step
     pub(crate) css_constraints: Vec<CssDeclaration>,
+    /// The declarations of all `::-webkit-scrollbar*` rules that match this node, in cascade order.
+    /// Scrollbar styles are not inherited.
+    pub(crate) scrollbar_constraints: Vec<(CssScrollbarPseudoElement, CssDeclaration)>,
 }
\ No newline at end of file
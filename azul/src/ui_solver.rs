@@ -1,6 +1,6 @@
 use std::{f32, collections::BTreeMap};
 use azul_css::{
-    LayoutPosition, LayoutMargin, LayoutPadding,
+    LayoutPosition, LayoutMargin, LayoutPadding, LayoutWidth, LayoutHeight,
     RectLayout, StyleFontSize, RectStyle,
     StyleTextAlignmentHorz, StyleTextAlignmentVert, PixelValue,
 };
@@ -251,6 +251,30 @@ impl HeightCalculatedRect {
     }
 }
 
+/// Returns the containing block of an out-of-flow node: for `position: absolute`, this is the
+/// nearest positioned (non-`static`) ancestor or the root node if there is none. Returns `None`
+/// for `position: fixed`, since fixed nodes are positioned relative to the window.
+fn get_containing_block(
+    node_id: NodeId,
+    node_hierarchy: &NodeHierarchy,
+    arena_data: &NodeDataContainer<RectLayout>)
+-> Option<NodeId>
+{
+    if arena_data[node_id].position == Some(LayoutPosition::Fixed) {
+        return None;
+    }
+
+    let mut current_node = node_hierarchy[node_id].parent;
+    while let Some(parent_id) = current_node {
+        if arena_data[parent_id].position.unwrap_or_default() != LayoutPosition::Static {
+            return Some(parent_id);
+        }
+        current_node = node_hierarchy[parent_id].parent;
+    }
+
+    Some(NodeId::new(0))
+}
+
 // `typed_arena!(WidthCalculatedRect, preferred_width, determine_preferred_width, get_horizontal_padding, get_flex_basis_horizontal)`
 macro_rules! typed_arena {(
     $struct_name:ident,
@@ -259,6 +283,8 @@ macro_rules! typed_arena {(
     $get_padding_fn:ident,
     $get_flex_basis:ident,
     $bubble_fn_name:ident,
+    $left:ident,
+    $right:ident,
     $main_axis:ident
 ) => (
 
@@ -344,6 +370,53 @@ impl NodeDataContainer<$struct_name> {
         parent_ids_sorted_by_depth: &[(usize, NodeId)],
         root_width: f32
     ) {
+        /// Returns the width of an out-of-flow (`position: absolute` or `position: fixed`) child,
+        /// resolved against the inner width of its containing block. If the child has no fixed
+        /// width, but both `left` and `right` are set, it is stretched to fill the space between them.
+        fn get_out_of_flow_width(
+            child_id: NodeId,
+            node_hierarchy: &NodeHierarchy,
+            arena_data: &NodeDataContainer<RectLayout>,
+            width_calculated_arena: &NodeDataContainer<$struct_name>,
+            root_width: f32)
+        -> f32
+        {
+            let containing_block_width = match get_containing_block(child_id, node_hierarchy, arena_data) {
+                Some(block_id) => {
+                    let block_node = &width_calculated_arena[block_id];
+                    block_node.min_inner_size_px + block_node.flex_grow_px - block_node.$get_padding_fn()
+                },
+                None => root_width,
+            };
+
+            let child_layout = &arena_data[child_id];
+            let available_width = match (child_layout.$left, child_layout.$right) {
+                (Some(left), Some(right)) => {
+                    let margin = child_layout.margin.unwrap_or_default();
+                    let margin_left = margin.$left.and_then(|x| Some(x.to_pixels())).unwrap_or(0.0);
+                    let margin_right = margin.$right.and_then(|x| Some(x.to_pixels())).unwrap_or(0.0);
+                    (containing_block_width - left.0.to_pixels() - right.0.to_pixels() - margin_left - margin_right).max(0.0)
+                },
+                _ => containing_block_width,
+            };
+
+            // The node might have a min-width / max-width constraint, which has a
+            // higher precedence than `top, bottom, left, right`.
+            match width_calculated_arena[child_id].$preferred_field {
+                WhConstraint::EqualTo(e) => e,
+                WhConstraint::Between(min, max) => {
+                    if available_width < min {
+                        min
+                    } else if available_width > max {
+                        max
+                    } else {
+                        available_width
+                    }
+                },
+                WhConstraint::Unconstrained => available_width,
+            }
+        }
+
         /// Does the actual width layout, respects the `width`, `min_width` and `max_width`
         /// properties as well as the `flex_grow` factor. `flex_shrink` currently does nothing.
         fn distribute_space_along_main_axis(
@@ -351,7 +424,7 @@ impl NodeDataContainer<$struct_name> {
             node_hierarchy: &NodeHierarchy,
             arena_data: &NodeDataContainer<RectLayout>,
             width_calculated_arena: &mut NodeDataContainer<$struct_name>,
-            positioned_node_stack: &[NodeId])
+            root_width: f32)
         {
             // The inner space of the parent node, without the padding
             let mut parent_node_inner_width = {
@@ -377,9 +450,9 @@ impl NodeDataContainer<$struct_name> {
 
                 for (exact_width_child_id, exact_width) in exact_width_childs {
 
-                    // If this child node is `position: absolute`, it doesn't take any space away from
-                    // its siblings, since it is taken out of the regular content flow
-                    if arena_data[exact_width_child_id].position.unwrap_or_default() != LayoutPosition::Absolute {
+                    // If this child node is `position: absolute` or `fixed`, it doesn't take any space away
+                    // from its siblings, since it is taken out of the regular content flow
+                    if !arena_data[exact_width_child_id].position.unwrap_or_default().is_out_of_flow() {
                         horizontal_space_taken_up_by_fixed_width_items += exact_width;
                     }
 
@@ -409,7 +482,7 @@ impl NodeDataContainer<$struct_name> {
 
             for variable_child_id in &variable_width_childs {
 
-                if arena_data[*variable_child_id].position.unwrap_or_default() != LayoutPosition::Absolute {
+                if !arena_data[*variable_child_id].position.unwrap_or_default().is_out_of_flow() {
 
                     let min_width = width_calculated_arena[*variable_child_id].$preferred_field.min_needed_space().unwrap_or(0.0);
 
@@ -421,40 +494,14 @@ impl NodeDataContainer<$struct_name> {
 
                 } else {
 
-                    // `position: absolute` items don't take space away from their siblings, rather
-                    // they take the minimum needed space by their content
-
-                    let root_id = NodeId::new(0);
-                    let nearest_relative_parent_node = positioned_node_stack.get(positioned_node_stack.len() - 1).unwrap_or(&root_id);
-                    let relative_parent_width = {
-                        let relative_parent_node = &width_calculated_arena[*nearest_relative_parent_node];
-                        relative_parent_node.flex_grow_px + relative_parent_node.min_inner_size_px
-                    };
-
-                    // By default, absolute positioned elements take the width of their content
-                    // let min_inner_width = width_calculated_arena[*variable_child_id].$preferred_field.min_needed_space().unwrap_or(0.0);
-
-                    // The absolute positioned node might have a max-width constraint, which has a
-                    // higher precedence than `top, bottom, left, right`.
-                    let max_space_current_node = match width_calculated_arena[*variable_child_id].$preferred_field {
-                        WhConstraint::EqualTo(e) => e,
-                        WhConstraint::Between(min, max) => {
-                            if relative_parent_width > min {
-                                if relative_parent_width < max {
-                                    relative_parent_width
-                                } else {
-                                    max
-                                }
-                            } else {
-                                min
-                            }
-                        },
-                        WhConstraint::Unconstrained => relative_parent_width,
-                    };
+                    // `position: absolute` and `fixed` items don't take space away from their siblings,
+                    // rather they are sized relative to their containing block
+                    let out_of_flow_width = get_out_of_flow_width(
+                        *variable_child_id, node_hierarchy, arena_data, width_calculated_arena, root_width);
 
-                    // so that node.min_inner_size_px + node.flex_grow_px = max_space_current_node
+                    // so that node.min_inner_size_px + node.flex_grow_px = out_of_flow_width
                     width_calculated_arena[*variable_child_id].flex_grow_px =
-                        max_space_current_node - width_calculated_arena[*variable_child_id].min_inner_size_px;
+                        out_of_flow_width - width_calculated_arena[*variable_child_id].min_inner_size_px;
 
                     absolute_variable_width_nodes.push(*variable_child_id);
                 }
@@ -558,7 +605,7 @@ impl NodeDataContainer<$struct_name> {
             node_hierarchy: &NodeHierarchy,
             arena_data: &NodeDataContainer<RectLayout>,
             width_calculated_arena: &mut NodeDataContainer<$struct_name>,
-            positioned_node_stack: &[NodeId])
+            root_width: f32)
         {
             // The inner space of the parent node, without the padding
             let parent_node_inner_width = {
@@ -566,20 +613,15 @@ impl NodeDataContainer<$struct_name> {
                 parent_node.min_inner_size_px + parent_node.flex_grow_px - parent_node.$get_padding_fn()
             };
 
-            let last_relative_node_width = {
-                let zero_node = NodeId::new(0);
-                let last_relative_node_id = positioned_node_stack.get(positioned_node_stack.len() - 1).unwrap_or(&zero_node);
-                let last_relative_node = &width_calculated_arena[*last_relative_node_id];
-                last_relative_node.min_inner_size_px + last_relative_node.flex_grow_px - last_relative_node.$get_padding_fn()
-            };
-
             for child_id in node_id.children(node_hierarchy) {
 
-                let parent_node_inner_width = if arena_data[child_id].position.unwrap_or_default() != LayoutPosition::Absolute {
-                    parent_node_inner_width
-                } else {
-                    last_relative_node_width
-                };
+                if arena_data[child_id].position.unwrap_or_default().is_out_of_flow() {
+                    let out_of_flow_width = get_out_of_flow_width(
+                        child_id, node_hierarchy, arena_data, width_calculated_arena, root_width);
+                    width_calculated_arena[child_id].flex_grow_px =
+                        out_of_flow_width - width_calculated_arena[child_id].min_inner_size_px;
+                    continue;
+                }
 
                 let preferred_width = {
                     let min_width = width_calculated_arena[child_id].$preferred_field.min_needed_space().unwrap_or(0.0);
@@ -625,30 +667,18 @@ impl NodeDataContainer<$struct_name> {
 
         self[NodeId::new(0)].flex_grow_px = root_preferred_width - top_level_flex_basis;
 
-        // Keep track of the nearest relative or absolute positioned element
-        let mut positioned_node_stack = vec![NodeId::new(0)];
-
         for (_node_depth, parent_id) in parent_ids_sorted_by_depth {
 
-            use azul_css::{LayoutAxis, LayoutPosition};
-
-            let parent_is_positioned = arena_data[*parent_id].position.unwrap_or_default() != LayoutPosition::Static;
-            if parent_is_positioned {
-                positioned_node_stack.push(*parent_id);
-            }
+            use azul_css::LayoutAxis;
 
             // Only stretch the items, if they have a align-items: stretch!
             if arena_data[*parent_id].align_items.unwrap_or_default() == LayoutAlignItems::Stretch {
                 if arena_data[*parent_id].direction.unwrap_or_default().get_axis() == LayoutAxis::$main_axis {
-                    distribute_space_along_main_axis(parent_id, node_hierarchy, arena_data, self, &positioned_node_stack);
+                    distribute_space_along_main_axis(parent_id, node_hierarchy, arena_data, self, root_width);
                 } else {
-                    distribute_space_along_cross_axis(parent_id, node_hierarchy, arena_data, self, &positioned_node_stack);
+                    distribute_space_along_cross_axis(parent_id, node_hierarchy, arena_data, self, root_width);
                 }
             }
-
-            if parent_is_positioned {
-                positioned_node_stack.pop();
-            }
         }
     }
 
@@ -662,7 +692,7 @@ impl NodeDataContainer<$struct_name> {
     {
         node_id
             .children(node_hierarchy)
-            .filter(|child_node_id| display_arena[*child_node_id].position != Some(LayoutPosition::Absolute))
+            .filter(|child_node_id| !display_arena[*child_node_id].position.unwrap_or_default().is_out_of_flow())
             .map(|child_node_id| self[child_node_id].$get_flex_basis())
             .sum()
     }
@@ -677,6 +707,8 @@ typed_arena!(
     get_horizontal_padding,
     get_flex_basis_horizontal,
     bubble_preferred_widths_to_parents,
+    left,
+    right,
     Horizontal
 );
 
@@ -687,6 +719,8 @@ typed_arena!(
     get_vertical_padding,
     get_flex_basis_vertical,
     bubble_preferred_heights_to_parents,
+    top,
+    bottom,
     Vertical
 );
 
@@ -731,9 +765,13 @@ pub(crate) fn solve_flex_layout_width<'a>(
     node_hierarchy: &NodeHierarchy,
     display_rectangles: &NodeDataContainer<DisplayRectangle<'a>>,
     preferred_widths: &NodeDataContainer<Option<f32>>,
+    aspect_ratios: &NodeDataContainer<Option<f32>>,
     window_width: f32
 ) -> SolvedWidthLayout {
-    let layout_only_arena = display_rectangles.transform(|node, _| node.layout);
+    let layout_only_arena = display_rectangles.transform(|node, node_id| {
+        let layout = reserve_scrollbar_space(&node.layout, &node.style);
+        apply_aspect_ratio_to_width(layout, aspect_ratios[node_id])
+    });
     let mut width_calculated_arena = NodeDataContainer::<WidthCalculatedRect>::from_rect_layout_arena(&layout_only_arena, preferred_widths);
     let non_leaf_nodes_sorted_by_depth = node_hierarchy.get_parents_sorted_by_depth();
     width_calculated_arena.bubble_preferred_widths_to_parents(node_hierarchy, &layout_only_arena, &non_leaf_nodes_sorted_by_depth);
@@ -742,14 +780,67 @@ pub(crate) fn solve_flex_layout_width<'a>(
     SolvedWidthLayout { solved_widths , layout_only_arena, non_leaf_nodes_sorted_by_depth }
 }
 
+/// Adds the width of the scrollbars of `overflow: scroll` nodes to the right / bottom padding,
+/// so that the children don't get laid out below the scrollbars.
+///
+/// Scrollbars of `overflow: auto` nodes are only shown once the content overflows,
+/// so they overlay the content instead of taking up space (otherwise the layout would
+/// depend on itself).
+fn reserve_scrollbar_space(layout: &RectLayout, style: &RectStyle) -> RectLayout {
+
+    fn add_px(value: Option<PixelValue>, px: f32) -> Option<PixelValue> {
+        Some(PixelValue::px(value.map(|v| v.to_pixels()).unwrap_or(0.0) + px))
+    }
+
+    let overflow = match layout.overflow {
+        Some(o) => o,
+        None => return *layout,
+    };
+
+    let mut layout = *layout;
+    let mut padding = layout.padding.unwrap_or_default();
+
+    if overflow.needs_vertical_scrollbar(false) {
+        padding.right = add_px(padding.right, style.get_vertical_scrollbar_style().width.0.to_pixels());
+    }
+
+    if overflow.needs_horizontal_scrollbar(false) {
+        padding.bottom = add_px(padding.bottom, style.get_horizontal_scrollbar_style().width.0.to_pixels());
+    }
+
+    layout.padding = Some(padding);
+    layout
+}
+
+/// If the node has an aspect ratio and a `height`, but no `width`, derives the `width` from the `height`
+fn apply_aspect_ratio_to_width(mut layout: RectLayout, aspect_ratio: Option<f32>) -> RectLayout {
+    if let (Some(aspect_ratio), None, Some(height)) = (aspect_ratio, layout.width, layout.height) {
+        layout.width = Some(LayoutWidth::px(height.0.to_pixels() * aspect_ratio));
+    }
+    layout
+}
+
+/// If the node has an aspect ratio, but no `height`, derives the `height` from the solved
+/// width (without the padding) of the node
+fn apply_aspect_ratio_to_height(mut layout: RectLayout, aspect_ratio: Option<f32>, inner_width: f32) -> RectLayout {
+    if let (Some(aspect_ratio), None) = (aspect_ratio, layout.height) {
+        layout.height = Some(LayoutHeight::px(inner_width / aspect_ratio));
+    }
+    layout
+}
+
 /// Returns the solved height of the items in a BTree form
 pub(crate) fn solve_flex_layout_height(
     node_hierarchy: &NodeHierarchy,
     solved_widths: &SolvedWidthLayout,
     preferred_heights: &NodeDataContainer<Option<f32>>,
+    aspect_ratios: &NodeDataContainer<Option<f32>>,
     window_height: f32
 ) -> SolvedHeightLayout {
-    let SolvedWidthLayout { layout_only_arena, .. } = solved_widths;
+    let layout_only_arena = solved_widths.layout_only_arena.transform(|layout, node_id| {
+        let inner_width = solved_widths.solved_widths[node_id].total() - layout.get_horizontal_padding();
+        apply_aspect_ratio_to_height(*layout, aspect_ratios[node_id], inner_width)
+    });
     let mut height_calculated_arena = NodeDataContainer::<HeightCalculatedRect>::from_rect_layout_arena(&layout_only_arena, preferred_heights);
     height_calculated_arena.bubble_preferred_heights_to_parents(node_hierarchy, &layout_only_arena, &solved_widths.non_leaf_nodes_sorted_by_depth);
     height_calculated_arena.apply_flex_grow(node_hierarchy, &layout_only_arena, &solved_widths.non_leaf_nodes_sorted_by_depth, window_height);
@@ -779,15 +870,17 @@ fn $fn_name(
     node_hierarchy: &NodeHierarchy,
     node_data: &NodeDataContainer<RectLayout>,
     non_leaf_nodes: &[(usize, NodeId)],
-    solved_widths: &$width_layout)
+    solved_widths: &$width_layout,
+    window_width: f32)
 -> NodeDataContainer<$height_solved_position>
 {
     fn determine_child_x_absolute(
         child_id: NodeId,
-        positioned_node_stack: &[NodeId],
+        node_hierarchy: &NodeHierarchy,
         arena_data: &NodeDataContainer<RectLayout>,
         arena_solved_data: &mut NodeDataContainer<$height_solved_position>,
         solved_widths: &$width_layout,
+        window_width: f32,
     ) {
         let child_width_with_padding = {
             let child_node = &solved_widths.$solved_widths_field[child_id];
@@ -799,38 +892,41 @@ fn $fn_name(
         let child_margin_left = child_margin.$left.and_then(|x| Some(x.to_pixels())).unwrap_or(0.0);
         let child_margin_right = child_margin.$right.and_then(|x| Some(x.to_pixels())).unwrap_or(0.0);
 
-        let zero_node = NodeId::new(0);
-        let last_relative_node_id = positioned_node_stack.get(positioned_node_stack.len() - 1).unwrap_or(&zero_node);
-
-        let last_relative_node = arena_data[*last_relative_node_id];
-        let last_relative_padding = last_relative_node.padding.unwrap_or_default();
-        let last_relative_padding_left = last_relative_padding.$left.and_then(|x| Some(x.to_pixels())).unwrap_or(0.0);
-        let last_relative_padding_right = last_relative_padding.$right.and_then(|x| Some(x.to_pixels())).unwrap_or(0.0);
-
-        let last_relative_node_x = arena_solved_data[*last_relative_node_id].0 + last_relative_padding_left;
-        let last_relative_node_inner_width = {
-            let last_relative_node = &solved_widths.$solved_widths_field[*last_relative_node_id];
-            last_relative_node.$min_width + last_relative_node.space_added - (last_relative_padding_left + last_relative_padding_right)
+        // `position: fixed` nodes are positioned relative to the window, `position: absolute`
+        // nodes relative to the inner rect of their nearest positioned ancestor
+        let (containing_block_x, containing_block_inner_width) = match get_containing_block(child_id, node_hierarchy, arena_data) {
+            Some(block_id) => {
+                let block_padding = arena_data[block_id].padding.unwrap_or_default();
+                let block_padding_left = block_padding.$left.and_then(|x| Some(x.to_pixels())).unwrap_or(0.0);
+                let block_padding_right = block_padding.$right.and_then(|x| Some(x.to_pixels())).unwrap_or(0.0);
+                let block_node = &solved_widths.$solved_widths_field[block_id];
+                (
+                    arena_solved_data[block_id].0 + block_padding_left,
+                    block_node.$min_width + block_node.space_added - (block_padding_left + block_padding_right),
+                )
+            },
+            None => (0.0, window_width),
         };
 
-        let child_left = &arena_data[child_id].$left.and_then(|s| Some(s.0.to_pixels()));
-        let child_right = &arena_data[child_id].$right.and_then(|s| Some(s.0.to_pixels()));
+        let child_left = child_node.$left.and_then(|s| Some(s.0.to_pixels()));
+        let child_right = child_node.$right.and_then(|s| Some(s.0.to_pixels()));
 
-        if let Some(child_right) = child_right {
-            // align right / bottom of last relative parent
-            arena_solved_data[child_id].0 =
-                last_relative_node_x
-                + last_relative_node_inner_width
+        arena_solved_data[child_id].0 = match (child_left, child_right) {
+            // align right / bottom of the containing block
+            (None, Some(child_right)) => {
+                containing_block_x
+                + containing_block_inner_width
                 - child_width_with_padding
                 - child_margin_right
-                - child_right;
-        } else {
-            // align left / top of last relative parent
-            arena_solved_data[child_id].0 =
-                last_relative_node_x
+                - child_right
+            },
+            // align left / top of the containing block (`left` wins if both are set)
+            (child_left, _) => {
+                containing_block_x
                 + child_margin_left
-                + child_left.unwrap_or(0.0);
-        }
+                + child_left.unwrap_or(0.0)
+            },
+        };
     }
 
     fn determine_child_x_along_main_axis(
@@ -843,7 +939,8 @@ fn $fn_name(
         parent_x_position: f32,
         parent_inner_width: f32,
         sum_x_of_children_so_far: &mut f32,
-        positioned_node_stack: &[NodeId],
+        node_hierarchy: &NodeHierarchy,
+        window_width: f32,
     ) {
         use azul_css::LayoutJustifyContent::*;
 
@@ -858,13 +955,14 @@ fn $fn_name(
         let child_margin_left = child_margin.$left.and_then(|x| Some(x.to_pixels())).unwrap_or(0.0);
         let child_margin_right = child_margin.$right.and_then(|x| Some(x.to_pixels())).unwrap_or(0.0);
 
-        if child_node.position.unwrap_or_default() == LayoutPosition::Absolute {
+        if child_node.position.unwrap_or_default().is_out_of_flow() {
             determine_child_x_absolute(
                 child_id,
-                positioned_node_stack,
+                node_hierarchy,
                 arena_data,
                 arena_solved_data,
-                solved_widths
+                solved_widths,
+                window_width,
             );
         } else {
             // X position of the top left corner
@@ -895,20 +993,22 @@ fn $fn_name(
         arena_data: &NodeDataContainer<RectLayout>,
         solved_widths: &$width_layout,
         child_id: NodeId,
-        positioned_node_stack: &[NodeId],
+        node_hierarchy: &NodeHierarchy,
         arena_solved_data: &mut NodeDataContainer<$height_solved_position>,
-        parent_x_position: f32)
+        parent_x_position: f32,
+        window_width: f32)
     {
         let child_node = &arena_data[child_id];
         let child_margin_left = child_node.margin.unwrap_or_default().$left.and_then(|x| Some(x.to_pixels())).unwrap_or(0.0);
 
-        if child_node.position.unwrap_or_default() == LayoutPosition::Absolute {
+        if child_node.position.unwrap_or_default().is_out_of_flow() {
             determine_child_x_absolute(
                 child_id,
-                positioned_node_stack,
+                node_hierarchy,
                 arena_data,
                 arena_solved_data,
-                solved_widths
+                solved_widths,
+                window_width,
             );
         } else {
             arena_solved_data[child_id].0 = parent_x_position + child_margin_left;
@@ -919,9 +1019,6 @@ fn $fn_name(
 
     let mut arena_solved_data = NodeDataContainer::new(vec![$height_solved_position(0.0); node_data.len()]);
 
-    // Stack of the positioned nodes (nearest relative or absolute positioned node)
-    let mut positioned_node_stack = vec![NodeId::new(0)];
-
     for (_node_depth, parent_id) in non_leaf_nodes {
 
         let parent_node = node_data[*parent_id];
@@ -931,12 +1028,6 @@ fn $fn_name(
         let parent_x_position = arena_solved_data[*parent_id].0 + parent_padding_left;
         let parent_direction = parent_node.direction.unwrap_or_default();
 
-        // Push nearest relative or absolute positioned element
-        let parent_is_positioned = parent_node.position.unwrap_or_default() != LayoutPosition::Static;
-        if parent_is_positioned {
-            positioned_node_stack.push(*parent_id);
-        }
-
         let parent_inner_width = {
             let parent_node = &solved_widths.$solved_widths_field[*parent_id];
             parent_node.$min_width + parent_node.space_added - (parent_padding_left + parent_padding_right)
@@ -959,7 +1050,8 @@ fn $fn_name(
                         parent_x_position,
                         parent_inner_width,
                         &mut sum_x_of_children_so_far,
-                        &positioned_node_stack,
+                        node_hierarchy,
+                        window_width,
                     );
                 }
             } else {
@@ -974,7 +1066,8 @@ fn $fn_name(
                         parent_x_position,
                         parent_inner_width,
                         &mut sum_x_of_children_so_far,
-                        &positioned_node_stack,
+                        node_hierarchy,
+                        window_width,
                     );
                 }
             }
@@ -988,7 +1081,7 @@ fn $fn_name(
             if should_align_towards_end {
                 let diff = parent_inner_width - sum_x_of_children_so_far;
                 for child_id in parent_id.children(node_hierarchy).filter(|ch| {
-                    node_data[*ch].position.unwrap_or_default() != LayoutPosition::Absolute
+                    !node_data[*ch].position.unwrap_or_default().is_out_of_flow()
                 }) {
                     arena_solved_data[child_id].0 += diff;
                 }
@@ -1003,9 +1096,10 @@ fn $fn_name(
                         node_data,
                         solved_widths,
                         child_id,
-                        &positioned_node_stack,
+                        node_hierarchy,
                         &mut arena_solved_data,
                         parent_x_position,
+                        window_width,
                     );
                 }
             } else {
@@ -1014,18 +1108,14 @@ fn $fn_name(
                         node_data,
                         solved_widths,
                         child_id,
-                        &positioned_node_stack,
+                        node_hierarchy,
                         &mut arena_solved_data,
                         parent_x_position,
+                        window_width,
                     );
                 }
             }
         }
-
-        if parent_is_positioned {
-            positioned_node_stack.pop();
-        }
-
     }
 
     arena_solved_data
@@ -1037,10 +1127,11 @@ fn get_x_positions(
     solved_widths: &SolvedWidthLayout,
     node_hierarchy: &NodeHierarchy,
     origin: LayoutPoint,
+    window_width: f32,
 ) -> NodeDataContainer<HorizontalSolvedPosition>
 {
     get_position!(get_pos_x, SolvedWidthLayout, HorizontalSolvedPosition, solved_widths, min_width, left, right, Horizontal);
-    let mut arena = get_pos_x(node_hierarchy, &solved_widths.layout_only_arena, &solved_widths.non_leaf_nodes_sorted_by_depth, solved_widths);
+    let mut arena = get_pos_x(node_hierarchy, &solved_widths.layout_only_arena, &solved_widths.non_leaf_nodes_sorted_by_depth, solved_widths, window_width);
 
     // Add the origin on top of the position
     let x = origin.x as f32;
@@ -1056,11 +1147,12 @@ fn get_y_positions(
     solved_heights: &SolvedHeightLayout,
     solved_widths: &SolvedWidthLayout,
     node_hierarchy: &NodeHierarchy,
-    origin: LayoutPoint
+    origin: LayoutPoint,
+    window_height: f32,
 ) -> NodeDataContainer<VerticalSolvedPosition>
 {
     get_position!(get_pos_y, SolvedHeightLayout, VerticalSolvedPosition, solved_heights, min_height, top, bottom, Vertical);
-    let mut arena = get_pos_y(node_hierarchy, &solved_widths.layout_only_arena, &solved_widths.non_leaf_nodes_sorted_by_depth, solved_heights);
+    let mut arena = get_pos_y(node_hierarchy, &solved_widths.layout_only_arena, &solved_widths.non_leaf_nodes_sorted_by_depth, solved_heights, window_height);
 
     // Add the origin on top of the position
     let y = origin.y as f32;
@@ -1099,7 +1191,7 @@ fn get_content_height<T: Layout>(
     match &node_type {
         Image(i) => {
             let image_size = &app_resources.get_image_info(i)?.descriptor.size;
-            Some(div_width * (image_size.width as f32 / image_size.height as f32))
+            Some(div_width * (image_size.height as f32 / image_size.width as f32))
         },
         Label(_) | Text(_) => {
             positioned_words.get(node_id).map(|pos| pos.0.content_size.height)
@@ -1108,6 +1200,30 @@ fn get_content_height<T: Layout>(
     }
 }
 
+/// Returns the preferred ratio of the width to the height of a node: either from the `aspect-ratio`
+/// property or, for images without an `aspect-ratio`, from the dimensions of the image
+fn get_aspect_ratio<T: Layout>(
+    node_type: &NodeType<T>,
+    layout: &RectLayout,
+    app_resources: &AppResources,
+) -> Option<f32> {
+    use dom::NodeType::*;
+    if let Some(aspect_ratio) = layout.aspect_ratio {
+        return aspect_ratio.get();
+    }
+    match node_type {
+        Image(image_id) => {
+            let (width, height) = app_resources.get_image_info(image_id)?.get_dimensions();
+            if width == 0 || height == 0 {
+                None
+            } else {
+                Some(width as f32 / height as f32)
+            }
+        },
+        _ => None,
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) enum PreferredHeight {
     Image { original_dimensions: (f32, f32), current_height: f32 },
@@ -1189,11 +1305,16 @@ pub(crate) fn do_the_layout<'a,'b, T: Layout>(
     rect_offset: LayoutPoint,
 ) -> LayoutResult {
 
+    let aspect_ratios = node_data.transform(|node, node_id| {
+        get_aspect_ratio(&node.node_type, &display_rects[node_id].layout, app_resources)
+    });
+
     // Determine what the width would be if the content didn't matter
     let widths_content_ignored = solve_flex_layout_width(
         node_hierarchy,
         &display_rects,
         &node_data.transform(|node, node_id| None),
+        &aspect_ratios,
         rect_size.width as f32,
     );
 
@@ -1227,12 +1348,14 @@ pub(crate) fn do_the_layout<'a,'b, T: Layout>(
         node_hierarchy,
         &display_rects,
         &content_widths,
+        &aspect_ratios,
         rect_size.width as f32,
     );
 
     // Layout the words again, this time with the proper width constraints!
+    // (the padding includes the space reserved for the scrollbars)
     let proper_max_widths = solved_widths.solved_widths.linear_iter().map(|node_id| {
-        (node_id, solved_widths.solved_widths[node_id].total() - display_rects[node_id].layout.get_horizontal_padding())
+        (node_id, solved_widths.solved_widths[node_id].total() - solved_widths.layout_only_arena[node_id].get_horizontal_padding())
     }).collect();
 
     let word_positions_with_max_width = create_word_positions(&word_cache, &scaled_words, display_rects, &proper_max_widths, &inline_text_blocks);
@@ -1250,11 +1373,12 @@ pub(crate) fn do_the_layout<'a,'b, T: Layout>(
         node_hierarchy,
         &solved_widths,
         &content_heights,
+        &aspect_ratios,
         rect_size.height as f32,
     );
 
-    let x_positions = get_x_positions(&solved_widths, node_hierarchy, rect_offset.clone());
-    let y_positions = get_y_positions(&solved_heights, &solved_widths, node_hierarchy, rect_offset);
+    let x_positions = get_x_positions(&solved_widths, node_hierarchy, rect_offset.clone(), rect_size.width as f32);
+    let y_positions = get_y_positions(&solved_heights, &solved_widths, node_hierarchy, rect_offset, rect_size.height as f32);
 
     let layouted_rects = node_data.transform(|_node, node_id| {
         PositionedRectangle {
@@ -1712,4 +1836,188 @@ mod layout_tests {
             space_added: window_width - 200.0,
         });
     }
+
+    #[test]
+    fn test_get_containing_block() {
+        let positioned = |position| RectLayout { position: Some(position), .. Default::default() };
+
+        let (node_hierarchy, node_data) = get_display_rectangle_arena(&[
+            (1, positioned(LayoutPosition::Relative)),
+            (3, positioned(LayoutPosition::Absolute)),
+            (4, positioned(LayoutPosition::Fixed)),
+        ]);
+
+        // node 2 is static, so the nearest positioned ancestor of node 3 is node 1
+        assert_eq!(get_containing_block(NodeId::new(3), &node_hierarchy, &node_data), Some(NodeId::new(1)));
+        assert_eq!(get_containing_block(NodeId::new(4), &node_hierarchy, &node_data), None);
+        assert_eq!(get_containing_block(NodeId::new(1), &node_hierarchy, &node_data), Some(NodeId::new(0)));
+    }
+
+    #[test]
+    fn test_apply_aspect_ratio() {
+        use azul_css::{LayoutWidth, LayoutHeight};
+
+        let height_only = RectLayout { height: Some(LayoutHeight::px(90.0)), .. Default::default() };
+        assert_eq!(apply_aspect_ratio_to_width(height_only, Some(2.0)).width, Some(LayoutWidth::px(180.0)));
+        assert_eq!(apply_aspect_ratio_to_width(height_only, None).width, None);
+
+        let width_and_height = RectLayout { width: Some(LayoutWidth::px(10.0)), .. height_only };
+        assert_eq!(apply_aspect_ratio_to_width(width_and_height, Some(2.0)), width_and_height);
+        assert_eq!(apply_aspect_ratio_to_height(width_and_height, Some(2.0), 10.0), width_and_height);
+
+        let no_height = RectLayout::default();
+        assert_eq!(apply_aspect_ratio_to_height(no_height, Some(2.0), 100.0).height, Some(LayoutHeight::px(50.0)));
+    }
+
+    #[test]
+    fn test_reserve_scrollbar_space() {
+        use azul_css::{RectStyle, LayoutOverflow, Overflow, LayoutPadding, PixelValue};
+
+        let style = RectStyle::default();
+        let vertical_scrollbar_width = style.get_vertical_scrollbar_style().width.0.to_pixels();
+        let horizontal_scrollbar_height = style.get_horizontal_scrollbar_style().width.0.to_pixels();
+
+        let layout = |horizontal, vertical| RectLayout {
+            overflow: Some(LayoutOverflow { horizontal: Some(horizontal), vertical: Some(vertical) }),
+            padding: Some(LayoutPadding { right: Some(PixelValue::px(5.0)), .. Default::default() }),
+            .. Default::default()
+        };
+
+        // `overflow: scroll` always shows the scrollbars, so they are added to the padding
+        let padding = reserve_scrollbar_space(&layout(Overflow::Scroll, Overflow::Scroll), &style).padding.unwrap();
+        assert_eq!(padding.right, Some(PixelValue::px(5.0 + vertical_scrollbar_width)));
+        assert_eq!(padding.bottom, Some(PixelValue::px(horizontal_scrollbar_height)));
+
+        let padding = reserve_scrollbar_space(&layout(Overflow::Hidden, Overflow::Scroll), &style).padding.unwrap();
+        assert_eq!(padding.right, Some(PixelValue::px(5.0 + vertical_scrollbar_width)));
+        assert_eq!(padding.bottom, None);
+
+        // the scrollbars of `overflow: auto` overlay the content
+        let auto = layout(Overflow::Auto, Overflow::Auto);
+        assert_eq!(reserve_scrollbar_space(&auto, &style), auto);
+    }
+
+    /// Runs the width, height and position solvers over the given layout,
+    /// returns the solved `(x, y, width, height)` of every node
+    fn solve_rects(node_hierarchy: &NodeHierarchy, node_data: NodeDataContainer<RectLayout>, window_width: f32, window_height: f32)
+    -> Vec<(f32, f32, f32, f32)>
+    {
+        let no_content = node_data.transform(|_, _| None);
+
+        let mut width_calculated_arena = NodeDataContainer::<WidthCalculatedRect>::from_rect_layout_arena(&node_data, &no_content);
+        let non_leaf_nodes_sorted_by_depth = node_hierarchy.get_parents_sorted_by_depth();
+        width_calculated_arena.bubble_preferred_widths_to_parents(node_hierarchy, &node_data, &non_leaf_nodes_sorted_by_depth);
+        width_calculated_arena.apply_flex_grow(node_hierarchy, &node_data, &non_leaf_nodes_sorted_by_depth, window_width);
+        let solved_widths = SolvedWidthLayout {
+            solved_widths: width_calculated_arena.transform(|node, _| node.solved_result()),
+            layout_only_arena: node_data,
+            non_leaf_nodes_sorted_by_depth,
+        };
+
+        let solved_heights = solve_flex_layout_height(node_hierarchy, &solved_widths, &no_content, &no_content, window_height);
+        let x_positions = get_x_positions(&solved_widths, node_hierarchy, LayoutPoint::zero(), window_width);
+        let y_positions = get_y_positions(&solved_heights, &solved_widths, node_hierarchy, LayoutPoint::zero(), window_height);
+
+        (0..node_hierarchy.len()).map(|id| {
+            let id = NodeId::new(id);
+            (x_positions[id].0, y_positions[id].0, solved_widths.solved_widths[id].total(), solved_heights.solved_heights[id].total())
+        }).collect()
+    }
+
+    #[test]
+    fn test_solve_absolute_positioned_rects() {
+        use azul_css::*;
+
+        // 0
+        // '- 1             -- [position: relative; padding: 10px; width: 300px; height: 200px]
+        //    '-- 2         -- [] - static, so node 1 is the containing block of 3 and 4
+        //    '   '-- 3     -- [position: absolute; left: 20px; right: 30px; top: 5px; height: 40px]
+        //    '   '-- 4     -- [position: absolute; right: 30px; bottom: 20px; width: 50px; height: 40px]
+        //    '-- 5
+        let (node_hierarchy, node_data) = get_display_rectangle_arena(&[
+            (1, RectLayout {
+                position: Some(LayoutPosition::Relative),
+                padding: Some(LayoutPadding {
+                    top: Some(PixelValue::px(10.0)),
+                    bottom: Some(PixelValue::px(10.0)),
+                    left: Some(PixelValue::px(10.0)),
+                    right: Some(PixelValue::px(10.0)),
+                }),
+                width: Some(LayoutWidth::px(300.0)),
+                height: Some(LayoutHeight::px(200.0)),
+                .. Default::default()
+            }),
+            (3, RectLayout {
+                position: Some(LayoutPosition::Absolute),
+                left: Some(LayoutLeft::px(20.0)),
+                right: Some(LayoutRight::px(30.0)),
+                top: Some(LayoutTop::px(5.0)),
+                height: Some(LayoutHeight::px(40.0)),
+                .. Default::default()
+            }),
+            (4, RectLayout {
+                position: Some(LayoutPosition::Absolute),
+                right: Some(LayoutRight::px(30.0)),
+                bottom: Some(LayoutBottom::px(20.0)),
+                width: Some(LayoutWidth::px(50.0)),
+                height: Some(LayoutHeight::px(40.0)),
+                .. Default::default()
+            }),
+        ]);
+
+        let rects = solve_rects(&node_hierarchy, node_data, 800.0, 600.0);
+
+        // The inner rect of node 1 starts at (10, 10) and is 280px x 180px large
+        //
+        // left + right without a width: stretched to 280 - 20 - 30 = 230px
+        assert_eq!(rects[3], (10.0 + 20.0, 10.0 + 5.0, 230.0, 40.0));
+        // right / bottom: aligned to the right / bottom edge of the inner rect
+        assert_eq!(rects[4], (10.0 + 280.0 - 50.0 - 30.0, 10.0 + 180.0 - 40.0 - 20.0, 50.0, 40.0));
+    }
+
+    #[test]
+    fn test_solve_fixed_positioned_rects() {
+        use azul_css::*;
+
+        let window_width = 800.0;
+        let window_height = 600.0;
+
+        // Same as above, but 3 and 4 are `position: fixed`, so they ignore node 1
+        // and are positioned relative to the window
+        let (node_hierarchy, node_data) = get_display_rectangle_arena(&[
+            (1, RectLayout {
+                position: Some(LayoutPosition::Relative),
+                padding: Some(LayoutPadding {
+                    top: Some(PixelValue::px(10.0)),
+                    bottom: Some(PixelValue::px(10.0)),
+                    left: Some(PixelValue::px(10.0)),
+                    right: Some(PixelValue::px(10.0)),
+                }),
+                width: Some(LayoutWidth::px(300.0)),
+                height: Some(LayoutHeight::px(200.0)),
+                .. Default::default()
+            }),
+            (3, RectLayout {
+                position: Some(LayoutPosition::Fixed),
+                left: Some(LayoutLeft::px(20.0)),
+                right: Some(LayoutRight::px(30.0)),
+                top: Some(LayoutTop::px(5.0)),
+                height: Some(LayoutHeight::px(40.0)),
+                .. Default::default()
+            }),
+            (4, RectLayout {
+                position: Some(LayoutPosition::Fixed),
+                right: Some(LayoutRight::px(10.0)),
+                bottom: Some(LayoutBottom::px(10.0)),
+                width: Some(LayoutWidth::px(50.0)),
+                height: Some(LayoutHeight::px(40.0)),
+                .. Default::default()
+            }),
+        ]);
+
+        let rects = solve_rects(&node_hierarchy, node_data, window_width, window_height);
+
+        assert_eq!(rects[3], (20.0, 5.0, window_width - 20.0 - 30.0, 40.0));
+        assert_eq!(rects[4], (window_width - 50.0 - 10.0, window_height - 40.0 - 10.0, 50.0, 40.0));
+    }
 }
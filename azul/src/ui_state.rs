@@ -24,6 +24,9 @@ pub struct UiState<T: Layout> {
     pub dom: Dom<T>,
     /// The style properties that should be overridden for this frame, cloned from the `Css`
     pub dynamic_css_overrides: BTreeMap<NodeId, FastHashMap<DomString, CssProperty>>,
+    /// The style properties that should be overridden on all nodes of the window,
+    /// cloned from `FakeWindow::set_css_variable`
+    pub window_css_overrides: FastHashMap<DomString, CssProperty>,
     /// Stores all tags for nodes that need to activate on a `:hover` or `:active` event.
     pub tag_ids_to_hover_active_states: BTreeMap<TagId, (NodeId, HoverGroup)>,
 
@@ -61,6 +64,7 @@ impl<T: Layout> fmt::Debug for UiState<T> {
 
                 dom: {:?}, \
                 dynamic_css_overrides: {:?}, \
+                window_css_overrides: {:?}, \
                 tag_ids_to_hover_active_states: {:?}, \
                 tab_index_tags: {:?}, \
                 draggable_tags: {:?}, \
@@ -78,6 +82,7 @@ impl<T: Layout> fmt::Debug for UiState<T> {
 
             self.dom,
             self.dynamic_css_overrides,
+            self.window_css_overrides,
             self.tag_ids_to_hover_active_states,
             self.tab_index_tags,
             self.draggable_tags,
@@ -123,7 +128,9 @@ impl<T: Layout> UiState<T> {
             }
         };
 
-        Ok(dom.into_ui_state())
+        let mut ui_state = dom.into_ui_state();
+        ui_state.window_css_overrides = app_state.windows[window_id].css_variables.clone();
+        Ok(ui_state)
     }
 
     pub(crate) fn create_tags_for_hover_nodes(&mut self, hover_nodes: &BTreeMap<NodeId, HoverGroup>) {
@@ -25,7 +25,7 @@ use glium::{
     backend::{Context as BackendContext, Facade, glutin::DisplayCreationError},
 };
 use gleam::gl::{self, Gl};
-use azul_css::{Css, ColorU};
+use azul_css::{Css, ColorU, CssProperty};
 #[cfg(debug_assertions)]
 use azul_css::HotReloadHandler;
 use {
@@ -38,7 +38,8 @@ use {
         Callback, DefaultCallbackSystem, StackCheckedPointer,
         DefaultCallback, DefaultCallbackId, Texture,
     },
-    display_list::ScrolledNodes,
+    display_list::{ScrolledNodes, ScrollbarAxis},
+    dom::DomString,
 };
 pub use webrender::api::HitTestItem;
 pub use window_state::*;
@@ -62,6 +63,11 @@ pub struct FakeWindow<T: Layout> {
     /// but not change any window properties from underneath - this would
     /// lead to mismatch between the
     pub(crate) read_only_window: Rc<Display>,
+    /// Style that should replace the style of the window on the next frame, see `set_css`
+    pub(crate) pending_css: Option<Css>,
+    /// Values for the `[[ dynamic_id ]]` declarations of the stylesheet that apply to all
+    /// nodes of this window, see `set_css_variable`
+    pub(crate) css_variables: FastHashMap<DomString, CssProperty>,
 }
 
 impl<T: Layout> FakeWindow<T> {
@@ -104,6 +110,43 @@ impl<T: Layout> FakeWindow<T> {
         self.state.get_mouse_state()
     }
 
+    /// Replaces the style of the window at runtime (i.e. to toggle a dark mode from a callback),
+    /// the same way a hot-reloaded style gets swapped. The window is not recreated, the new
+    /// style gets applied and the window relayouted at the end of the current frame.
+    ///
+    /// If the window was created with `App::create_hot_reload_window`, this also stops
+    /// the hot-reloading, otherwise the next reload would overwrite the new style.
+    pub fn set_css(&mut self, css: Css) {
+        self.pending_css = Some(css);
+    }
+
+    /// Sets the value of all `[[ id | default ]]` declarations in the stylesheet of this window,
+    /// without having to attach a `dynamic_css_overrides` entry to each node. Overrides on the
+    /// node itself take precedence over the window-wide value.
+    ///
+    /// Like the `state`, the value persists between frames and is applied on the next layout,
+    /// so the callback setting it has to return `UpdateScreen::Redraw`.
+    ///
+    /// ```rust,ignore
+    /// // #header { background-color: [[ accent_color | #4286f4 ]]; }
+    /// let window = app_state.windows.get_mut(event.window_id).unwrap();
+    /// window.set_css_variable("accent_color", CssProperty::BackgroundColor(color));
+    /// ```
+    pub fn set_css_variable<S: Into<DomString>>(&mut self, id: S, property: CssProperty) {
+        self.css_variables.insert(id.into(), property);
+    }
+
+    /// Removes a value set by `set_css_variable`, so that the declarations use
+    /// their default value again. Returns the removed value.
+    pub fn remove_css_variable(&mut self, id: &str) -> Option<CssProperty> {
+        self.css_variables.remove(&DomString::Heap(id.to_string()))
+    }
+
+    /// Returns the window-wide value of a dynamic CSS property, if any
+    pub fn get_css_variable(&self, id: &str) -> Option<&CssProperty> {
+        self.css_variables.get(&DomString::Heap(id.to_string()))
+    }
+
     /// Adds a default callback to the window. The default callbacks are
     /// cleared after every frame, so two-way data binding widgets have to call this
     /// on every frame they want to insert a default callback.
@@ -185,7 +228,9 @@ impl<T: Layout> fmt::Debug for FakeWindow<T> {
             "FakeWindow {{\
                 state: {:?}, \
                 read_only_window: Rc<Display>, \
-            }}", self.state)
+                pending_css: {:?}, \
+                css_variables: {:?}, \
+            }}", self.state, self.pending_css, self.css_variables)
     }
 }
 
@@ -400,14 +445,36 @@ impl ScrollStates {
 
     /// Updating the scroll amount does not update the `entry.used_this_frame`,
     /// since that is only relevant when we are actually querying the renderer.
-    pub(crate) fn scroll_node(&mut self, scroll_id: &ExternalScrollId, scroll_by_x: f32, scroll_by_y: f32) {
-        if let Some(entry) = self.0.get_mut(scroll_id) {
-            entry.add(scroll_by_x, scroll_by_y);
+    ///
+    /// Returns whether the scroll amount has changed (i.e. `false` if the node is
+    /// already scrolled to the end).
+    pub(crate) fn scroll_node(&mut self, scroll_id: &ExternalScrollId, scroll_by_x: f32, scroll_by_y: f32) -> bool {
+        match self.0.get_mut(scroll_id) {
+            Some(entry) => {
+                let old = (entry.scroll_amount_x, entry.scroll_amount_y);
+                entry.add(scroll_by_x, scroll_by_y);
+                old != (entry.scroll_amount_x, entry.scroll_amount_y)
+            },
+            None => false,
         }
     }
 
+    /// Inserts a new scroll state or, if the node was already scrolled, updates how far the
+    /// node can be scrolled (the content may have changed since the last frame)
     pub(crate) fn ensure_initialized_scroll_state(&mut self, scroll_id: ExternalScrollId, overflow_x: f32, overflow_y: f32) {
-        self.0.entry(scroll_id).or_insert_with(|| ScrollState::new(overflow_x, overflow_y));
+        self.0.entry(scroll_id)
+            .and_modify(|state| state.set_overflow(overflow_x, overflow_y))
+            .or_insert_with(|| ScrollState::new(overflow_x, overflow_y));
+    }
+
+    /// Sets the scroll amount of a node (clamped to the scrollable area), used when
+    /// the scrollbar thumb is dragged. Returns whether the scroll amount has changed.
+    pub(crate) fn set_scroll_amount(&mut self, scroll_id: &ExternalScrollId, scroll_x: f32, scroll_y: f32) -> bool {
+        let (old_x, old_y) = match self.0.get(scroll_id) {
+            Some(entry) => (entry.scroll_amount_x, entry.scroll_amount_y),
+            None => return false,
+        };
+        self.scroll_node(scroll_id, scroll_x - old_x, scroll_y - old_y)
     }
 
     /// Removes all scroll states that weren't used in the last frame
@@ -447,6 +514,12 @@ impl ScrollState {
         self.scroll_amount_x = self.overflow_x.min(self.scroll_amount_x + x).max(0.0);
         self.scroll_amount_y = self.overflow_y.min(self.scroll_amount_y + y).max(0.0);
     }
+
+    fn set_overflow(&mut self, overflow_x: f32, overflow_y: f32) {
+        self.overflow_x = overflow_x;
+        self.overflow_y = overflow_y;
+        self.add(0.0, 0.0);
+    }
 }
 
 impl Default for ScrollState {
@@ -463,11 +536,27 @@ impl Default for ScrollState {
 
 pub(crate) struct WindowInternal {
     pub(crate) last_scrolled_nodes: ScrolledNodes,
+    /// The scrollbar thumb that is currently being dragged with the mouse, if any
+    pub(crate) scrollbar_drag: Option<ScrollbarDrag>,
     pub(crate) epoch: Epoch,
     pub(crate) pipeline_id: PipelineId,
     pub(crate) document_id: DocumentId,
 }
 
+/// State of a scrollbar thumb that is dragged with the mouse
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct ScrollbarDrag {
+    /// The node that is scrolled by the thumb
+    pub(crate) scroll_id: ExternalScrollId,
+    pub(crate) axis: ScrollbarAxis,
+    /// Cursor position (along the axis of the scrollbar) when the thumb was grabbed
+    pub(crate) start_cursor_pos: f32,
+    /// Scroll amount (along the axis of the scrollbar) when the thumb was grabbed
+    pub(crate) start_scroll_amount: f32,
+    /// See `ScrollbarGeometry::scroll_per_thumb_px`
+    pub(crate) scroll_per_thumb_px: f32,
+}
+
 // TODO: Right now it's not very ergonomic to cache shaders between
 // renderers - notify webrender about this.
 const WR_SHADER_CACHE: Option<&mut WrShaders> = None;
@@ -603,6 +692,7 @@ impl<'a, T: Layout> Window<T> {
                 pipeline_id: pipeline_id,
                 document_id: document_id,
                 last_scrolled_nodes: ScrolledNodes::default(),
+                scrollbar_drag: None,
             },
             marker: PhantomData,
         };
@@ -626,6 +716,19 @@ impl<'a, T: Layout> Window<T> {
         Ok(window)
     }
 
+    /// Replaces the style of this window, for example to switch between a light and a dark
+    /// theme. The window is not recreated, the new style is applied on the next layout.
+    ///
+    /// Stops the hot-reloading of the style (if the window has a hot-reloader),
+    /// otherwise the next reload would overwrite the new style.
+    pub fn set_css(&mut self, css: Css) {
+        #[cfg(debug_assertions)] {
+            replace_hot_reloaded_css(&mut self.css, &mut self.css_loader, css);
+        } #[cfg(not(debug_assertions))] {
+            replace_css(&mut self.css, css);
+        }
+    }
+
     /// Returns an iterator over all given monitors
     pub fn get_available_monitors() -> MonitorIter {
         MonitorIter {
@@ -1044,4 +1147,46 @@ fn linux_get_hidpi_factor(monitor: &MonitorId, events_loop: &EventsLoop) -> f64
 
     let options = [winit_hidpi_factor, qt_font_dpi, gsettings_dpi_factor, xft_dpi];
     options.into_iter().filter_map(|x| *x).next().unwrap_or(winit_dpi)
-}
\ No newline at end of file
+}
+
+/// Sorts the new style by specificity and replaces the current style with it
+fn replace_css(css: &mut Css, mut new_css: Css) {
+    new_css.sort_by_specificity();
+    *css = new_css;
+}
+
+/// Replaces the style like `replace_css` and stops the hot-reloading of the style,
+/// otherwise the next reload would overwrite the new style
+#[cfg(debug_assertions)]
+fn replace_hot_reloaded_css(css: &mut Css, css_loader: &mut Option<Box<dyn HotReloadHandler>>, new_css: Css) {
+    replace_css(css, new_css);
+    *css_loader = None;
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_replace_hot_reloaded_css() {
+    use std::time::Duration;
+    use azul_css::{CssRuleBlock, CssPath, CssPathSelector, NodeTypePath};
+
+    struct TestReloader { }
+
+    impl HotReloadHandler for TestReloader {
+        fn reload_style(&mut self) -> Result<Css, String> { Ok(Css::default()) }
+        fn get_reload_interval(&self) -> Duration { Duration::from_millis(500) }
+    }
+
+    let rule = |selector| CssRuleBlock::new(CssPath { selectors: vec![selector] }, Vec::new());
+    let new_css = Css {
+        stylesheets: vec![vec![rule(CssPathSelector::Id("a".into())), rule(CssPathSelector::Type(NodeTypePath::Div))].into()],
+    };
+
+    let mut css = Css::default();
+    let mut css_loader: Option<Box<dyn HotReloadHandler>> = Some(Box::new(TestReloader { }));
+    replace_hot_reloaded_css(&mut css, &mut css_loader, new_css.clone());
+
+    let mut sorted_css = new_css;
+    sorted_css.sort_by_specificity();
+    assert_eq!(css, sorted_css);
+    assert!(css_loader.is_none());
+}